@@ -1,7 +1,7 @@
 use std::fmt;
 use crate::lexer::Span;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Schema {
     pub title: Option<String>,
     pub tables: Vec<Table>,
@@ -26,6 +26,7 @@ impl Schema {
 pub struct Table {
     pub name: String,
     pub columns: Vec<Column>,
+    pub attributes: Vec<TableAttribute>,
     pub span: Option<Span>,
 }
 
@@ -34,9 +35,40 @@ impl Table {
         Table {
             name,
             columns: Vec::new(),
+            attributes: Vec::new(),
             span: Some(span),
         }
     }
+    
+    pub fn color(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|a| match a {
+            TableAttribute::Color(c) => Some(c.as_str()),
+            _ => None,
+        })
+    }
+    
+    pub fn icon(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|a| match a {
+            TableAttribute::Icon(i) => Some(i.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// Table-level style attributes, e.g. `table Users [color=#3b82f6, icon="user"]`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableAttribute {
+    Color(String),  // Hex colour including the leading '#'
+    Icon(String),
+}
+
+impl fmt::Display for TableAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TableAttribute::Color(c) => write!(f, "color={}", c),
+            TableAttribute::Icon(i) => write!(f, "icon=\"{}\"", i),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -84,6 +116,8 @@ pub enum DataType {
 }
 
 impl DataType {
+    // Never fails: unknown names become custom types
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "string" | "str" => DataType::String,
@@ -368,6 +402,21 @@ mod tests {
         assert!(table.span.is_some());
     }
 
+    #[test]
+    fn test_table_style_attributes() {
+        let span = Span { line: 1, column: 1, length: 10 };
+        let mut table = Table::with_span("Users".to_string(), span);
+        assert!(table.color().is_none());
+        assert!(table.icon().is_none());
+        
+        table.attributes.push(TableAttribute::Color("#3b82f6".to_string()));
+        table.attributes.push(TableAttribute::Icon("user".to_string()));
+        assert_eq!(table.color(), Some("#3b82f6"));
+        assert_eq!(table.icon(), Some("user"));
+        assert_eq!(table.attributes[0].to_string(), "color=#3b82f6");
+        assert_eq!(table.attributes[1].to_string(), "icon=\"user\"");
+    }
+    
     #[test]
     fn test_column_creation() {
         let span = Span { line: 1, column: 1, length: 10 };
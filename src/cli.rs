@@ -0,0 +1,866 @@
+// The `free-erd` command-line tool
+use crate::{ast, codegen, diff, docs, formats, migrate, renderer, seed, sql, writer};
+#[cfg(feature = "viewer")]
+use crate::editor;
+use crate::parser::Parser;
+use crate::interpreter::Interpreter;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const VERSION: &str = "0.2.1 BETA";
+
+/// Run the command given in the process arguments
+pub fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    
+    // Machine-readable output goes to stdout on its own, without the banner
+    let sql_to_stdout = args.get(1).map(String::as_str) == Some("migrate") && flag_value(&args, &["--out-dir"]).is_none();
+    if flag_value(&args, &["--format"]) != Some("json") && !sql_to_stdout {
+        print_banner();
+    }
+    
+    if args.len() < 2 {
+        print_usage();
+        return;
+    }
+    
+    let command = &args[1];
+    
+    match command.as_str() {
+        "help" | "--help" | "-h" => {
+            print_help();
+        }
+        "check" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd check <file>");
+                std::process::exit(1);
+            }
+            
+            let file_path = &args[2];
+            
+            if let Err(e) = check_file(file_path) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        #[cfg(feature = "viewer")]
+        "run" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd run <filename> | free-erd run --compare <old.frd> <new.frd>");
+                std::process::exit(1);
+            }
+            
+            let theme = flag_value(&args, &["--theme"]);
+            let result = if args[2] == "--compare" {
+                if args.len() < 5 {
+                    eprintln!("❌ Error: --compare needs two file paths");
+                    std::process::exit(1);
+                }
+                open_compare_window(&args[3], &args[4], theme)
+            } else {
+                open_window(&args[2], theme)
+            };
+            
+            if let Err(e) = result {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        #[cfg(not(feature = "viewer"))]
+        "run" => {
+            eprintln!("❌ Error: free-erd was built without the viewer feature");
+            std::process::exit(1);
+        }
+        "export" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd export <filename> [--format svg|mermaid|dot|plantuml|dbml|prisma|graphql|cypher|html|pdf|drawio] [-o <output>] [--theme <name|file.toml>] [--examples] [--paper <size>] [--orientation <portrait|landscape>] [--tile] [--scale <percent>] [--font <file.ttf>]");
+                std::process::exit(1);
+            }
+            
+            let file_path = &args[2];
+            let output = flag_value(&args, &["-o", "--output"]);
+            let theme = flag_value(&args, &["--theme"]);
+            let format = flag_value(&args, &["--format"]).unwrap_or("svg");
+            let examples = args.iter().any(|a| a == "--examples");
+            let font = flag_value(&args, &["--font"]);
+            let pdf = match pdf_options(&args) {
+                Ok(pdf) => pdf,
+                Err(e) => {
+                    eprintln!("❌ Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            
+            if let Err(e) = export_file(file_path, output, theme, format, examples, &pdf, font) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "diff" => {
+            if args.len() < 4 {
+                eprintln!("❌ Error: Missing file paths");
+                eprintln!("Usage: free-erd diff <old.frd> <new.frd> [--format text|json]");
+                std::process::exit(1);
+            }
+            
+            let format = flag_value(&args, &["--format"]).unwrap_or("text");
+            
+            if let Err(e) = diff_files(&args[2], &args[3], format) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "migrate" => {
+            if args.len() < 4 {
+                eprintln!("❌ Error: Missing file paths");
+                eprintln!("Usage: free-erd migrate <old.frd> <new.frd> [--dialect postgres|mysql] [--out-dir <dir>]");
+                std::process::exit(1);
+            }
+            
+            let dialect = flag_value(&args, &["--dialect"]).unwrap_or("postgres");
+            let out_dir = flag_value(&args, &["--out-dir"]);
+            
+            if let Err(e) = migrate_files(&args[2], &args[3], dialect, out_dir) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "codegen" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd codegen <filename> --target <{}> [--out-dir <dir>] [--enum <type>=<a,b,...>]", codegen::TARGETS.join("|"));
+                std::process::exit(1);
+            }
+            
+            let Some(target) = flag_value(&args, &["--target"]) else {
+                eprintln!("❌ Error: Missing --target ({})", codegen::TARGETS.join(", "));
+                std::process::exit(1);
+            };
+            let out_dir = flag_value(&args, &["--out-dir"]).unwrap_or(".");
+            let enums = flag_values(&args, &["--enum"]);
+            
+            if let Err(e) = generate_code(&args[2], target, out_dir, &enums) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "docs" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd docs <filename> [-o <dir>] [--theme <name|file.toml>]");
+                std::process::exit(1);
+            }
+            
+            let out_dir = flag_value(&args, &["-o", "--output"]).unwrap_or("docs");
+            let theme = flag_value(&args, &["--theme"]);
+            
+            if let Err(e) = generate_docs(&args[2], out_dir, theme) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "seed" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd seed <filename> [--rows <n>] [--format sql|csv] [--seed <n>] [--dialect postgres|mysql] [-o <output>]");
+                std::process::exit(1);
+            }
+            
+            let rows = flag_value(&args, &["--rows"]).unwrap_or("10");
+            let format = flag_value(&args, &["--format"]).unwrap_or("sql");
+            let seed = flag_value(&args, &["--seed"]).unwrap_or("42");
+            let dialect = flag_value(&args, &["--dialect"]).unwrap_or("postgres");
+            let output = flag_value(&args, &["-o", "--output"]);
+            
+            if let Err(e) = seed_file(&args[2], rows, format, seed, dialect, output) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "import-mermaid" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd import-mermaid <file.mmd|file.md> [-o <output.frd>] [--force]");
+                std::process::exit(1);
+            }
+            
+            let output = flag_value(&args, &["-o", "--output"]);
+            let force = args.iter().any(|a| a == "--force");
+            
+            if let Err(e) = import_mermaid(&args[2], output, force) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "import-dbml" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd import-dbml <file.dbml> [-o <output.frd>] [--force]");
+                std::process::exit(1);
+            }
+            
+            let output = flag_value(&args, &["-o", "--output"]);
+            let force = args.iter().any(|a| a == "--force");
+            
+            if let Err(e) = import_dbml(&args[2], output, force) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "import-prisma" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd import-prisma <schema.prisma> [-o <output.frd>] [--force]");
+                std::process::exit(1);
+            }
+            
+            let output = flag_value(&args, &["-o", "--output"]);
+            let force = args.iter().any(|a| a == "--force");
+            
+            if let Err(e) = import_prisma(&args[2], output, force) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "import-sqlite" => {
+            if args.len() < 3 {
+                eprintln!("❌ Error: Missing file path");
+                eprintln!("Usage: free-erd import-sqlite <app.db> [-o <output.frd>] [--force]");
+                std::process::exit(1);
+            }
+            
+            let output = flag_value(&args, &["-o", "--output"]);
+            let force = args.iter().any(|a| a == "--force");
+            
+            if let Err(e) = import_sqlite(&args[2], output, force) {
+                eprintln!("❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "about" => {
+            print_about();
+        }
+        _ => {
+            eprintln!("❌ Unknown command: {}", command);
+            eprintln!("Run 'free-erd help' for usage information.");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_banner() {
+    let quote = get_random_quote();
+    println!(r#"
+/$$$$$$$$                            /$$$$$$$$ /$$$$$$$  /$$$$$$$ 
+| $$_____/                           | $$_____/| $$__  $$| $$__  $$
+| $$     /$$$$$$   /$$$$$$   /$$$$$$ | $$      | $$  \ $$| $$  \ $$
+| $$$$$ /$$__  $$ /$$__  $$ /$$__  $$| $$$$$   | $$$$$$$/| $$  | $$
+| $$__/| $$  \__/| $$$$$$$$| $$$$$$$$| $$__/   | $$__  $$| $$  | $$
+| $$   | $$      | $$_____/| $$_____/| $$      | $$  \ $$| $$  | $$
+| $$   | $$      |  $$$$$$$|  $$$$$$$| $$$$$$$$| $$  | $$| $$$$$$$/
+|__/   |__/       \_______/ \_______/|________/|__/  |__/|_______/ 
+    "#);
+    println!("Version {}", VERSION);
+    if let Some(q) = quote {
+        println!("  {}", q);
+    }
+    println!("{}", "=".repeat(107));
+}
+
+fn check_file(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let path = Path::new(file_path);
+    
+    if !path.exists() {
+        return Err(format!("File not found: {}", file_path).into());
+    }
+    
+    println!("\n📂 Reading file: {}", file_path);
+    let content = fs::read_to_string(path)?;
+    
+    println!("🔍 Parsing...");
+    let mut parser = Parser::new(&content);
+    let schema = match parser.parse() {
+        Ok(s) => s,
+        Err(e) => {
+            eprint!("\n{}", e.format_with_source(&content, file_path));
+            return Err(e.into());
+        }
+    };
+    println!("✅ Parsing successful!\n");
+    
+    let interpreter = Interpreter::new(schema);
+    
+    println!("🔍 Validating schema...");
+    match interpreter.validate() {
+        Ok(_) => {
+            println!("✅ Schema is valid!\n");
+            let stats = interpreter.get_statistics();
+            stats.print();
+        }
+        Err(errors) => {
+            eprintln!("\n\x1b[1;31m❌ Validation failed with {} error(s):\x1b[0m\n", errors.len());
+            for error in errors.iter() {
+                eprint!("{}", error.format_with_source(&content, file_path));
+            }
+            return Err("Validation failed".into());
+        }
+    }
+    
+    Ok(())
+}
+
+fn print_usage() {
+    println!("\nUsage:");
+    println!("  free-erd <command> [arguments]\n");
+    println!("Commands:");
+    println!("  run <filename>               - opens the window to view the ERD");
+    println!("  run --compare <old> <new>    - opens <new> with changes since <old> highlighted");
+    println!("  check <filename>             - checks the .frd file");
+    println!("  export <filename>            - exports the ERD to SVG or another format");
+    println!("  diff <old> <new>             - lists schema changes between two .frd files");
+    println!("  migrate <old> <new>          - generates SQL that migrates old to new");
+    println!("  codegen <filename>           - generates ORM code, TypeScript types or JSON Schema");
+    println!("  docs <filename>              - writes an HTML and Markdown data dictionary (-o <dir>, default: docs)");
+    println!("  seed <filename>              - generates sample rows as SQL INSERTs or CSV files");
+    println!("  import-mermaid <file>        - converts a Mermaid erDiagram to .frd");
+    println!("  import-dbml <file>           - converts a DBML (dbdiagram.io) file to .frd");
+    println!("  import-prisma <file>         - converts a Prisma schema to .frd");
+    println!("  import-sqlite <file>         - reads the tables of a SQLite database into .frd");
+    println!("  help                         - Help menu");
+    println!("  about                        - Information about this system\n");
+    println!("Options:");
+    println!("  -o, --output <file>          - output path for export or import (default: <filename> with the format's extension)");
+    println!("  --theme <name|file.toml>     - light, dark, high-contrast or a TOML theme file");
+    println!("  --format <text|json>         - diff output format (default: text)");
+    println!("  --format <svg|mermaid|dot|plantuml|dbml|prisma|graphql|cypher|html|pdf|drawio> - export format (default: svg)");
+    println!("  --examples                   - add commented sample CREATE statements per edge to cypher export");
+    println!("  --paper <{}>  - PDF paper size (default: a4)", renderer::Paper::NAMES.join("|"));
+    println!("  --orientation <portrait|landscape> - PDF page orientation (default: landscape)");
+    println!("  --tile                       - print a PDF across several pages with crop marks instead of fitting one page");
+    println!("  --scale <percent>            - zoom for --tile, where 100 prints the canvas at 96 dpi (default: 100)");
+    println!("  --font <file.ttf>            - TrueType font to embed in PDF export (default: the canvas font)");
+    println!("  --dialect <postgres|mysql>   - SQL dialect for migrate and seed (default: postgres)");
+    println!("  --rows <n>                   - rows per table for seed (default: 10)");
+    println!("  --format <sql|csv>           - seed output: one .sql file, or a directory of .csv files (default: sql)");
+    println!("  --seed <n>                   - random seed; the same seed gives the same sample data (default: 42)");
+    println!("  --out-dir <dir>              - write migrate output as <dir>/<timestamp>_<name>/up.sql and down.sql,");
+    println!("                                 or codegen output into <dir> (default: current directory)");
+    println!("  --force                      - let import overwrite an existing .frd file");
+    println!("  --target <name>              - codegen target: {}", codegen::TARGETS.join(", "));
+    println!("  --enum <type>=<a,b,...>      - values of a custom enum type for codegen (repeatable)\n");
+}
+
+/// PDF page settings from `--paper`, `--orientation`, `--tile` and `--scale`
+fn pdf_options(args: &[String]) -> Result<renderer::PdfOptions, String> {
+    let mut options = renderer::PdfOptions::default();
+    if let Some(paper) = flag_value(args, &["--paper"]) {
+        options.paper = renderer::Paper::parse(paper)
+            .ok_or_else(|| format!("Unknown paper size '{}' (expected {})", paper, renderer::Paper::NAMES.join(", ")))?;
+    }
+    options.landscape = match flag_value(args, &["--orientation"]).unwrap_or("landscape") {
+        "landscape" => true,
+        "portrait" => false,
+        other => return Err(format!("Unknown orientation '{}' (expected portrait or landscape)", other)),
+    };
+    if args.iter().any(|a| a == "--tile") {
+        let percent = flag_value(args, &["--scale"]).unwrap_or("100");
+        let zoom = percent.trim_end_matches('%').parse::<f32>().ok()
+            .filter(|p| *p > 0.0)
+            .ok_or_else(|| format!("--scale expects a positive percentage but got '{}'", percent))?;
+        options.layout = renderer::PageLayout::Tile { zoom: zoom / 100.0 };
+    }
+    Ok(options)
+}
+
+/// Value following any of the given flags, e.g. `--theme dark`
+fn flag_value<'a>(args: &'a [String], names: &[&str]) -> Option<&'a str> {
+    args.iter()
+        .position(|a| names.contains(&a.as_str()))
+        .and_then(|i| args.get(i + 1))
+        .map(|v| v.as_str())
+}
+
+/// Values following every occurrence of the given flags
+fn flag_values<'a>(args: &'a [String], names: &[&str]) -> Vec<&'a str> {
+    args.windows(2)
+        .filter(|pair| names.contains(&pair[0].as_str()))
+        .map(|pair| pair[1].as_str())
+        .collect()
+}
+
+fn resolve_theme(theme: Option<&str>) -> Result<renderer::Theme, Box<dyn std::error::Error>> {
+    match theme {
+        Some(value) => Ok(renderer::Theme::resolve(value)?),
+        None => Ok(renderer::Theme::default()),
+    }
+}
+
+fn print_help() {
+    println!("\n🎨 FreeERD - Entity Relationship Diagram DSL Interpreter\n");
+    println!("A lightweight domain-specific language for defining database schemas");
+    println!("and entity relationships in a simple, human-readable format.\n");
+    
+    print_usage();
+}
+
+fn print_about() {
+    println!("\n🎨 FreeERD - Free Entity Relationship Diagram Tool");
+    println!("Version: {}\n", VERSION);
+    println!("Description:");
+    println!("  A lightweight, open-source tool for creating Entity Relationship Diagrams");
+    println!("  using a simple domain-specific language. FreeERD allows you to define");
+    println!("  database schemas in a human-readable format and generate beautiful SVG");
+    println!("  diagrams automatically.\n");
+    println!("License: GNU General Public License v2.0");
+    println!("  This program is free software; you can redistribute it and/or modify it");
+    println!("  under the terms of the GNU General Public License version 2 as published");
+    println!("  by the Free Software Foundation. This program is distributed in the hope");
+    println!("  that it will be useful, but WITHOUT ANY WARRANTY; without even the implied");
+    println!("  warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.");
+    println!("  See the LICENSE file for more details.\n");
+    println!("Repository: https://github.com/JustinVijar/FreeERD");
+    println!("\nFor help and usage information, run: free-erd help\n");
+}
+
+fn get_random_quote() -> Option<String> {
+    // Embed quotes.txt content at compile time
+    const QUOTES: &str = include_str!("quotes.txt");
+    
+    let quotes: Vec<&str> = QUOTES
+        .lines()
+        .filter(|line| !line.trim().is_empty() && line.trim() != ".")
+        .collect();
+    
+    if quotes.is_empty() {
+        return None;
+    }
+    
+    // Use current time as seed for pseudo-random selection
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    
+    let index = (seed as usize) % quotes.len();
+    Some(quotes[index].to_string())
+}
+
+#[cfg(feature = "viewer")]
+fn open_window(file_path: &str, theme: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let theme = resolve_theme(theme)?;
+    let schema = load_schema(file_path)?;
+    let erd_graph = build_erd_graph(&schema)?;
+    
+    // Re-read the source so saving from the editor can keep its comments and layout
+    let source = fs::read_to_string(file_path)?;
+    let document = editor::Document::new(std::path::PathBuf::from(file_path), source, schema.clone());
+    
+    println!("🪟 Opening window...");
+    let title = schema.title.clone().unwrap_or_else(|| "Untitled Schema".to_string());
+    renderer::render_window(erd_graph, title, theme, Some(document))?;
+    
+    Ok(())
+}
+
+/// Open the new schema with added, removed and changed items highlighted
+#[cfg(feature = "viewer")]
+fn open_compare_window(old_path: &str, new_path: &str, theme: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let theme = resolve_theme(theme)?;
+    let old_schema = load_schema(old_path)?;
+    let new_schema = load_schema(new_path)?;
+    
+    println!("🎨 Building comparison graph...");
+    let erd_graph = renderer::ErdGraph::from_comparison(&old_schema, &new_schema)?;
+    
+    println!("🪟 Opening window...");
+    let title = format!("{} (compared with {})", new_schema.title.as_deref().unwrap_or("Untitled Schema"), old_path);
+    renderer::render_window(erd_graph, title, theme, None)?;
+    
+    Ok(())
+}
+
+fn export_file(file_path: &str, output: Option<&str>, theme: Option<&str>, format: &str, examples: bool, pdf: &renderer::PdfOptions, font: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let extension = match format {
+        "svg" => "svg",
+        "mermaid" => "mmd",
+        "dot" => "dot",
+        "plantuml" => "puml",
+        "dbml" => "dbml",
+        "prisma" => "prisma",
+        "graphql" => "graphql",
+        "cypher" => "cypher",
+        "html" => "html",
+        "pdf" => "pdf",
+        "drawio" => "drawio",
+        other => return Err(format!("Unknown export format '{}' (expected svg, mermaid, dot, plantuml, dbml, prisma, graphql, cypher, html, pdf or drawio)", other).into()),
+    };
+    
+    let theme = resolve_theme(theme)?;
+    let schema = load_schema(file_path)?;
+    
+    let output = match output {
+        Some(path) => path.to_string(),
+        None => Path::new(file_path).with_extension(extension).to_string_lossy().to_string(),
+    };
+    
+    let content: Vec<u8> = match format {
+        "mermaid" => {
+            println!("🧜 Exporting Mermaid erDiagram...");
+            formats::mermaid::to_mermaid(&schema).into_bytes()
+        }
+        "dot" => {
+            println!("🕸️  Exporting Graphviz DOT...");
+            formats::dot::to_dot(&schema).into_bytes()
+        }
+        "plantuml" => {
+            println!("🌱 Exporting PlantUML...");
+            formats::plantuml::to_plantuml(&schema).into_bytes()
+        }
+        "dbml" => {
+            println!("📐 Exporting DBML...");
+            formats::dbml::to_dbml(&schema).into_bytes()
+        }
+        "prisma" => {
+            println!("🔷 Exporting Prisma schema...");
+            formats::prisma::to_prisma(&schema).into_bytes()
+        }
+        "graphql" => {
+            println!("💠 Exporting GraphQL schema...");
+            formats::graphql::to_graphql(&schema).into_bytes()
+        }
+        "cypher" => {
+            println!("🔗 Exporting Cypher constraints...");
+            formats::cypher::to_cypher(&schema, examples).into_bytes()
+        }
+        "html" => {
+            let erd_graph = build_erd_graph(&schema)?;
+            println!("🌐 Exporting interactive HTML with theme '{}'...", theme.name);
+            let title = schema.title.clone().unwrap_or_default();
+            let svg = renderer::export_svg_with(&erd_graph, &title, &theme, renderer::SvgOptions {
+                annotate: true,
+                ..Default::default()
+            })?;
+            formats::html::to_html(&title, &svg).into_bytes()
+        }
+        "pdf" => {
+            let erd_graph = build_erd_graph(&schema)?;
+            println!("📄 Exporting PDF with theme '{}'...", theme.name);
+            let title = schema.title.clone().unwrap_or_default();
+            renderer::export_pdf(&erd_graph, &title, &theme, pdf, font.map(Path::new))?
+        }
+        "drawio" => {
+            let erd_graph = build_erd_graph(&schema)?;
+            println!("🧩 Exporting draw.io diagram with theme '{}'...", theme.name);
+            let title = schema.title.clone().unwrap_or_default();
+            renderer::export_drawio(&erd_graph, &title, &theme).into_bytes()
+        }
+        _ => {
+            let erd_graph = build_erd_graph(&schema)?;
+            println!("🖼️  Exporting with theme '{}'...", theme.name);
+            let title = schema.title.clone().unwrap_or_default();
+            renderer::export_svg(&erd_graph, &title, &theme)?.into_bytes()
+        }
+    };
+    fs::write(&output, content)?;
+    println!("✅ Exported to {}", output);
+    
+    Ok(())
+}
+
+/// Write generated source files for a codegen target under `out_dir`.
+/// Each `--enum` value lists a custom type's values as `name=a,b,c`.
+fn generate_code(file_path: &str, target: &str, out_dir: &str, enums: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut options = codegen::Options::default();
+    for value in enums {
+        let (name, values) = value.split_once('=')
+            .ok_or_else(|| format!("--enum expects <type>=<a,b,...> but got '{}'", value))?;
+        options.enums.insert(name.to_string(), values.split(',').map(|v| v.trim().to_string()).collect());
+    }
+    
+    let schema = load_schema(file_path)?;
+    let files = codegen::generate(&schema, target, &options)?;
+    
+    println!("⚙️  Generating {} code...", target);
+    for file in &files {
+        let path = Path::new(out_dir).join(&file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &file.content)?;
+        println!("  ✏️  {}", path.display());
+    }
+    println!("✅ Generated {} file(s)", files.len());
+    
+    Ok(())
+}
+
+/// Write the data dictionary site: HTML and Markdown pages plus the diagram
+fn generate_docs(file_path: &str, out_dir: &str, theme: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let theme = resolve_theme(theme)?;
+    let schema = load_schema(file_path)?;
+    let erd_graph = build_erd_graph(&schema)?;
+    let title = schema.title.clone().unwrap_or_default();
+    
+    println!("📚 Writing data dictionary...");
+    let inline_prefix = format!("#{}", docs::ANCHOR_PREFIX);
+    let inline_svg = renderer::export_svg_with(&erd_graph, &title, &theme, renderer::SvgOptions {
+        table_link_prefix: Some(&inline_prefix),
+        ..Default::default()
+    })?;
+    let diagram_prefix = format!("index.html#{}", docs::ANCHOR_PREFIX);
+    let diagram_svg = renderer::export_svg_with(&erd_graph, &title, &theme, renderer::SvgOptions {
+        table_link_prefix: Some(&diagram_prefix),
+        ..Default::default()
+    })?;
+    
+    fs::create_dir_all(out_dir)?;
+    let files = docs::site(&schema, &inline_svg, &diagram_svg);
+    for file in &files {
+        let path = Path::new(out_dir).join(&file.path);
+        fs::write(&path, &file.content)?;
+        println!("  ✏️  {}", path.display());
+    }
+    println!("✅ Documented {} table(s) in {}", schema.tables.len(), out_dir);
+    
+    Ok(())
+}
+
+/// Write sample rows for every table as SQL INSERTs, or as one CSV file per
+/// table in a directory
+fn seed_file(file_path: &str, rows: &str, format: &str, seed: &str, dialect: &str, output: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let rows: usize = rows.parse().map_err(|_| format!("--rows expects a number but got '{}'", rows))?;
+    let seed: u64 = seed.parse().map_err(|_| format!("--seed expects a number but got '{}'", seed))?;
+    let dialect = sql::Dialect::from_name(dialect)
+        .ok_or_else(|| format!("Unknown dialect '{}' (expected postgres or mysql)", dialect))?;
+    if format != "sql" && format != "csv" {
+        return Err(format!("Unknown seed format '{}' (expected sql or csv)", format).into());
+    }
+    
+    let schema = load_schema(file_path)?;
+    println!("🎲 Generating {} row(s) per table with seed {}...", rows, seed);
+    let data = seed::SeedData::generate(&schema, rows, seed);
+    for warning in &data.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    
+    if format == "sql" {
+        let output = match output {
+            Some(path) => path.to_string(),
+            None => Path::new(file_path).with_extension("seed.sql").to_string_lossy().to_string(),
+        };
+        fs::write(&output, data.to_sql(dialect))?;
+        println!("✅ Wrote sample data for {} table(s) to {}", data.tables.len(), output);
+    } else {
+        let out_dir = output.unwrap_or("seed");
+        fs::create_dir_all(out_dir)?;
+        for file in data.to_csv() {
+            let path = Path::new(out_dir).join(&file.path);
+            fs::write(&path, &file.content)?;
+            println!("  ✏️  {}", path.display());
+        }
+        println!("✅ Wrote sample data for {} table(s) to {}", data.tables.len(), out_dir);
+    }
+    
+    Ok(())
+}
+
+/// Convert a Mermaid erDiagram into a .frd file
+fn import_mermaid(file_path: &str, output: Option<&str>, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    println!("📂 Reading file: {}", file_path);
+    let content = fs::read_to_string(file_path)?;
+    
+    let schema = formats::mermaid::from_mermaid(&content)
+        .map_err(|e| format!("{}: {}", file_path, e))?;
+    write_imported(file_path, output, force, &schema)
+}
+
+/// Convert a DBML file into a .frd file, listing anything that couldn't be kept
+fn import_dbml(file_path: &str, output: Option<&str>, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    println!("📂 Reading file: {}", file_path);
+    let content = fs::read_to_string(file_path)?;
+    
+    let imported = formats::dbml::from_dbml(&content)
+        .map_err(|e| format!("{}: {}", file_path, e))?;
+    for warning in &imported.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    write_imported(file_path, output, force, &imported.schema)
+}
+
+/// Convert a Prisma schema into a .frd file, listing anything that couldn't be kept
+fn import_prisma(file_path: &str, output: Option<&str>, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    println!("📂 Reading file: {}", file_path);
+    let content = fs::read_to_string(file_path)?;
+    
+    let imported = formats::prisma::from_prisma(&content)
+        .map_err(|e| format!("{}: {}", file_path, e))?;
+    for warning in &imported.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    write_imported(file_path, output, force, &imported.schema)
+}
+
+fn import_sqlite(file_path: &str, output: Option<&str>, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    println!("📂 Reading database: {}", file_path);
+    if !Path::new(file_path).is_file() {
+        return Err(format!("{}: no such file", file_path).into());
+    }
+    
+    let imported = formats::sqlite::from_sqlite(Path::new(file_path))
+        .map_err(|e| format!("{}: {}", file_path, e))?;
+    for warning in &imported.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    write_imported(file_path, output, force, &imported.schema)
+}
+
+/// Write an imported schema as .frd, next to the source file by default.
+/// An existing file is only replaced with `--force`.
+fn write_imported(file_path: &str, output: Option<&str>, force: bool, schema: &ast::Schema) -> Result<(), Box<dyn std::error::Error>> {
+    let output = match output {
+        Some(path) => path.to_string(),
+        None => Path::new(file_path).with_extension("frd").to_string_lossy().to_string(),
+    };
+    if Path::new(&output).exists() && !force {
+        return Err(format!("{} already exists; pass --force to overwrite it", output).into());
+    }
+    
+    let content = writer::to_frd(schema)?;
+    Parser::new(&content).parse()
+        .map_err(|e| format!("Imported schema does not parse back: {}", e))?;
+    fs::write(&output, content)?;
+    println!("✅ Imported {} table(s) and {} relationship(s) into {}", schema.tables.len(), schema.relationships.len(), output);
+    
+    Ok(())
+}
+
+fn diff_files(old_path: &str, new_path: &str, format: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let json = match format {
+        "text" => false,
+        "json" => true,
+        other => return Err(format!("Unknown diff format '{}' (expected text or json)", other).into()),
+    };
+    
+    let (old_source, old_schema) = validate_quietly(old_path)?;
+    let (new_source, new_schema) = validate_quietly(new_path)?;
+    let diff = diff::SchemaDiff::between(&old_schema, &new_schema);
+    
+    if json {
+        print!("{}", diff.to_json());
+    } else if diff.is_empty() {
+        println!("✅ No schema changes between {} and {}", old_path, new_path);
+    } else {
+        println!("\n🔀 Changes from {} to {}:\n", old_path, new_path);
+        print!("{}", diff.format_with_source(&old_source, old_path, &new_source, new_path));
+    }
+    
+    Ok(())
+}
+
+fn migrate_files(old_path: &str, new_path: &str, dialect: &str, out_dir: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let dialect = sql::Dialect::from_name(dialect)
+        .ok_or_else(|| format!("Unknown dialect '{}' (expected postgres or mysql)", dialect))?;
+    
+    // Without --out-dir the SQL goes to stdout, so it must be the only thing there
+    let (old_schema, new_schema) = match out_dir {
+        Some(_) => (load_schema(old_path)?, load_schema(new_path)?),
+        None => (validate_quietly(old_path)?.1, validate_quietly(new_path)?.1),
+    };
+    let up = migrate::Migration::between(&old_schema, &new_schema, dialect);
+    
+    for warning in &up.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    if up.is_empty() {
+        eprintln!("✅ No schema changes to migrate");
+        return Ok(());
+    }
+    
+    match out_dir {
+        Some(dir) => {
+            // Paired up/down scripts in a timestamped directory, as sqlx and diesel lay them out
+            let name = Path::new(new_path).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let dir = Path::new(dir).join(format!("{}_{}", chrono::Local::now().format("%Y%m%d%H%M%S"), name));
+            fs::create_dir_all(&dir)?;
+            
+            let down = migrate::Migration::between(&new_schema, &old_schema, dialect);
+            fs::write(dir.join("up.sql"), up.to_sql())?;
+            fs::write(dir.join("down.sql"), down.to_sql())?;
+            println!("✅ Wrote {} and down.sql", dir.join("up.sql").display());
+        }
+        None => print!("{}", up.to_sql()),
+    }
+    
+    Ok(())
+}
+
+/// Read and parse a schema file without progress output, so commands can
+/// print machine-readable results. Diagnostics still go to stderr.
+fn parse_quietly(file_path: &str) -> Result<(String, ast::Schema), Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| format!("Cannot read {}: {}", file_path, e))?;
+    match Parser::new(&content).parse() {
+        Ok(schema) => Ok((content, schema)),
+        Err(error) => {
+            eprint!("{}", error.format_with_source(&content, file_path));
+            Err("Parsing failed".into())
+        }
+    }
+}
+
+/// Like `parse_quietly`, and also validate the schema
+fn validate_quietly(file_path: &str) -> Result<(String, ast::Schema), Box<dyn std::error::Error>> {
+    let (content, schema) = parse_quietly(file_path)?;
+    if let Err(errors) = Interpreter::new(schema.clone()).validate() {
+        for error in errors.iter() {
+            eprint!("{}", error.format_with_source(&content, file_path));
+        }
+        return Err("Validation failed".into());
+    }
+    Ok((content, schema))
+}
+
+/// Read, parse and validate a schema file, printing diagnostics on failure
+fn load_schema(file_path: &str) -> Result<ast::Schema, Box<dyn std::error::Error>> {
+    println!("📂 Reading file: {}", file_path);
+    let content = fs::read_to_string(file_path)?;
+    
+    println!("🔍 Parsing...");
+    let mut parser = Parser::new(&content);
+    let schema = match parser.parse() {
+        Ok(s) => {
+            println!("✅ Parsing successful!");
+            s
+        }
+        Err(error) => {
+            eprintln!("\n\x1b[1;31m❌ Parsing failed:\x1b[0m\n");
+            eprint!("{}", error.format_with_source(&content, file_path));
+            return Err("Parsing failed".into());
+        }
+    };
+    
+    println!("🔍 Validating schema...");
+    let interpreter = Interpreter::new(schema.clone());
+    if let Err(errors) = interpreter.validate() {
+        eprintln!("\n\x1b[1;31m❌ Validation failed with {} error(s):\x1b[0m\n", errors.len());
+        for error in errors.iter() {
+            eprint!("{}", error.format_with_source(&content, file_path));
+        }
+        return Err("Validation failed".into());
+    }
+    println!("✅ Schema is valid!");
+    
+    Ok(schema)
+}
+
+/// Convert a schema into the graph used by the renderers
+fn build_erd_graph(schema: &ast::Schema) -> Result<renderer::ErdGraph, Box<dyn std::error::Error>> {
+    println!("🎨 Building ERD graph...");
+    let erd_graph = renderer::ErdGraph::from_schema(schema)?;
+    Ok(erd_graph)
+}
+
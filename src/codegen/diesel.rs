@@ -0,0 +1,154 @@
+use super::{pascal_case, rust_ident};
+use crate::ast::*;
+use crate::sql;
+use std::collections::HashSet;
+
+/// Diesel SQL type for a datatype (Postgres flavour)
+fn sql_type(datatype: &DataType) -> String {
+    match datatype {
+        DataType::String => "Text".to_string(),
+        DataType::Int => "Int4".to_string(),
+        DataType::Bool => "Bool".to_string(),
+        DataType::Double => "Float8".to_string(),
+        DataType::Float => "Float4".to_string(),
+        DataType::Decimal => "Numeric".to_string(),
+        DataType::Date => "Date".to_string(),
+        DataType::Time => "Time".to_string(),
+        DataType::DateTime => "Timestamp".to_string(),
+        DataType::Blob | DataType::TinyBlob | DataType::LargeBlob => "Bytea".to_string(),
+        DataType::Custom(name) => pascal_case(name),
+    }
+}
+
+/// `#[sql_name = "..."]` when the Rust name differs from the database name
+fn sql_name(ident: &str, name: &str, indent: &str) -> String {
+    if ident == name {
+        String::new()
+    } else {
+        format!("{}#[sql_name = \"{}\"]\n", indent, name)
+    }
+}
+
+/// Diesel `schema.rs` with a `table!` per table, `joinable!` per foreign key
+/// and every table allowed in the same query
+pub fn schema_rs(schema: &Schema) -> String {
+    let mut output = String::from("// @generated by FreeERD. Regenerate instead of editing by hand.\n");
+
+    // Custom types need a marker type, as diesel print-schema generates
+    let mut custom_types: Vec<&str> = Vec::new();
+    for column in schema.tables.iter().flat_map(|t| &t.columns) {
+        if let DataType::Custom(name) = &column.datatype {
+            if !custom_types.contains(&name.as_str()) {
+                custom_types.push(name);
+            }
+        }
+    }
+    if !custom_types.is_empty() {
+        output.push_str("\npub mod sql_types {\n");
+        for (i, name) in custom_types.iter().enumerate() {
+            if i > 0 {
+                output.push('\n');
+            }
+            output.push_str("    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]\n");
+            output.push_str(&format!("    #[diesel(postgres_type(name = \"{}\"))]\n", name));
+            output.push_str(&format!("    pub struct {};\n", pascal_case(name)));
+        }
+        output.push_str("}\n");
+    }
+
+    for table in &schema.tables {
+        let ident = rust_ident(&table.name);
+        let keys: Vec<String> = table.columns.iter()
+            .filter(|c| c.is_primary_key())
+            .map(|c| rust_ident(&c.name))
+            .collect();
+
+        output.push_str("\ndiesel::table! {\n");
+        let uses: Vec<String> = table.columns.iter()
+            .filter_map(|c| match &c.datatype {
+                DataType::Custom(name) => Some(pascal_case(name)),
+                _ => None,
+            })
+            .collect();
+        if !uses.is_empty() {
+            let mut seen = HashSet::new();
+            output.push_str("    use diesel::sql_types::*;\n");
+            for name in uses.iter().filter(|n| seen.insert(n.as_str())) {
+                output.push_str(&format!("    use super::sql_types::{};\n", name));
+            }
+            output.push('\n');
+        }
+        output.push_str(&sql_name(&ident, &table.name, "    "));
+        if keys.is_empty() {
+            // Without a key diesel assumes the table has an `id` column
+            output.push_str(&format!("    {} {{\n", ident));
+        } else {
+            output.push_str(&format!("    {} ({}) {{\n", ident, keys.join(", ")));
+        }
+        for column in &table.columns {
+            let column_ident = rust_ident(&column.name);
+            let mut column_type = sql_type(&column.datatype);
+            if sql::is_nullable(column) {
+                column_type = format!("Nullable<{}>", column_type);
+            }
+            output.push_str(&sql_name(&column_ident, &column.name, "        "));
+            output.push_str(&format!("        {} -> {},\n", column_ident, column_type));
+        }
+        output.push_str("    }\n}\n");
+    }
+
+    // Diesel allows one joinable! per pair of tables and none for self-references
+    let mut joined = HashSet::new();
+    let joinables: Vec<String> = sql::foreign_keys(schema).into_iter()
+        .filter(|fk| fk.table != fk.ref_table)
+        .filter(|fk| joined.insert((fk.table.clone(), fk.ref_table.clone())))
+        .map(|fk| format!("diesel::joinable!({} -> {} ({}));\n", rust_ident(&fk.table), rust_ident(&fk.ref_table), rust_ident(&fk.column)))
+        .collect();
+    if !joinables.is_empty() {
+        output.push('\n');
+        output.push_str(&joinables.concat());
+    }
+
+    if schema.tables.len() > 1 {
+        output.push_str("\ndiesel::allow_tables_to_appear_in_same_query!(\n");
+        for table in &schema.tables {
+            output.push_str(&format!("    {},\n", rust_ident(&table.name)));
+        }
+        output.push_str(");\n");
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_schema_rs() {
+        let schema = Parser::new(r#"
+table Users {
+  id: int [pk],
+  email: str,
+  bio: str [nullable],
+  mood: mood_kind
+}
+table Posts {
+  id: int [pk],
+  user_id: int [fk],
+  editor_id: int [fk, nullable]
+}
+Users.id > Posts.user_id
+Users.id > Posts.editor_id
+"#).parse().unwrap();
+
+        let rs = schema_rs(&schema);
+        assert!(rs.contains("pub mod sql_types {\n    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]\n    #[diesel(postgres_type(name = \"mood_kind\"))]\n    pub struct MoodKind;\n}\n"));
+        assert!(rs.contains("diesel::table! {\n    use diesel::sql_types::*;\n    use super::sql_types::MoodKind;\n\n    #[sql_name = \"Users\"]\n    users (id) {\n"));
+        assert!(rs.contains("        bio -> Nullable<Text>,\n        mood -> MoodKind,\n"));
+        assert!(rs.contains("        editor_id -> Nullable<Int4>,\n"));
+        assert!(rs.contains("\ndiesel::joinable!(posts -> users (user_id));\n\ndiesel::allow_tables_to_appear_in_same_query!(\n    users,\n    posts,\n);\n"));
+        assert_eq!(rs.matches("joinable!").count(), 1);
+    }
+}
@@ -0,0 +1,119 @@
+use super::{GeneratedFile, Options};
+use crate::ast::*;
+use crate::diff::json_string;
+use crate::sql;
+
+/// JSON Schema keywords describing a column's values
+fn type_keywords(datatype: &DataType, nullable: bool, options: &Options) -> Vec<String> {
+    let (json_type, extra) = match datatype {
+        DataType::String => ("string", None),
+        DataType::Int => ("integer", None),
+        DataType::Bool => ("boolean", None),
+        DataType::Double | DataType::Float | DataType::Decimal => ("number", None),
+        DataType::Date => ("string", Some("\"format\": \"date\"".to_string())),
+        DataType::Time => ("string", Some("\"format\": \"time\"".to_string())),
+        DataType::DateTime => ("string", Some("\"format\": \"date-time\"".to_string())),
+        DataType::Blob | DataType::TinyBlob | DataType::LargeBlob => ("string", Some("\"contentEncoding\": \"base64\"".to_string())),
+        DataType::Custom(name) => match options.enums.get(name) {
+            Some(values) if !values.is_empty() => {
+                let mut values: Vec<String> = values.iter().map(|v| json_string(v)).collect();
+                if nullable {
+                    values.push("null".to_string());
+                }
+                return vec![format!("\"enum\": [{}]", values.join(", "))];
+            }
+            // Without known values any JSON value is accepted
+            _ => return vec![format!("\"$comment\": {}", json_string(&format!("custom type {}", name)))],
+        },
+    };
+
+    let mut keywords = vec![if nullable {
+        format!("\"type\": [\"{}\", \"null\"]", json_type)
+    } else {
+        format!("\"type\": \"{}\"", json_type)
+    }];
+    keywords.extend(extra);
+    keywords
+}
+
+fn default_keyword(value: &DefaultValue) -> String {
+    match value {
+        DefaultValue::Now => "\"$comment\": \"defaults to the current time\"".to_string(),
+        DefaultValue::True => "\"default\": true".to_string(),
+        DefaultValue::False => "\"default\": false".to_string(),
+        DefaultValue::Null => "\"default\": null".to_string(),
+        DefaultValue::String(s) => format!("\"default\": {}", json_string(s)),
+        DefaultValue::Number(n) => format!("\"default\": {}", n),
+    }
+}
+
+/// JSON Schema (draft 2020-12) document describing one row of a table
+pub fn table_schema(table: &Table, options: &Options) -> String {
+    let mut properties = Vec::new();
+    for column in &table.columns {
+        let mut keywords = type_keywords(&column.datatype, sql::is_nullable(column), options);
+        if let Some(value) = sql::default_value(column) {
+            keywords.push(default_keyword(value));
+        }
+        if column.attributes.contains(&Attribute::AutoIncrement) {
+            keywords.push("\"readOnly\": true".to_string());
+        }
+        properties.push(format!("    {}: {{ {} }}", json_string(&column.name), keywords.join(", ")));
+    }
+
+    let required: Vec<String> = table.columns.iter()
+        .filter(|c| !c.attributes.contains(&Attribute::Nullable))
+        .map(|c| json_string(&c.name))
+        .collect();
+
+    let mut output = String::from("{\n");
+    output.push_str("  \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n");
+    output.push_str(&format!("  \"$id\": {},\n", json_string(&format!("{}.schema.json", table.name))));
+    output.push_str(&format!("  \"title\": {},\n", json_string(&table.name)));
+    output.push_str("  \"type\": \"object\",\n");
+    if properties.is_empty() {
+        output.push_str("  \"properties\": {},\n");
+    } else {
+        output.push_str(&format!("  \"properties\": {{\n{}\n  }},\n", properties.join(",\n")));
+    }
+    output.push_str(&format!("  \"required\": [{}],\n", required.join(", ")));
+    output.push_str("  \"additionalProperties\": false\n}\n");
+    output
+}
+
+/// One `schemas/<table>.schema.json` document per table
+pub fn documents(schema: &Schema, options: &Options) -> Vec<GeneratedFile> {
+    schema.tables.iter()
+        .map(|table| GeneratedFile::new(format!("schemas/{}.schema.json", table.name), table_schema(table, options)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_table_schema() {
+        let schema = Parser::new(r#"
+table Users {
+  id: int [pk, autoincrement],
+  name: str [default="anon"],
+  bio: str [nullable],
+  role: role_kind [nullable],
+  joined: datetime [default=NOW]
+}
+"#).parse().unwrap();
+
+        let mut options = Options::default();
+        options.enums.insert("role_kind".to_string(), vec!["admin".to_string(), "user".to_string()]);
+        let doc = table_schema(&schema.tables[0], &options);
+        assert!(doc.contains("  \"$id\": \"Users.schema.json\",\n"));
+        assert!(doc.contains("    \"id\": { \"type\": \"integer\", \"readOnly\": true },\n"));
+        assert!(doc.contains("    \"name\": { \"type\": \"string\", \"default\": \"anon\" },\n"));
+        assert!(doc.contains("    \"bio\": { \"type\": [\"string\", \"null\"] },\n"));
+        assert!(doc.contains("    \"role\": { \"enum\": [\"admin\", \"user\", null] },\n"));
+        assert!(doc.contains("    \"joined\": { \"type\": \"string\", \"format\": \"date-time\", \"$comment\": \"defaults to the current time\" }\n"));
+        assert!(doc.contains("  \"required\": [\"id\", \"name\", \"joined\"],\n"));
+    }
+}
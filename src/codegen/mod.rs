@@ -0,0 +1,113 @@
+// Source code generated from a schema for use in application code
+pub mod diesel;
+pub mod sea_orm;
+pub mod typescript;
+pub mod json_schema;
+
+use crate::ast::Schema;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A generated file, with its path relative to the output directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+impl GeneratedFile {
+    pub fn new(path: impl Into<PathBuf>, content: String) -> Self {
+        GeneratedFile { path: path.into(), content }
+    }
+}
+
+/// Settings shared by the targets
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Values of custom types that are enums, e.g. `mood_kind` → happy, sad
+    pub enums: HashMap<String, Vec<String>>,
+}
+
+pub const TARGETS: &[&str] = &["diesel", "sea-orm", "typescript", "json-schema"];
+
+/// Generate the files for a codegen target such as `diesel`
+pub fn generate(schema: &Schema, target: &str, options: &Options) -> Result<Vec<GeneratedFile>, String> {
+    match target {
+        "diesel" => Ok(vec![GeneratedFile::new("schema.rs", diesel::schema_rs(schema))]),
+        "sea-orm" => Ok(sea_orm::entities(schema)),
+        "typescript" => Ok(vec![GeneratedFile::new("models.ts", typescript::models_ts(schema, options))]),
+        "json-schema" => Ok(json_schema::documents(schema, options)),
+        other => Err(format!("Unknown codegen target '{}' (expected {})", other, TARGETS.join(", "))),
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override", "priv",
+    "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// `UserAccounts` or `userAccounts` as `user_accounts`
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut output = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let boundary = match prev {
+                Some(p) if p.is_lowercase() || p.is_numeric() => true,
+                Some(p) if p.is_uppercase() => next.is_some_and(|n| n.is_lowercase()),
+                _ => false,
+            };
+            if boundary && !output.ends_with('_') {
+                output.push('_');
+            }
+            output.extend(c.to_lowercase());
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// `user_accounts` or `userAccounts` as `UserAccounts`
+pub fn pascal_case(name: &str) -> String {
+    snake_case(name)
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Snake-case Rust identifier for a table or column, avoiding keywords
+pub fn rust_ident(name: &str) -> String {
+    let mut ident = snake_case(name);
+    if RUST_KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(snake_case("UserAccounts"), "user_accounts");
+        assert_eq!(snake_case("HTTPLog"), "http_log");
+        assert_eq!(snake_case("created_at"), "created_at");
+        assert_eq!(pascal_case("order_items"), "OrderItems");
+        assert_eq!(pascal_case("userId"), "UserId");
+        assert_eq!(rust_ident("Type"), "type_");
+    }
+}
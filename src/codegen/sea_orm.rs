@@ -0,0 +1,216 @@
+use super::{pascal_case, rust_ident, GeneratedFile};
+use crate::ast::*;
+use crate::sql;
+
+const HEADER: &str = "//! `SeaORM` entity generated by FreeERD. Regenerate instead of editing by hand.\n";
+
+/// Rust type of a model field
+fn rust_type(datatype: &DataType) -> &'static str {
+    match datatype {
+        DataType::String | DataType::Custom(_) => "String",
+        DataType::Int => "i32",
+        DataType::Bool => "bool",
+        DataType::Double => "f64",
+        DataType::Float => "f32",
+        DataType::Decimal => "Decimal",
+        DataType::Date => "Date",
+        DataType::Time => "Time",
+        DataType::DateTime => "DateTime",
+        DataType::Blob | DataType::TinyBlob | DataType::LargeBlob => "Vec<u8>",
+    }
+}
+
+/// One variant of an entity's `Relation` enum
+struct RelationVariant {
+    name: String,
+    target: String,
+    attribute: String,
+}
+
+/// Relations of one table: `belongs_to` where it holds the foreign key,
+/// `has_many` or `has_one` where another table references it
+fn relations(schema: &Schema, table: &Table) -> (Vec<RelationVariant>, Vec<String>) {
+    let mut variants: Vec<RelationVariant> = Vec::new();
+    let mut notes = Vec::new();
+
+    for rel in &schema.relationships {
+        if rel.relationship_type == RelationshipType::ManyToMany {
+            if rel.from_table == table.name || rel.to_table == table.name {
+                let other = if rel.from_table == table.name { &rel.to_table } else { &rel.from_table };
+                notes.push(format!("Many-to-many with `{}` needs a junction entity", other));
+            }
+            continue;
+        }
+        let Some(fk) = sql::foreign_key(schema, rel) else { continue };
+
+        if fk.table == table.name {
+            let target = rust_ident(&fk.ref_table);
+            let attribute = format!(
+                "#[sea_orm(\n        belongs_to = \"super::{target}::Entity\",\n        from = \"Column::{}\",\n        to = \"super::{target}::Column::{}\"\n    )]",
+                pascal_case(&fk.column),
+                pascal_case(&fk.ref_column),
+            );
+            // Self-references are named as sea-orm-cli names them
+            let name = if fk.ref_table == table.name { "SelfRef".to_string() } else { pascal_case(&fk.ref_table) };
+            variants.push(RelationVariant { name, target, attribute });
+        } else if fk.ref_table == table.name {
+            // has_many needs the child's Related impl, which only exists for a single foreign key
+            let references = sql::foreign_keys(schema).iter()
+                .filter(|other| other.table == fk.table && other.ref_table == table.name)
+                .count();
+            if references > 1 {
+                let note = format!("`{}` references this table more than once; add its relations by hand", fk.table);
+                if !notes.contains(&note) {
+                    notes.push(note);
+                }
+                continue;
+            }
+            let target = rust_ident(&fk.table);
+            let kind = if rel.relationship_type == RelationshipType::OneToOne { "has_one" } else { "has_many" };
+            let attribute = format!("#[sea_orm({} = \"super::{}::Entity\")]", kind, target);
+            variants.push(RelationVariant { name: pascal_case(&fk.table), target, attribute });
+        }
+    }
+
+    // Several relations to the same table get numbered variant names
+    let names: Vec<String> = variants.iter().map(|v| v.name.clone()).collect();
+    for (i, variant) in variants.iter_mut().enumerate() {
+        if names.iter().filter(|n| **n == names[i]).count() > 1 {
+            variant.name = format!("{}{}", variant.name, i + 1);
+        }
+    }
+
+    (variants, notes)
+}
+
+/// Entity module for one table
+fn entity(schema: &Schema, table: &Table) -> String {
+    let mut output = format!("{}\nuse sea_orm::entity::prelude::*;\n\n", HEADER);
+    output.push_str("#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]\n");
+    output.push_str(&format!("#[sea_orm(table_name = \"{}\")]\npub struct Model {{\n", table.name));
+
+    for column in &table.columns {
+        let ident = rust_ident(&column.name);
+        let mut options = Vec::new();
+        if column.is_primary_key() {
+            options.push("primary_key".to_string());
+            if !column.attributes.contains(&Attribute::AutoIncrement) {
+                options.push("auto_increment = false".to_string());
+            }
+        }
+        if column.attributes.contains(&Attribute::Unique) {
+            options.push("unique".to_string());
+        }
+        if column.attributes.contains(&Attribute::Indexed) {
+            options.push("indexed".to_string());
+        }
+        if ident != column.name {
+            options.push(format!("column_name = \"{}\"", column.name));
+        }
+        if let DataType::Custom(name) = &column.datatype {
+            options.push(format!("column_type = \"custom(\\\"{}\\\")\"", name));
+        }
+        if !options.is_empty() {
+            output.push_str(&format!("    #[sea_orm({})]\n", options.join(", ")));
+        }
+
+        let field_type = rust_type(&column.datatype);
+        if sql::is_nullable(column) {
+            output.push_str(&format!("    pub {}: Option<{}>,\n", ident, field_type));
+        } else {
+            output.push_str(&format!("    pub {}: {},\n", ident, field_type));
+        }
+    }
+    output.push_str("}\n\n");
+
+    let (variants, notes) = relations(schema, table);
+    output.push_str("#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\npub enum Relation {\n");
+    for note in &notes {
+        output.push_str(&format!("    // {}\n", note));
+    }
+    for variant in &variants {
+        output.push_str(&format!("    {}\n    {},\n", variant.attribute, variant.name));
+    }
+    output.push_str("}\n");
+
+    // Related can only be implemented once per target, and not for self-references
+    for variant in &variants {
+        let single = variants.iter().filter(|v| v.target == variant.target).count() == 1;
+        if single && variant.target != rust_ident(&table.name) {
+            output.push_str(&format!(
+                "\nimpl Related<super::{}::Entity> for Entity {{\n    fn to() -> RelationDef {{\n        Relation::{}.def()\n    }}\n}}\n",
+                variant.target, variant.name
+            ));
+        }
+    }
+
+    output.push_str("\nimpl ActiveModelBehavior for ActiveModel {}\n");
+    output
+}
+
+/// `entity/` module with one file per table, plus `mod.rs` and `prelude.rs`
+/// as sea-orm-cli lays them out
+pub fn entities(schema: &Schema) -> Vec<GeneratedFile> {
+    let mut files = Vec::new();
+    let mut mod_rs = format!("{}\npub mod prelude;\n\n", HEADER);
+    let mut prelude = HEADER.to_string();
+    prelude.push('\n');
+
+    for table in &schema.tables {
+        let module = rust_ident(&table.name);
+        mod_rs.push_str(&format!("pub mod {};\n", module));
+        prelude.push_str(&format!("pub use super::{}::Entity as {};\n", module, pascal_case(&table.name)));
+        files.push(GeneratedFile::new(format!("entity/{}.rs", module), entity(schema, table)));
+    }
+
+    files.insert(0, GeneratedFile::new("entity/prelude.rs", prelude));
+    files.insert(0, GeneratedFile::new("entity/mod.rs", mod_rs));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_entities() {
+        let schema = Parser::new(r#"
+table Users {
+  id: int [pk, autoincrement],
+  email: str [unique],
+  bio: str [nullable]
+}
+table Posts {
+  id: int [pk],
+  user_id: int [fk],
+  parent_id: int [fk, nullable]
+}
+table Tags {
+  id: int [pk]
+}
+Users.id > Posts.user_id
+Posts.id > Posts.parent_id
+Posts.id <> Tags.id
+"#).parse().unwrap();
+
+        let files = entities(&schema);
+        let paths: Vec<String> = files.iter().map(|f| f.path.display().to_string()).collect();
+        assert_eq!(paths, vec!["entity/mod.rs", "entity/prelude.rs", "entity/users.rs", "entity/posts.rs", "entity/tags.rs"]);
+        assert!(files[0].content.contains("pub mod prelude;\n\npub mod users;\npub mod posts;\n"));
+        assert!(files[1].content.contains("pub use super::users::Entity as Users;\n"));
+
+        let users = &files[2].content;
+        assert!(users.contains("#[sea_orm(table_name = \"Users\")]\npub struct Model {\n    #[sea_orm(primary_key)]\n    pub id: i32,\n    #[sea_orm(unique)]\n    pub email: String,\n    pub bio: Option<String>,\n}\n"));
+        assert!(users.contains("    #[sea_orm(has_many = \"super::posts::Entity\")]\n    Posts,\n"));
+        assert!(users.contains("impl Related<super::posts::Entity> for Entity {"));
+
+        let posts = &files[3].content;
+        assert!(posts.contains("    #[sea_orm(primary_key, auto_increment = false)]\n    pub id: i32,\n"));
+        assert!(posts.contains("        belongs_to = \"super::users::Entity\",\n        from = \"Column::UserId\",\n        to = \"super::users::Column::Id\"\n"));
+        assert!(!posts.contains("has_many"));
+        assert!(posts.contains("        to = \"super::posts::Column::Id\"\n    )]\n    SelfRef,\n"));
+        assert!(posts.contains("    // Many-to-many with `Tags` needs a junction entity\n"));
+        assert!(!posts.contains("impl Related<super::posts::Entity>"));
+    }
+}
@@ -0,0 +1,94 @@
+use super::{pascal_case, Options};
+use crate::ast::*;
+use crate::sql;
+
+/// TypeScript type of a column as it arrives over JSON
+fn ts_type(datatype: &DataType) -> String {
+    match datatype {
+        DataType::String => "string".to_string(),
+        DataType::Int | DataType::Double | DataType::Float | DataType::Decimal => "number".to_string(),
+        DataType::Bool => "boolean".to_string(),
+        // Dates are ISO 8601 strings and binary data base64 once serialized
+        DataType::Date | DataType::Time | DataType::DateTime => "string".to_string(),
+        DataType::Blob | DataType::TinyBlob | DataType::LargeBlob => "string".to_string(),
+        DataType::Custom(name) => pascal_case(name),
+    }
+}
+
+/// Property name, quoted when it isn't a plain identifier
+fn property(name: &str) -> String {
+    let plain = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if plain {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\'', "\\'"))
+    }
+}
+
+/// `models.ts` with an interface per table. Custom types become a type
+/// alias: a union of string literals when their values are given, else `unknown`.
+pub fn models_ts(schema: &Schema, options: &Options) -> String {
+    let mut output = String::from("// Generated by FreeERD. Regenerate instead of editing by hand.\n");
+
+    let mut custom_types: Vec<&str> = Vec::new();
+    for column in schema.tables.iter().flat_map(|t| &t.columns) {
+        if let DataType::Custom(name) = &column.datatype {
+            if !custom_types.contains(&name.as_str()) {
+                custom_types.push(name);
+            }
+        }
+    }
+    if !custom_types.is_empty() {
+        output.push('\n');
+    }
+    for name in custom_types {
+        let union = match options.enums.get(name) {
+            Some(values) if !values.is_empty() => values.iter()
+                .map(|v| format!("'{}'", v.replace('\'', "\\'")))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            _ => "unknown".to_string(),
+        };
+        output.push_str(&format!("export type {} = {};\n", pascal_case(name), union));
+    }
+
+    for table in &schema.tables {
+        output.push_str(&format!("\nexport interface {} {{\n", pascal_case(&table.name)));
+        for column in &table.columns {
+            let mut column_type = ts_type(&column.datatype);
+            if sql::is_nullable(column) {
+                column_type.push_str(" | null");
+            }
+            output.push_str(&format!("  {}: {};\n", property(&column.name), column_type));
+        }
+        output.push_str("}\n");
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_models_ts() {
+        let schema = Parser::new(r#"
+table user_accounts {
+  id: int [pk],
+  bio: str [nullable],
+  joined: datetime,
+  mood: mood_kind [nullable],
+  extra: jsonb
+}
+"#).parse().unwrap();
+
+        let mut options = Options::default();
+        options.enums.insert("mood_kind".to_string(), vec!["happy".to_string(), "sad".to_string()]);
+        let ts = models_ts(&schema, &options);
+        assert!(ts.contains("\nexport type MoodKind = 'happy' | 'sad';\nexport type Jsonb = unknown;\n"));
+        assert!(ts.contains("export interface UserAccounts {\n  id: number;\n  bio: string | null;\n  joined: string;\n  mood: MoodKind | null;\n  extra: Jsonb;\n}\n"));
+    }
+}
@@ -0,0 +1,562 @@
+use crate::ast::*;
+use crate::lexer::Span;
+
+/// Whether an item exists only in the new schema, only in the old one, or in both with differences
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        }
+    }
+
+    /// ANSI colour used for the header: green, red or amber
+    fn color(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "\x1b[1;32m",
+            ChangeKind::Removed => "\x1b[1;31m",
+            ChangeKind::Changed => "\x1b[1;33m",
+        }
+    }
+
+    fn marker(&self) -> char {
+        match self {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+        }
+    }
+}
+
+/// One difference between two schemas. Items are matched by name; a renamed
+/// table shows up as one removal and one addition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    TitleChanged { from: Option<String>, to: Option<String> },
+    TableAdded(Table),
+    TableRemoved(Table),
+    TableChanged { old: Table, new: Table }, // Table-level attributes (color, icon)
+    ColumnAdded { table: String, column: Column },
+    ColumnRemoved { table: String, column: Column },
+    ColumnChanged { table: String, old: Column, new: Column },
+    RelationshipAdded(Relationship),
+    RelationshipRemoved(Relationship),
+    RelationshipChanged { old: Relationship, new: Relationship },
+    NodeAdded(Node),
+    NodeRemoved(Node),
+    NodeFieldAdded { node: String, field: NodeField },
+    NodeFieldRemoved { node: String, field: NodeField },
+    NodeFieldChanged { node: String, old: NodeField, new: NodeField },
+    EdgeAdded(Edge),
+    EdgeRemoved(Edge),
+    EdgeChanged { old: Edge, new: Edge },
+}
+
+impl Change {
+    pub fn kind(&self) -> ChangeKind {
+        match self {
+            Change::TableAdded(_)
+            | Change::ColumnAdded { .. }
+            | Change::RelationshipAdded(_)
+            | Change::NodeAdded(_)
+            | Change::NodeFieldAdded { .. }
+            | Change::EdgeAdded(_) => ChangeKind::Added,
+            Change::TableRemoved(_)
+            | Change::ColumnRemoved { .. }
+            | Change::RelationshipRemoved(_)
+            | Change::NodeRemoved(_)
+            | Change::NodeFieldRemoved { .. }
+            | Change::EdgeRemoved(_) => ChangeKind::Removed,
+            Change::TitleChanged { .. }
+            | Change::TableChanged { .. }
+            | Change::ColumnChanged { .. }
+            | Change::RelationshipChanged { .. }
+            | Change::NodeFieldChanged { .. }
+            | Change::EdgeChanged { .. } => ChangeKind::Changed,
+        }
+    }
+
+    /// Kind of item that changed, e.g. "column"
+    pub fn entity(&self) -> &'static str {
+        match self {
+            Change::TitleChanged { .. } => "title",
+            Change::TableAdded(_) | Change::TableRemoved(_) | Change::TableChanged { .. } => "table",
+            Change::ColumnAdded { .. } | Change::ColumnRemoved { .. } | Change::ColumnChanged { .. } => "column",
+            Change::RelationshipAdded(_) | Change::RelationshipRemoved(_) | Change::RelationshipChanged { .. } => "relationship",
+            Change::NodeAdded(_) | Change::NodeRemoved(_) => "node",
+            Change::NodeFieldAdded { .. } | Change::NodeFieldRemoved { .. } | Change::NodeFieldChanged { .. } => "node field",
+            Change::EdgeAdded(_) | Change::EdgeRemoved(_) | Change::EdgeChanged { .. } => "edge",
+        }
+    }
+
+    /// Name of the changed item, e.g. "Users.email" or "Posts.user_id > Users.id"
+    pub fn subject(&self) -> String {
+        match self {
+            Change::TitleChanged { .. } => "#title".to_string(),
+            Change::TableAdded(t) | Change::TableRemoved(t) | Change::TableChanged { new: t, .. } => t.name.clone(),
+            Change::ColumnAdded { table, column } | Change::ColumnRemoved { table, column } | Change::ColumnChanged { table, new: column, .. } => {
+                format!("{}.{}", table, column.name)
+            }
+            Change::RelationshipAdded(r) | Change::RelationshipRemoved(r) | Change::RelationshipChanged { new: r, .. } => relationship_name(r),
+            Change::NodeAdded(n) | Change::NodeRemoved(n) => n.name.clone(),
+            Change::NodeFieldAdded { node, field } | Change::NodeFieldRemoved { node, field } | Change::NodeFieldChanged { node, new: field, .. } => {
+                format!("{}.{}", node, field.name)
+            }
+            Change::EdgeAdded(e) | Change::EdgeRemoved(e) | Change::EdgeChanged { new: e, .. } => edge_name(e),
+        }
+    }
+
+    /// What changed, one line per difference (empty for additions and removals)
+    pub fn details(&self) -> Vec<String> {
+        match self {
+            Change::TitleChanged { from, to } => vec![format!("{} → {}", quoted(from), quoted(to))],
+            Change::TableChanged { old, new } => list_changes(&old.attributes, &new.attributes),
+            Change::ColumnChanged { old, new, .. } => field_changes(&old.datatype, &new.datatype, &old.attributes, &new.attributes),
+            Change::NodeFieldChanged { old, new, .. } => field_changes(&old.datatype, &new.datatype, &old.attributes, &new.attributes),
+            Change::RelationshipChanged { old, new } => {
+                vec![format!("type: {} → {}", old.relationship_type, new.relationship_type)]
+            }
+            Change::EdgeChanged { old, new } => {
+                let mut details = Vec::new();
+                if old.edge_type != new.edge_type {
+                    details.push(format!("type: {} → {}", old.edge_type, new.edge_type));
+                }
+                for prop in &new.properties {
+                    match old.properties.iter().find(|p| p.name == prop.name) {
+                        None => details.push(format!("+ property {}: {}", prop.name, prop.datatype)),
+                        Some(before) => {
+                            for detail in field_changes(&before.datatype, &prop.datatype, &before.attributes, &prop.attributes) {
+                                details.push(format!("property {}: {}", prop.name, detail));
+                            }
+                        }
+                    }
+                }
+                for prop in old.properties.iter().filter(|p| !new.properties.iter().any(|n| n.name == p.name)) {
+                    details.push(format!("- property {}: {}", prop.name, prop.datatype));
+                }
+                details.extend(list_changes(&old.attributes, &new.attributes));
+                details
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Where the item is in the old file (removals) or the new file (everything else)
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Change::TitleChanged { .. } => None,
+            Change::TableAdded(t) | Change::TableRemoved(t) | Change::TableChanged { new: t, .. } => t.span,
+            Change::ColumnAdded { column, .. } | Change::ColumnRemoved { column, .. } | Change::ColumnChanged { new: column, .. } => column.span,
+            Change::RelationshipAdded(r) | Change::RelationshipRemoved(r) | Change::RelationshipChanged { new: r, .. } => r.span,
+            Change::NodeAdded(n) | Change::NodeRemoved(n) => n.span,
+            Change::NodeFieldAdded { field, .. } | Change::NodeFieldRemoved { field, .. } | Change::NodeFieldChanged { new: field, .. } => field.span,
+            Change::EdgeAdded(e) | Change::EdgeRemoved(e) | Change::EdgeChanged { new: e, .. } => e.span,
+        }
+    }
+}
+
+fn quoted(value: &Option<String>) -> String {
+    value.as_ref().map(|v| format!("\"{}\"", v)).unwrap_or_else(|| "none".to_string())
+}
+
+fn relationship_name(r: &Relationship) -> String {
+    let symbol = match r.relationship_type {
+        RelationshipType::OneToMany => ">",
+        RelationshipType::ManyToOne => "<",
+        RelationshipType::ManyToMany => "<>",
+        RelationshipType::OneToOne => "-",
+    };
+    format!("{}.{} {} {}.{}", r.from_table, r.from_field, symbol, r.to_table, r.to_field)
+}
+
+fn edge_name(e: &Edge) -> String {
+    match e.edge_type {
+        EdgeType::Outgoing => format!("{} -[{}]-> {}", e.from_node, e.name, e.to_node),
+        EdgeType::Incoming => format!("{} <-[{}]- {}", e.from_node, e.name, e.to_node),
+        EdgeType::Bidirectional => format!("{} <-[{}]-> {}", e.from_node, e.name, e.to_node),
+    }
+}
+
+/// "+unique" / "-nullable" lines for attributes present on only one side
+fn list_changes<T: PartialEq + std::fmt::Display>(old: &[T], new: &[T]) -> Vec<String> {
+    let added = new.iter().filter(|a| !old.contains(a)).map(|a| format!("+ {}", a));
+    let removed = old.iter().filter(|a| !new.contains(a)).map(|a| format!("- {}", a));
+    added.chain(removed).collect()
+}
+
+fn field_changes(old_type: &DataType, new_type: &DataType, old: &[Attribute], new: &[Attribute]) -> Vec<String> {
+    let mut details = Vec::new();
+    if old_type != new_type {
+        details.push(format!("type: {} → {}", old_type, new_type));
+    }
+    details.extend(list_changes(old, new));
+    details
+}
+
+/// Fields are compared by content, ignoring spans and attribute order, so
+/// moving a statement isn't a change
+fn same_field(old_type: &DataType, new_type: &DataType, old: &[Attribute], new: &[Attribute]) -> bool {
+    old_type == new_type && old.len() == new.len() && old.iter().all(|a| new.contains(a))
+}
+
+fn same_edge(old: &Edge, new: &Edge) -> bool {
+    old.edge_type == new.edge_type
+        && old.properties.len() == new.properties.len()
+        && old.properties.iter().all(|p| {
+            new.properties.iter().any(|n| n.name == p.name && same_field(&p.datatype, &n.datatype, &p.attributes, &n.attributes))
+        })
+        && old.attributes.len() == new.attributes.len()
+        && old.attributes.iter().all(|a| new.attributes.contains(a))
+}
+
+/// Ends and cardinality of a relationship with the ends in a fixed order,
+/// so `A.x > B.y` and `B.y < A.x` compare equal
+fn normalized(r: &Relationship) -> ((&str, &str), (&str, &str), RelationshipType) {
+    let from = (r.from_table.as_str(), r.from_field.as_str());
+    let to = (r.to_table.as_str(), r.to_field.as_str());
+    if from <= to {
+        return (from, to, r.relationship_type);
+    }
+    let flipped = match r.relationship_type {
+        RelationshipType::OneToMany => RelationshipType::ManyToOne,
+        RelationshipType::ManyToOne => RelationshipType::OneToMany,
+        other => other,
+    };
+    (to, from, flipped)
+}
+
+fn same_relationship_ends(a: &Relationship, b: &Relationship) -> bool {
+    let (a_from, a_to, _) = normalized(a);
+    let (b_from, b_to, _) = normalized(b);
+    a_from == b_from && a_to == b_to
+}
+
+/// All differences between two schemas, in the order: title, tables and
+/// their columns, relationships, nodes and their fields, edges
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDiff {
+    pub changes: Vec<Change>,
+}
+
+impl SchemaDiff {
+    pub fn between(old: &Schema, new: &Schema) -> Self {
+        let mut changes = Vec::new();
+
+        if old.title != new.title {
+            changes.push(Change::TitleChanged { from: old.title.clone(), to: new.title.clone() });
+        }
+
+        for table in &old.tables {
+            if !new.tables.iter().any(|t| t.name == table.name) {
+                changes.push(Change::TableRemoved(table.clone()));
+            }
+        }
+        for table in &new.tables {
+            let Some(before) = old.tables.iter().find(|t| t.name == table.name) else {
+                changes.push(Change::TableAdded(table.clone()));
+                continue;
+            };
+            if before.attributes != table.attributes {
+                changes.push(Change::TableChanged { old: before.clone(), new: table.clone() });
+            }
+            for column in &before.columns {
+                if !table.columns.iter().any(|c| c.name == column.name) {
+                    changes.push(Change::ColumnRemoved { table: table.name.clone(), column: column.clone() });
+                }
+            }
+            for column in &table.columns {
+                match before.columns.iter().find(|c| c.name == column.name) {
+                    None => changes.push(Change::ColumnAdded { table: table.name.clone(), column: column.clone() }),
+                    Some(old_column) if !same_field(&old_column.datatype, &column.datatype, &old_column.attributes, &column.attributes) => {
+                        changes.push(Change::ColumnChanged { table: table.name.clone(), old: old_column.clone(), new: column.clone() });
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for rel in &old.relationships {
+            if !new.relationships.iter().any(|r| same_relationship_ends(r, rel)) {
+                changes.push(Change::RelationshipRemoved(rel.clone()));
+            }
+        }
+        for rel in &new.relationships {
+            match old.relationships.iter().find(|r| same_relationship_ends(r, rel)) {
+                None => changes.push(Change::RelationshipAdded(rel.clone())),
+                Some(before) if normalized(before).2 != normalized(rel).2 => {
+                    changes.push(Change::RelationshipChanged { old: before.clone(), new: rel.clone() });
+                }
+                Some(_) => {}
+            }
+        }
+
+        for node in &old.nodes {
+            if !new.nodes.iter().any(|n| n.name == node.name) {
+                changes.push(Change::NodeRemoved(node.clone()));
+            }
+        }
+        for node in &new.nodes {
+            let Some(before) = old.nodes.iter().find(|n| n.name == node.name) else {
+                changes.push(Change::NodeAdded(node.clone()));
+                continue;
+            };
+            for field in &before.fields {
+                if !node.fields.iter().any(|f| f.name == field.name) {
+                    changes.push(Change::NodeFieldRemoved { node: node.name.clone(), field: field.clone() });
+                }
+            }
+            for field in &node.fields {
+                match before.fields.iter().find(|f| f.name == field.name) {
+                    None => changes.push(Change::NodeFieldAdded { node: node.name.clone(), field: field.clone() }),
+                    Some(old_field) if !same_field(&old_field.datatype, &field.datatype, &old_field.attributes, &field.attributes) => {
+                        changes.push(Change::NodeFieldChanged { node: node.name.clone(), old: old_field.clone(), new: field.clone() });
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        // Edges are identified by label and endpoints, since one label can connect several node pairs
+        let edge_key = |e: &Edge| (e.name.clone(), e.from_node.clone(), e.to_node.clone());
+        for edge in &old.edges {
+            if !new.edges.iter().any(|e| edge_key(e) == edge_key(edge)) {
+                changes.push(Change::EdgeRemoved(edge.clone()));
+            }
+        }
+        for edge in &new.edges {
+            match old.edges.iter().find(|e| edge_key(e) == edge_key(edge)) {
+                None => changes.push(Change::EdgeAdded(edge.clone())),
+                Some(before) if !same_edge(before, edge) => {
+                    changes.push(Change::EdgeChanged { old: before.clone(), new: edge.clone() });
+                }
+                Some(_) => {}
+            }
+        }
+
+        SchemaDiff { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of added, removed and changed items
+    pub fn counts(&self) -> (usize, usize, usize) {
+        let count = |kind| self.changes.iter().filter(|c| c.kind() == kind).count();
+        (count(ChangeKind::Added), count(ChangeKind::Removed), count(ChangeKind::Changed))
+    }
+
+    /// Coloured report in the style of the parser's errors, quoting the
+    /// source line of each change from the file it lives in
+    pub fn format_with_source(&self, old_source: &str, old_file: &str, new_source: &str, new_file: &str) -> String {
+        let mut output = String::new();
+
+        for change in &self.changes {
+            let kind = change.kind();
+            output.push_str(&format!(
+                "{}{}\x1b[0m: {} `{}`\n",
+                kind.color(),
+                kind.as_str(),
+                change.entity(),
+                change.subject()
+            ));
+
+            let (source, filename) = match kind {
+                ChangeKind::Removed => (old_source, old_file),
+                _ => (new_source, new_file),
+            };
+            if let Some(span) = change.span() {
+                output.push_str(&format!("  \x1b[1;34m-->\x1b[0m {}:{}:{}\n", filename, span.line, span.column));
+                output.push_str("   \x1b[1;34m|\x1b[0m\n");
+                if let Some(line_text) = source.lines().nth(span.line - 1) {
+                    output.push_str(&format!(" \x1b[1;34m{:>3} |\x1b[0m {}\n", span.line, line_text));
+                    output.push_str("   \x1b[1;34m|\x1b[0m ");
+                    output.push_str(&" ".repeat(span.column - 1));
+                    output.push_str(&format!(
+                        "{}{}\x1b[0m\n",
+                        kind.color(),
+                        kind.marker().to_string().repeat(span.length.max(1))
+                    ));
+                }
+            }
+            for detail in change.details() {
+                output.push_str(&format!("   \x1b[1;34m=\x1b[0m {}\n", detail));
+            }
+            output.push('\n');
+        }
+
+        let (added, removed, changed) = self.counts();
+        output.push_str(&format!(
+            "\x1b[1;32m{} added\x1b[0m, \x1b[1;31m{} removed\x1b[0m, \x1b[1;33m{} changed\x1b[0m\n",
+            added, removed, changed
+        ));
+        output
+    }
+
+    /// Machine-readable report: `{"summary": {...}, "changes": [...]}`
+    pub fn to_json(&self) -> String {
+        let (added, removed, changed) = self.counts();
+        let mut output = String::from("{\n");
+        output.push_str(&format!(
+            "  \"summary\": {{\"added\": {}, \"removed\": {}, \"changed\": {}}},\n",
+            added, removed, changed
+        ));
+        output.push_str("  \"changes\": [");
+
+        for (i, change) in self.changes.iter().enumerate() {
+            output.push_str(if i == 0 { "\n" } else { ",\n" });
+            let details: Vec<String> = change.details().iter().map(|d| json_string(d)).collect();
+            let line = change.span().map(|s| s.line.to_string()).unwrap_or_else(|| "null".to_string());
+            output.push_str(&format!(
+                "    {{\"kind\": {}, \"entity\": {}, \"name\": {}, \"line\": {}, \"details\": [{}]}}",
+                json_string(change.kind().as_str()),
+                json_string(change.entity()),
+                json_string(&change.subject()),
+                line,
+                details.join(", ")
+            ));
+        }
+
+        if !self.changes.is_empty() {
+            output.push_str("\n  ");
+        }
+        output.push_str("]\n}\n");
+        output
+    }
+}
+
+/// JSON string literal with escaping
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn parse(source: &str) -> Schema {
+        Parser::new(source).parse().unwrap()
+    }
+
+    const OLD: &str = r#"
+#title "Shop"
+table Users {
+  id: int [pk],
+  email: str,
+  age: int [nullable]
+}
+table Orders {
+  id: int [pk],
+  user_id: int
+}
+table Legacy {
+  id: int [pk]
+}
+Orders.user_id > Users.id
+"#;
+
+    const NEW: &str = r#"
+#title "Shop v2"
+table Users {
+  id: int [pk],
+  email: str [unique],
+  name: str
+}
+table Orders {
+  id: int [pk],
+  user_id: int
+}
+table Products {
+  id: int [pk]
+}
+Orders.user_id < Users.id
+"#;
+
+    #[test]
+    fn test_diff_tables_columns_and_relationships() {
+        let diff = SchemaDiff::between(&parse(OLD), &parse(NEW));
+        let summary: Vec<(ChangeKind, String)> = diff.changes.iter().map(|c| (c.kind(), c.subject())).collect();
+
+        assert_eq!(summary, vec![
+            (ChangeKind::Changed, "#title".to_string()),
+            (ChangeKind::Removed, "Legacy".to_string()),
+            (ChangeKind::Removed, "Users.age".to_string()),
+            (ChangeKind::Changed, "Users.email".to_string()),
+            (ChangeKind::Added, "Users.name".to_string()),
+            (ChangeKind::Added, "Products".to_string()),
+            (ChangeKind::Changed, "Orders.user_id < Users.id".to_string()),
+        ]);
+        assert_eq!(diff.changes[3].details(), vec!["+ unique".to_string()]);
+        assert_eq!(diff.counts(), (2, 2, 3));
+    }
+
+    #[test]
+    fn test_reversed_relationship_is_unchanged() {
+        let tables = "table Users {\n  id: int [pk]\n}\ntable Orders {\n  user_id: int\n}\n";
+        let old = parse(&format!("{}Users.id > Orders.user_id\n", tables));
+        assert!(SchemaDiff::between(&old, &parse(&format!("{}Orders.user_id < Users.id\n", tables))).is_empty());
+
+        let diff = SchemaDiff::between(&old, &parse(&format!("{}Orders.user_id > Users.id\n", tables)));
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].kind(), ChangeKind::Changed);
+    }
+
+    #[test]
+    fn test_identical_schemas_have_no_changes() {
+        let diff = SchemaDiff::between(&parse(OLD), &parse(OLD));
+        assert!(diff.is_empty());
+        assert!(diff.to_json().contains("\"changes\": []"));
+    }
+
+    #[test]
+    fn test_diff_nodes_and_edges() {
+        let old = parse("node Person { name: str }\nnode Movie { title: str }\nPerson -[ACTED_IN]-> Movie");
+        let new = parse("node Person { name: str, born: int }\nnode Movie { title: str }\nedge ACTED_IN (from: Person, to: Movie) { role: str }");
+        let diff = SchemaDiff::between(&old, &new);
+
+        assert_eq!(diff.changes.len(), 2);
+        assert_eq!(diff.changes[0], Change::NodeFieldAdded {
+            node: "Person".to_string(),
+            field: new.nodes[0].fields[1].clone(),
+        });
+        // Edge blocks are bidirectional, so the edge type changes too
+        assert_eq!(diff.changes[1].details(), vec![
+            "type: outgoing → bidirectional".to_string(),
+            "+ property role: str".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_json_and_human_output() {
+        let diff = SchemaDiff::between(&parse(OLD), &parse(NEW));
+        let json = diff.to_json();
+        assert!(json.contains("\"summary\": {\"added\": 2, \"removed\": 2, \"changed\": 3}"));
+        assert!(json.contains("{\"kind\": \"changed\", \"entity\": \"title\", \"name\": \"#title\", \"line\": null, \"details\": [\"\\\"Shop\\\" → \\\"Shop v2\\\"\"]}"));
+
+        let human = diff.format_with_source(OLD, "old.frd", NEW, "new.frd");
+        assert!(human.contains("\x1b[1;31mremoved\x1b[0m: table `Legacy`"));
+        assert!(human.contains("old.frd:12:"));
+        assert!(human.contains("=\x1b[0m + unique"));
+    }
+}
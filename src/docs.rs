@@ -0,0 +1,259 @@
+// Data dictionary: one section per table with its columns and references
+use crate::ast::*;
+use crate::codegen::GeneratedFile;
+use crate::formats::{strip_xml_declaration, xml_escape};
+use crate::sql;
+
+/// Sections are anchored at this prefix plus the table name
+pub const ANCHOR_PREFIX: &str = "table-";
+
+/// One line of a table's "references" or "referenced by" list
+#[derive(Debug, Clone, PartialEq)]
+struct Link {
+    column: String,
+    table: String,
+    other_column: String,
+    kind: &'static str,
+}
+
+/// Relationships touching `table`, split into the ones where it holds the
+/// foreign key (or either side of a many-to-many) and the ones pointing at it
+fn links(schema: &Schema, table: &str) -> (Vec<Link>, Vec<Link>) {
+    let mut references = Vec::new();
+    let mut referenced_by = Vec::new();
+    for rel in &schema.relationships {
+        match sql::foreign_key(schema, rel) {
+            Some(fk) => {
+                let kind = if rel.relationship_type == RelationshipType::OneToOne { "one-to-one" } else { "many-to-one" };
+                if fk.table == table {
+                    references.push(Link { column: fk.column.clone(), table: fk.ref_table.clone(), other_column: fk.ref_column.clone(), kind });
+                }
+                if fk.ref_table == table {
+                    let kind = if kind == "one-to-one" { kind } else { "one-to-many" };
+                    referenced_by.push(Link { column: fk.ref_column, table: fk.table, other_column: fk.column, kind });
+                }
+            }
+            None => {
+                if rel.from_table == table {
+                    references.push(Link { column: rel.from_field.clone(), table: rel.to_table.clone(), other_column: rel.to_field.clone(), kind: "many-to-many" });
+                }
+                if rel.to_table == table && rel.from_table != table {
+                    references.push(Link { column: rel.to_field.clone(), table: rel.from_table.clone(), other_column: rel.from_field.clone(), kind: "many-to-many" });
+                }
+            }
+        }
+    }
+    (references, referenced_by)
+}
+
+/// Attributes other than nullability and defaults, which have their own columns
+fn attribute_list(column: &Column) -> String {
+    column.attributes.iter()
+        .filter_map(|a| match a {
+            Attribute::PrimaryKey => Some("primary key"),
+            Attribute::ForeignKey => Some("foreign key"),
+            Attribute::Unique => Some("unique"),
+            Attribute::Indexed => Some("indexed"),
+            Attribute::AutoIncrement => Some("auto-increment"),
+            Attribute::Nullable | Attribute::Default(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Notes for a column: where its foreign key points
+fn column_notes(column: &Column, references: &[Link]) -> Vec<String> {
+    references.iter()
+        .filter(|link| link.column == column.name)
+        .map(|link| format!("→ {}.{} ({})", link.table, link.other_column, link.kind))
+        .collect()
+}
+
+fn html_links(links: &[Link], arrow: &str) -> String {
+    let mut output = String::from("<ul>\n");
+    for link in links {
+        output.push_str(&format!(
+            "<li><code>{}</code> {} <a href=\"#{}{}\">{}</a>.<code>{}</code> ({})</li>\n",
+            xml_escape(&link.column), arrow, ANCHOR_PREFIX, xml_escape(&link.table),
+            xml_escape(&link.table), xml_escape(&link.other_column), link.kind
+        ));
+    }
+    output.push_str("</ul>\n");
+    output
+}
+
+const STYLE: &str = "body { font-family: Arial, sans-serif; margin: 2em auto; max-width: 1100px; color: #2c3e50; }
+.diagram { overflow: auto; border: 1px solid #ddd; margin-bottom: 2em; }
+.diagram svg { max-width: 100%; height: auto; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1em; }
+th, td { border: 1px solid #ddd; padding: 6px 10px; text-align: left; vertical-align: top; }
+th { background: #f4f6f8; }
+section { margin-bottom: 2.5em; }
+section:target h2 { background: #fff7d6; }
+";
+
+/// `index.html` with the diagram inline and a section per table
+pub fn html(schema: &Schema, svg: &str) -> String {
+    let title = schema.title.clone().unwrap_or_else(|| "Data dictionary".to_string());
+    let mut output = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        xml_escape(&title), STYLE, xml_escape(&title)
+    );
+    output.push_str(&format!("<div class=\"diagram\">\n{}\n</div>\n", strip_xml_declaration(svg)));
+
+    output.push_str("<h2>Tables</h2>\n<ul>\n");
+    for table in &schema.tables {
+        output.push_str(&format!(
+            "<li><a href=\"#{}{}\">{}</a> ({} columns)</li>\n",
+            ANCHOR_PREFIX, xml_escape(&table.name), xml_escape(&table.name), table.columns.len()
+        ));
+    }
+    output.push_str("</ul>\n");
+
+    for table in &schema.tables {
+        let (references, referenced_by) = links(schema, &table.name);
+        output.push_str(&format!("<section id=\"{}{}\">\n<h2>{}</h2>\n", ANCHOR_PREFIX, xml_escape(&table.name), xml_escape(&table.name)));
+        output.push_str("<table>\n<thead><tr><th>Column</th><th>Type</th><th>Nullable</th><th>Attributes</th><th>Default</th><th>Notes</th></tr></thead>\n<tbody>\n");
+        for column in &table.columns {
+            let default = sql::default_value(column).map(|v| v.to_string()).unwrap_or_default();
+            output.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                xml_escape(&column.name),
+                xml_escape(&column.datatype.to_string()),
+                if sql::is_nullable(column) { "yes" } else { "no" },
+                attribute_list(column),
+                xml_escape(&default),
+                xml_escape(&column_notes(column, &references).join("; ")),
+            ));
+        }
+        output.push_str("</tbody>\n</table>\n");
+        if !references.is_empty() {
+            output.push_str("<h3>References</h3>\n");
+            output.push_str(&html_links(&references, "→"));
+        }
+        if !referenced_by.is_empty() {
+            output.push_str("<h3>Referenced by</h3>\n");
+            output.push_str(&html_links(&referenced_by, "←"));
+        }
+        output.push_str("</section>\n");
+    }
+
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+/// Markdown renders inline HTML, so schema text is escaped like HTML
+fn md_text(text: &str) -> String {
+    xml_escape(text)
+}
+
+/// Text for a Markdown table cell
+fn md_cell(text: &str) -> String {
+    md_text(text).replace('|', "\\|")
+}
+
+fn markdown_links(links: &[Link], arrow: &str) -> String {
+    links.iter()
+        .map(|link| format!(
+            "- `{}` {} [{}](#{}{}).`{}` ({})\n",
+            link.column, arrow, link.table, ANCHOR_PREFIX, link.table.to_lowercase(), link.other_column, link.kind
+        ))
+        .collect()
+}
+
+/// `dictionary.md` with the same sections, showing `diagram.svg` at the top
+pub fn markdown(schema: &Schema) -> String {
+    let title = schema.title.clone().unwrap_or_else(|| "Data dictionary".to_string());
+    let mut output = format!("# {}\n\n![Diagram](diagram.svg)\n\n## Tables\n\n", md_text(&title));
+    for table in &schema.tables {
+        output.push_str(&format!("- [{}](#{}{})\n", table.name, ANCHOR_PREFIX, table.name.to_lowercase()));
+    }
+
+    for table in &schema.tables {
+        let (references, referenced_by) = links(schema, &table.name);
+        output.push_str(&format!("\n<a id=\"{}{}\"></a>\n\n## {}\n\n", ANCHOR_PREFIX, table.name.to_lowercase(), table.name));
+        output.push_str("| Column | Type | Nullable | Attributes | Default | Notes |\n|---|---|---|---|---|---|\n");
+        for column in &table.columns {
+            let default = sql::default_value(column).map(|v| v.to_string()).unwrap_or_default();
+            output.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} | {} |\n",
+                column.name,
+                md_cell(&column.datatype.to_string()),
+                if sql::is_nullable(column) { "yes" } else { "no" },
+                attribute_list(column),
+                md_cell(&default),
+                md_cell(&column_notes(column, &references).join("; ")),
+            ));
+        }
+        if !references.is_empty() {
+            output.push_str(&format!("\n### References\n\n{}", markdown_links(&references, "→")));
+        }
+        if !referenced_by.is_empty() {
+            output.push_str(&format!("\n### Referenced by\n\n{}", markdown_links(&referenced_by, "←")));
+        }
+    }
+    output
+}
+
+/// The files of a documentation site: `index.html` with the linked diagram
+/// inline, the diagram on its own and a Markdown version of the dictionary
+pub fn site(schema: &Schema, inline_svg: &str, diagram_svg: &str) -> Vec<GeneratedFile> {
+    vec![
+        GeneratedFile::new("index.html", html(schema, inline_svg)),
+        GeneratedFile::new("diagram.svg", diagram_svg.to_string()),
+        GeneratedFile::new("dictionary.md", markdown(schema)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_dictionary_sections() {
+        let schema = Parser::new(r#"
+#title "Shop"
+table Users {
+  id: int [pk, autoincrement],
+  email: str [unique],
+  bio: str [nullable, default="none"]
+}
+table Orders {
+  id: int [pk],
+  user_id: int [fk]
+}
+table Tags {
+  id: int [pk]
+}
+Users.id > Orders.user_id
+Orders.id <> Tags.id
+"#).parse().unwrap();
+
+        let html = html(&schema, "<?xml version=\"1.0\"?>\n<svg><a href=\"#table-Users\"></a></svg>\n");
+        assert!(html.contains("<div class=\"diagram\">\n<svg><a href=\"#table-Users\"></a></svg>\n</div>\n"));
+        assert!(html.contains("<section id=\"table-Users\">\n<h2>Users</h2>\n"));
+        assert!(html.contains("<tr><td><code>id</code></td><td>int</td><td>no</td><td>primary key, auto-increment</td><td></td><td></td></tr>\n"));
+        assert!(html.contains("<tr><td><code>bio</code></td><td>str</td><td>yes</td><td></td><td>&quot;none&quot;</td><td></td></tr>\n"));
+        assert!(html.contains("<h3>Referenced by</h3>\n<ul>\n<li><code>id</code> ← <a href=\"#table-Orders\">Orders</a>.<code>user_id</code> (one-to-many)</li>\n"));
+        assert!(html.contains("<td>foreign key</td><td></td><td>→ Users.id (many-to-one)</td></tr>\n"));
+        assert!(html.contains("<li><code>id</code> → <a href=\"#table-Tags\">Tags</a>.<code>id</code> (many-to-many)</li>\n"));
+
+        let md = markdown(&schema);
+        assert!(md.starts_with("# Shop\n\n![Diagram](diagram.svg)\n\n## Tables\n\n- [Users](#table-users)\n"));
+        assert!(md.contains("| `user_id` | int | no | foreign key |  | → Users.id (many-to-one) |\n"));
+        assert!(md.contains("### References\n\n- `user_id` → [Users](#table-users).`id` (many-to-one)\n"));
+    }
+
+    #[test]
+    fn test_markdown_escapes_schema_text() {
+        let schema = Parser::new("#title \"A <b>|</b>\"\ntable Users {\n  bio: str [default=\"a|<i>&\"]\n}\n").parse().unwrap();
+        let md = markdown(&schema);
+        assert!(md.starts_with("# A &lt;b&gt;|&lt;/b&gt;\n"));
+        assert!(md.contains("| `bio` | str | no |  | &quot;a\\|&lt;i&gt;&amp;&quot; |  |\n"));
+
+        let erd_graph = crate::renderer::ErdGraph::from_schema(&schema).unwrap();
+        let svg = crate::renderer::export_svg(&erd_graph, schema.title.as_deref().unwrap(), &crate::renderer::Theme::light()).unwrap();
+        assert!(html(&schema, &svg).contains(">A &lt;b&gt;|&lt;/b&gt;</text>"));
+    }
+}
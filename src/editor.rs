@@ -0,0 +1,338 @@
+use crate::ast::*;
+use crate::parser::Parser;
+use crate::writer;
+use std::path::PathBuf;
+
+/// A single change to a schema made from the visual editor
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaEdit {
+    SetTitle(Option<String>),
+    AddTable { name: String },
+    RenameTable { from: String, to: String },
+    DeleteTable { name: String },
+    AddColumn { table: String, column: String, datatype: DataType },
+    RenameColumn { table: String, from: String, to: String },
+    SetColumnType { table: String, column: String, datatype: DataType },
+    SetColumnAttributes { table: String, column: String, attributes: Vec<Attribute> },
+    DeleteColumn { table: String, column: String },
+    AddRelationship { from_table: String, from_field: String, to_table: String, to_field: String, relationship_type: RelationshipType },
+    SetRelationshipType { index: usize, relationship_type: RelationshipType },
+    DeleteRelationship { index: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditError {
+    InvalidName(String),
+    DuplicateTable(String),
+    DuplicateColumn { table: String, column: String },
+    DuplicateRelationship(String),
+    UnknownTable(String),
+    UnknownColumn { table: String, column: String },
+    UnknownRelationship(usize),
+}
+
+impl std::fmt::Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EditError::InvalidName(name) => write!(f, "'{}' is not a valid name (use letters, digits and '_')", name),
+            EditError::DuplicateTable(name) => write!(f, "Table '{}' already exists", name),
+            EditError::DuplicateColumn { table, column } => write!(f, "Column '{}.{}' already exists", table, column),
+            EditError::DuplicateRelationship(rel) => write!(f, "Relationship {} already exists", rel),
+            EditError::UnknownTable(name) => write!(f, "Table '{}' not found", name),
+            EditError::UnknownColumn { table, column } => write!(f, "Column '{}.{}' not found", table, column),
+            EditError::UnknownRelationship(index) => write!(f, "Relationship #{} not found", index),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+fn validate_name(name: &str) -> Result<(), EditError> {
+    let valid = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(EditError::InvalidName(name.to_string()))
+    }
+}
+
+fn find_table<'a>(schema: &'a mut Schema, name: &str) -> Result<&'a mut Table, EditError> {
+    schema.tables.iter_mut()
+        .find(|t| t.name == name)
+        .ok_or_else(|| EditError::UnknownTable(name.to_string()))
+}
+
+fn find_column<'a>(table: &'a mut Table, name: &str) -> Result<&'a mut Column, EditError> {
+    let table_name = table.name.clone();
+    table.columns.iter_mut()
+        .find(|c| c.name == name)
+        .ok_or(EditError::UnknownColumn { table: table_name, column: name.to_string() })
+}
+
+fn has_column(schema: &Schema, table: &str, column: &str) -> bool {
+    schema.tables.iter().any(|t| t.name == table && t.columns.iter().any(|c| c.name == column))
+}
+
+impl SchemaEdit {
+    /// Apply the edit, keeping relationships pointing at renamed or deleted
+    /// tables and columns consistent. Spans of existing items are left alone so
+    /// the writer can match them back to the source.
+    pub fn apply(&self, schema: &mut Schema) -> Result<(), EditError> {
+        match self {
+            SchemaEdit::SetTitle(title) => {
+                schema.title = title.clone();
+            }
+            SchemaEdit::AddTable { name } => {
+                validate_name(name)?;
+                if schema.tables.iter().any(|t| t.name == *name) {
+                    return Err(EditError::DuplicateTable(name.clone()));
+                }
+                schema.tables.push(Table {
+                    name: name.clone(),
+                    columns: vec![Column {
+                        name: "id".to_string(),
+                        datatype: DataType::Int,
+                        attributes: vec![Attribute::PrimaryKey, Attribute::AutoIncrement],
+                        span: None,
+                    }],
+                    attributes: Vec::new(),
+                    span: None,
+                });
+            }
+            SchemaEdit::RenameTable { from, to } => {
+                validate_name(to)?;
+                if from != to && schema.tables.iter().any(|t| t.name == *to) {
+                    return Err(EditError::DuplicateTable(to.clone()));
+                }
+                find_table(schema, from)?.name = to.clone();
+                for rel in &mut schema.relationships {
+                    if rel.from_table == *from {
+                        rel.from_table = to.clone();
+                    }
+                    if rel.to_table == *from {
+                        rel.to_table = to.clone();
+                    }
+                }
+            }
+            SchemaEdit::DeleteTable { name } => {
+                let count = schema.tables.len();
+                schema.tables.retain(|t| t.name != *name);
+                if schema.tables.len() == count {
+                    return Err(EditError::UnknownTable(name.clone()));
+                }
+                schema.relationships.retain(|r| r.from_table != *name && r.to_table != *name);
+            }
+            SchemaEdit::AddColumn { table, column, datatype } => {
+                validate_name(column)?;
+                let target = find_table(schema, table)?;
+                if target.columns.iter().any(|c| c.name == *column) {
+                    return Err(EditError::DuplicateColumn { table: table.clone(), column: column.clone() });
+                }
+                target.columns.push(Column {
+                    name: column.clone(),
+                    datatype: datatype.clone(),
+                    attributes: Vec::new(),
+                    span: None,
+                });
+            }
+            SchemaEdit::RenameColumn { table, from, to } => {
+                validate_name(to)?;
+                let target = find_table(schema, table)?;
+                if from != to && target.columns.iter().any(|c| c.name == *to) {
+                    return Err(EditError::DuplicateColumn { table: table.clone(), column: to.clone() });
+                }
+                find_column(target, from)?.name = to.clone();
+                for rel in &mut schema.relationships {
+                    if rel.from_table == *table && rel.from_field == *from {
+                        rel.from_field = to.clone();
+                    }
+                    if rel.to_table == *table && rel.to_field == *from {
+                        rel.to_field = to.clone();
+                    }
+                }
+            }
+            SchemaEdit::SetColumnType { table, column, datatype } => {
+                find_column(find_table(schema, table)?, column)?.datatype = datatype.clone();
+            }
+            SchemaEdit::SetColumnAttributes { table, column, attributes } => {
+                find_column(find_table(schema, table)?, column)?.attributes = attributes.clone();
+            }
+            SchemaEdit::DeleteColumn { table, column } => {
+                let target = find_table(schema, table)?;
+                find_column(target, column)?;
+                target.columns.retain(|c| c.name != *column);
+                schema.relationships.retain(|r| {
+                    let uses_column = (r.from_table == *table && r.from_field == *column)
+                        || (r.to_table == *table && r.to_field == *column);
+                    !uses_column
+                });
+            }
+            SchemaEdit::AddRelationship { from_table, from_field, to_table, to_field, relationship_type } => {
+                for (table, column) in [(from_table, from_field), (to_table, to_field)] {
+                    if !has_column(schema, table, column) {
+                        return Err(EditError::UnknownColumn { table: table.clone(), column: column.clone() });
+                    }
+                }
+                let relationship = Relationship {
+                    from_table: from_table.clone(),
+                    from_field: from_field.clone(),
+                    to_table: to_table.clone(),
+                    to_field: to_field.clone(),
+                    relationship_type: *relationship_type,
+                    span: None,
+                };
+                let exists = schema.relationships.iter().any(|r| {
+                    r.from_table == relationship.from_table && r.from_field == relationship.from_field
+                        && r.to_table == relationship.to_table && r.to_field == relationship.to_field
+                });
+                if exists {
+                    return Err(EditError::DuplicateRelationship(writer::format_relationship(&relationship)));
+                }
+                schema.relationships.push(relationship);
+            }
+            SchemaEdit::SetRelationshipType { index, relationship_type } => {
+                schema.relationships.get_mut(*index)
+                    .ok_or(EditError::UnknownRelationship(*index))?
+                    .relationship_type = *relationship_type;
+            }
+            SchemaEdit::DeleteRelationship { index } => {
+                if *index >= schema.relationships.len() {
+                    return Err(EditError::UnknownRelationship(*index));
+                }
+                schema.relationships.remove(*index);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pick a relationship type for a column dragged onto another: keys and
+/// unique columns are the "one" side, everything else the "many" side
+pub fn infer_relationship_type(from: &Column, to: &Column) -> RelationshipType {
+    let is_one = |c: &Column| c.is_primary_key() || c.attributes.contains(&Attribute::Unique);
+    match (is_one(from), is_one(to)) {
+        (true, true) => RelationshipType::OneToOne,
+        (false, true) => RelationshipType::ManyToOne,
+        (false, false) => RelationshipType::ManyToMany,
+        (true, false) => RelationshipType::OneToMany,
+    }
+}
+
+/// A schema file opened for editing. `source` and `original` are kept from
+/// load time so every save can match edited items back to the file's text.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub path: PathBuf,
+    source: String,
+    original: Schema,
+    pub schema: Schema,
+    saved: Schema,
+}
+
+impl Document {
+    pub fn new(path: PathBuf, source: String, schema: Schema) -> Self {
+        Document {
+            path,
+            source,
+            original: schema.clone(),
+            saved: schema.clone(),
+            schema,
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.schema != self.saved
+    }
+
+    /// The file contents that saving would write
+    pub fn render(&self) -> Result<String, String> {
+        writer::write_preserving(&self.source, &self.original, &self.schema)
+    }
+
+    /// Write the edited schema back to the file, checking that it still parses
+    pub fn save(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let content = self.render()?;
+        Parser::new(&content).parse()
+            .map_err(|e| format!("Edited schema no longer parses: {}", e))?;
+        std::fs::write(&self.path, content)?;
+        self.saved = self.schema.clone();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Schema {
+        Parser::new(r#"
+table Users {
+  id: int [pk],
+  email: str [unique]
+}
+
+table Posts {
+  id: int [pk],
+  user_id: int [fk]
+}
+
+Users.id > Posts.user_id
+"#).parse().unwrap()
+    }
+
+    #[test]
+    fn test_rename_updates_relationships() {
+        let mut schema = schema();
+        SchemaEdit::RenameTable { from: "Users".to_string(), to: "Accounts".to_string() }.apply(&mut schema).unwrap();
+        SchemaEdit::RenameColumn { table: "Posts".to_string(), from: "user_id".to_string(), to: "account_id".to_string() }
+            .apply(&mut schema).unwrap();
+
+        let rel = &schema.relationships[0];
+        assert_eq!((rel.from_table.as_str(), rel.to_table.as_str(), rel.to_field.as_str()), ("Accounts", "Posts", "account_id"));
+        assert!(schema.tables[0].span.is_some(), "renames keep the source span");
+    }
+
+    #[test]
+    fn test_delete_removes_dangling_relationships() {
+        let mut schema = schema();
+        SchemaEdit::DeleteColumn { table: "Posts".to_string(), column: "user_id".to_string() }.apply(&mut schema).unwrap();
+        assert!(schema.relationships.is_empty());
+
+        let mut schema = self::schema();
+        SchemaEdit::DeleteTable { name: "Users".to_string() }.apply(&mut schema).unwrap();
+        assert_eq!(schema.tables.len(), 1);
+        assert!(schema.relationships.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_edits() {
+        let mut schema = schema();
+        let dup = SchemaEdit::AddTable { name: "Users".to_string() }.apply(&mut schema);
+        assert_eq!(dup, Err(EditError::DuplicateTable("Users".to_string())));
+
+        let bad = SchemaEdit::AddColumn { table: "Users".to_string(), column: "first name".to_string(), datatype: DataType::String }
+            .apply(&mut schema);
+        assert!(matches!(bad, Err(EditError::InvalidName(_))));
+
+        let missing = SchemaEdit::AddRelationship {
+            from_table: "Users".to_string(),
+            from_field: "nope".to_string(),
+            to_table: "Posts".to_string(),
+            to_field: "id".to_string(),
+            relationship_type: RelationshipType::OneToOne,
+        }.apply(&mut schema);
+        assert!(matches!(missing, Err(EditError::UnknownColumn { .. })));
+        assert_eq!(schema, self::schema());
+    }
+
+    #[test]
+    fn test_infer_relationship_type() {
+        let schema = schema();
+        let users = &schema.tables[0];
+        let posts = &schema.tables[1];
+        assert_eq!(infer_relationship_type(&users.columns[0], &posts.columns[1]), RelationshipType::OneToMany);
+        assert_eq!(infer_relationship_type(&posts.columns[1], &users.columns[0]), RelationshipType::ManyToOne);
+        assert_eq!(infer_relationship_type(&users.columns[1], &posts.columns[0]), RelationshipType::OneToOne);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::ast::*;
+use crate::codegen::snake_case;
+use std::collections::HashSet;
+
+/// Label, relationship type or property name, backquoted unless plain
+fn quote(name: &str) -> String {
+    let plain = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("`{}`", name.replace('`', "``"))
+    }
+}
+
+/// Cypher literal for a default value, if it has one
+fn default_literal(value: &DefaultValue) -> String {
+    match value {
+        DefaultValue::Now => "datetime()".to_string(),
+        DefaultValue::True => "true".to_string(),
+        DefaultValue::False => "false".to_string(),
+        DefaultValue::Null => "null".to_string(),
+        DefaultValue::String(s) => format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'")),
+        DefaultValue::Number(n) => n.to_string(),
+    }
+}
+
+/// Placeholder value for a property in the sample statements
+fn sample_value(name: &str, datatype: &DataType, attributes: &[Attribute]) -> String {
+    for attribute in attributes {
+        if let Attribute::Default(value) = attribute {
+            return default_literal(value);
+        }
+    }
+    match datatype {
+        DataType::Int => "1".to_string(),
+        DataType::Bool => "true".to_string(),
+        DataType::Double | DataType::Float | DataType::Decimal => "1.5".to_string(),
+        DataType::Date => "date('2024-01-01')".to_string(),
+        DataType::Time => "time('12:00:00')".to_string(),
+        DataType::DateTime => "datetime('2024-01-01T12:00:00')".to_string(),
+        _ => format!("'{}'", name.replace('\'', "\\'")),
+    }
+}
+
+/// Constraint and index statements for the properties of one label or
+/// relationship type. `pattern` is `(n:User)` or `()-[r:FOLLOWS]-()`.
+fn property_statements(
+    statements: &mut Vec<String>,
+    seen: &mut HashSet<String>,
+    prefix: &str,
+    pattern: &str,
+    var: &str,
+    properties: &[(&str, &[Attribute])],
+) {
+    let mut push = |name: String, body: String| {
+        if seen.insert(name.clone()) {
+            statements.push(body.replace("{name}", &name));
+        }
+    };
+    let has = |attributes: &[Attribute], attribute: &Attribute| attributes.contains(attribute);
+
+    // Several pk fields form one composite key
+    let keys: Vec<&str> = properties.iter()
+        .filter(|(_, attributes)| has(attributes, &Attribute::PrimaryKey))
+        .map(|(name, _)| *name)
+        .collect();
+    if keys.len() > 1 {
+        let columns: Vec<String> = keys.iter().map(|k| format!("{}.{}", var, quote(k))).collect();
+        push(
+            format!("{}_key_unique", prefix),
+            format!("CREATE CONSTRAINT {{name}} IF NOT EXISTS FOR {} REQUIRE ({}) IS UNIQUE;", pattern, columns.join(", ")),
+        );
+    }
+
+    for (name, attributes) in properties {
+        let primary_key = has(attributes, &Attribute::PrimaryKey);
+        let unique = has(attributes, &Attribute::Unique) || (primary_key && keys.len() == 1);
+        if unique {
+            push(
+                format!("{}_{}_unique", prefix, snake_case(name)),
+                format!("CREATE CONSTRAINT {{name}} IF NOT EXISTS FOR {} REQUIRE {}.{} IS UNIQUE;", pattern, var, quote(name)),
+            );
+        }
+        if primary_key || !has(attributes, &Attribute::Nullable) {
+            push(
+                format!("{}_{}_exists", prefix, snake_case(name)),
+                format!("CREATE CONSTRAINT {{name}} IF NOT EXISTS FOR {} REQUIRE {}.{} IS NOT NULL;", pattern, var, quote(name)),
+            );
+        }
+        // A uniqueness constraint already brings an index with it
+        if has(attributes, &Attribute::Indexed) && !unique {
+            push(
+                format!("{}_{}_index", prefix, snake_case(name)),
+                format!("CREATE INDEX {{name}} IF NOT EXISTS FOR {} ON ({}.{});", pattern, var, quote(name)),
+            );
+        }
+    }
+}
+
+/// Commented `CREATE` statement showing an edge with its properties,
+/// drawn in the direction the edge points
+fn sample_edge(edge: &Edge) -> String {
+    let properties: Vec<String> = edge.properties.iter()
+        .map(|p| format!("{}: {}", quote(&p.name), sample_value(&p.name, &p.datatype, &p.attributes)))
+        .collect();
+    let relationship = if properties.is_empty() {
+        format!("[:{}]", quote(&edge.name))
+    } else {
+        format!("[:{} {{{}}}]", quote(&edge.name), properties.join(", "))
+    };
+    let a = format!("(a:{})", quote(&edge.from_node));
+    let b = format!("(b:{})", quote(&edge.to_node));
+    match edge.edge_type {
+        EdgeType::Outgoing => format!("// CREATE {}-{}->{}", a, relationship, b),
+        EdgeType::Incoming => format!("// CREATE {}<-{}-{}", a, relationship, b),
+        // Relationships are stored with a direction; match bidirectional ones without it
+        EdgeType::Bidirectional => format!(
+            "// CREATE {}-{}->{}\n// MATCH {}-[:{}]-{} RETURN a, b",
+            a, relationship, b, a, quote(&edge.name), b
+        ),
+    }
+}
+
+/// Neo4j schema for the graph half of a schema: uniqueness constraints for
+/// `pk` and `unique` fields, property-existence constraints for non-nullable
+/// ones and indexes for `indexed` ones, with commented sample statements
+/// for each edge when `examples` is set. Tables are not exported.
+pub fn to_cypher(schema: &Schema, examples: bool) -> String {
+    let mut output = String::new();
+    if let Some(title) = &schema.title {
+        output.push_str(&format!("// {}\n", title));
+    }
+    output.push_str("// Neo4j 5 schema. Property-existence constraints need Enterprise Edition.\n");
+    if !schema.tables.is_empty() {
+        output.push_str(&format!("// {} table(s) skipped: only nodes and edges are exported.\n", schema.tables.len()));
+    }
+
+    let mut seen = HashSet::new();
+    for node in &schema.nodes {
+        let properties: Vec<(&str, &[Attribute])> = node.fields.iter()
+            .map(|f| (f.name.as_str(), f.attributes.as_slice()))
+            .collect();
+        let mut statements = Vec::new();
+        let pattern = format!("(n:{})", quote(&node.name));
+        property_statements(&mut statements, &mut seen, &snake_case(&node.name), &pattern, "n", &properties);
+        if !statements.is_empty() {
+            output.push_str(&format!("\n// {}\n{}\n", node.name, statements.join("\n")));
+        }
+    }
+
+    for edge in &schema.edges {
+        let properties: Vec<(&str, &[Attribute])> = edge.properties.iter()
+            .map(|p| (p.name.as_str(), p.attributes.as_slice()))
+            .collect();
+        let mut statements = Vec::new();
+        let pattern = format!("()-[r:{}]-()", quote(&edge.name));
+        property_statements(&mut statements, &mut seen, &snake_case(&edge.name), &pattern, "r", &properties);
+        if !statements.is_empty() {
+            output.push_str(&format!("\n// {}\n{}\n", edge.name, statements.join("\n")));
+        }
+    }
+
+    if examples && !schema.edges.is_empty() {
+        output.push_str("\n// Examples\n");
+        for edge in &schema.edges {
+            output.push_str(&sample_edge(edge));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_cypher_constraints_and_examples() {
+        let schema = Parser::new(r#"
+node User {
+  id: int [pk],
+  username: str [unique, indexed],
+  age: int [nullable],
+  city: str [nullable, indexed]
+}
+node Post {
+  id: int [pk]
+}
+edge FOLLOWS (from: User, to: User) {
+  since: date,
+  weight: float [nullable]
+}
+User -[LIKES]-> Post
+Post <-[TAGGED]- User
+"#).parse().unwrap();
+
+        let cypher = to_cypher(&schema, true);
+        assert!(cypher.contains("// User\n\
+CREATE CONSTRAINT user_id_unique IF NOT EXISTS FOR (n:User) REQUIRE n.id IS UNIQUE;\n\
+CREATE CONSTRAINT user_id_exists IF NOT EXISTS FOR (n:User) REQUIRE n.id IS NOT NULL;\n\
+CREATE CONSTRAINT user_username_unique IF NOT EXISTS FOR (n:User) REQUIRE n.username IS UNIQUE;\n\
+CREATE CONSTRAINT user_username_exists IF NOT EXISTS FOR (n:User) REQUIRE n.username IS NOT NULL;\n\
+CREATE INDEX user_city_index IF NOT EXISTS FOR (n:User) ON (n.city);\n"));
+        assert!(!cypher.contains("user_age"));
+        assert!(cypher.contains("CREATE CONSTRAINT follows_since_exists IF NOT EXISTS FOR ()-[r:FOLLOWS]-() REQUIRE r.since IS NOT NULL;\n"));
+        assert!(cypher.contains("// CREATE (a:User)-[:FOLLOWS {since: date('2024-01-01'), weight: 1.5}]->(b:User)\n// MATCH (a:User)-[:FOLLOWS]-(b:User) RETURN a, b\n"));
+        assert!(cypher.contains("// CREATE (a:User)-[:LIKES]->(b:Post)\n"));
+        assert!(cypher.contains("// CREATE (a:Post)<-[:TAGGED]-(b:User)\n"));
+        assert!(!to_cypher(&schema, false).contains("// CREATE"));
+    }
+}
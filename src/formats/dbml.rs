@@ -0,0 +1,850 @@
+use super::{datatype_from_sql, identifier, ImportError, Imported};
+use crate::ast::*;
+use std::collections::HashMap;
+
+/// DBML column type for a datatype
+fn dbml_type(datatype: &DataType) -> String {
+    match datatype {
+        DataType::String => "varchar".to_string(),
+        DataType::Int => "int".to_string(),
+        DataType::Bool => "boolean".to_string(),
+        DataType::Double => "double".to_string(),
+        DataType::Float => "float".to_string(),
+        DataType::Decimal => "decimal".to_string(),
+        DataType::Date => "date".to_string(),
+        DataType::Time => "time".to_string(),
+        DataType::DateTime => "timestamp".to_string(),
+        DataType::Blob => "blob".to_string(),
+        DataType::TinyBlob => "tinyblob".to_string(),
+        DataType::LargeBlob => "longblob".to_string(),
+        DataType::Custom(name) => name.clone(),
+    }
+}
+
+fn ref_operator(relationship_type: RelationshipType) -> &'static str {
+    match relationship_type {
+        RelationshipType::OneToMany => "<",
+        RelationshipType::ManyToOne => ">",
+        RelationshipType::OneToOne => "-",
+        RelationshipType::ManyToMany => "<>",
+    }
+}
+
+fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn column_settings(column: &Column) -> Vec<String> {
+    let mut settings = Vec::new();
+    for attribute in &column.attributes {
+        match attribute {
+            Attribute::PrimaryKey => settings.push("pk".to_string()),
+            Attribute::Unique => settings.push("unique".to_string()),
+            Attribute::AutoIncrement => settings.push("increment".to_string()),
+            Attribute::Default(value) => settings.push(format!("default: {}", match value {
+                DefaultValue::Now => "`now()`".to_string(),
+                DefaultValue::True => "true".to_string(),
+                DefaultValue::False => "false".to_string(),
+                DefaultValue::Null => "null".to_string(),
+                DefaultValue::String(s) => quote_string(s),
+                DefaultValue::Number(n) => n.to_string(),
+            })),
+            Attribute::Nullable => settings.push("null".to_string()),
+            // Foreign keys are written as Refs, indexes in the Indexes block
+            Attribute::ForeignKey | Attribute::Indexed => {}
+        }
+    }
+    // DBML columns are nullable unless marked otherwise
+    if !column.is_primary_key() && !column.attributes.contains(&Attribute::Nullable) {
+        settings.push("not null".to_string());
+    }
+    settings
+}
+
+/// Render a schema as DBML for dbdiagram.io. The title becomes the project
+/// name and foreign keys become `Ref` lines.
+pub fn to_dbml(schema: &Schema) -> String {
+    let mut blocks = Vec::new();
+
+    if let Some(title) = &schema.title {
+        blocks.push(format!("Project \"{}\" {{\n}}", title.replace('"', "\\\"")));
+    }
+
+    for table in &schema.tables {
+        let mut block = match table.color() {
+            Some(color) => format!("Table {} [headercolor: {}] {{\n", table.name, color),
+            None => format!("Table {} {{\n", table.name),
+        };
+        for column in &table.columns {
+            let settings = column_settings(column);
+            block.push_str(&format!("  {} {}", column.name, dbml_type(&column.datatype)));
+            if !settings.is_empty() {
+                block.push_str(&format!(" [{}]", settings.join(", ")));
+            }
+            block.push('\n');
+        }
+        let indexed: Vec<&str> = table.columns.iter()
+            .filter(|c| c.attributes.contains(&Attribute::Indexed))
+            .map(|c| c.name.as_str())
+            .collect();
+        if !indexed.is_empty() {
+            block.push_str("\n  Indexes {\n");
+            for name in indexed {
+                block.push_str(&format!("    {}\n", name));
+            }
+            block.push_str("  }\n");
+        }
+        block.push('}');
+        blocks.push(block);
+    }
+
+    let refs: Vec<String> = schema.relationships.iter().map(|rel| format!(
+        "Ref: {}.{} {} {}.{}",
+        rel.from_table,
+        rel.from_field,
+        ref_operator(rel.relationship_type),
+        rel.to_table,
+        rel.to_field
+    )).collect();
+    if !refs.is_empty() {
+        blocks.push(refs.join("\n"));
+    }
+
+    if !schema.nodes.is_empty() || !schema.edges.is_empty() {
+        let names: Vec<&str> = schema.nodes.iter().map(|n| n.name.as_str())
+            .chain(schema.edges.iter().map(|e| e.name.as_str()))
+            .collect();
+        blocks.push(format!("// Graph nodes and edges have no DBML equivalent: {}", names.join(", ")));
+    }
+
+    let mut output = blocks.join("\n\n");
+    output.push('\n');
+    output
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Word(String),
+    Str(String),
+    Expr(String),
+    Sym(&'static str),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    line: usize,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ImportError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    let read_until = |i: &mut usize, line: &mut usize, end: &str, start_line: usize| -> Result<String, ImportError> {
+        let end: Vec<char> = end.chars().collect();
+        let mut text = String::new();
+        while *i < chars.len() {
+            if chars[*i..].starts_with(&end) {
+                *i += end.len();
+                return Ok(text);
+            }
+            if chars[*i] == '\\' && *i + 1 < chars.len() {
+                *i += 1;
+            }
+            if chars[*i] == '\n' {
+                *line += 1;
+            }
+            text.push(chars[*i]);
+            *i += 1;
+        }
+        Err(ImportError::new(start_line, "Unterminated string"))
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = line;
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            _ if c.is_whitespace() => i += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                read_until(&mut i, &mut line, "*/", start)?;
+            }
+            '\'' if chars[i..].starts_with(&['\'', '\'', '\'']) => {
+                i += 3;
+                let text = read_until(&mut i, &mut line, "'''", start)?;
+                tokens.push(Token { tok: Tok::Str(text), line: start });
+            }
+            '\'' => {
+                i += 1;
+                let text = read_until(&mut i, &mut line, "'", start)?;
+                tokens.push(Token { tok: Tok::Str(text), line: start });
+            }
+            '"' => {
+                i += 1;
+                let text = read_until(&mut i, &mut line, "\"", start)?;
+                tokens.push(Token { tok: Tok::Word(text), line: start });
+            }
+            '`' => {
+                i += 1;
+                let text = read_until(&mut i, &mut line, "`", start)?;
+                tokens.push(Token { tok: Tok::Expr(text), line: start });
+            }
+            '<' if chars.get(i + 1) == Some(&'>') => {
+                tokens.push(Token { tok: Tok::Sym("<>"), line });
+                i += 2;
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '#' => {
+                let mut word = String::new();
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '#') {
+                    word.push(chars[i]);
+                    i += 1;
+                }
+                tokens.push(Token { tok: Tok::Word(word), line });
+            }
+            _ => {
+                let sym = match c {
+                    '{' => "{",
+                    '}' => "}",
+                    '[' => "[",
+                    ']' => "]",
+                    '(' => "(",
+                    ')' => ")",
+                    ',' => ",",
+                    ':' => ":",
+                    '.' => ".",
+                    '<' => "<",
+                    '>' => ">",
+                    '-' => "-",
+                    other => return Err(ImportError::new(line, format!("Unexpected character '{}'", other))),
+                };
+                tokens.push(Token { tok: Tok::Sym(sym), line });
+                i += 1;
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// One `key` or `key: value` entry from a `[...]` settings list
+struct Setting {
+    key: String,
+    value: Vec<Tok>,
+}
+
+/// Reference between table columns, resolved once every table is known
+struct PendingRef {
+    line: usize,
+    from_table: String,
+    from_columns: Vec<String>,
+    to_table: String,
+    to_columns: Vec<String>,
+    relationship_type: RelationshipType,
+}
+
+struct DbmlParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    schema: Schema,
+    aliases: HashMap<String, String>,
+    refs: Vec<PendingRef>,
+    warnings: Vec<String>,
+}
+
+impl DbmlParser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Tok> {
+        self.tokens.get(self.pos + offset).map(|t| &t.tok)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens.get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    fn next(&mut self) -> Option<Tok> {
+        let tok = self.tokens.get(self.pos).map(|t| t.tok.clone());
+        self.pos += 1;
+        tok
+    }
+
+    fn is_sym(&self, sym: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Sym(s)) if *s == sym)
+    }
+
+    fn eat_sym(&mut self, sym: &str) -> bool {
+        let found = self.is_sym(sym);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_sym(&mut self, sym: &str) -> Result<(), ImportError> {
+        if self.eat_sym(sym) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected '{}'", sym)))
+        }
+    }
+
+    fn word(&mut self, what: &str) -> Result<String, ImportError> {
+        match self.peek() {
+            Some(Tok::Word(w)) => {
+                let w = w.clone();
+                self.pos += 1;
+                Ok(w)
+            }
+            _ => Err(self.error(format!("Expected {}", what))),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> ImportError {
+        let found = match self.peek() {
+            Some(Tok::Word(w)) => format!(" but found '{}'", w),
+            Some(Tok::Sym(s)) => format!(" but found '{}'", s),
+            Some(Tok::Str(_)) => " but found a string".to_string(),
+            Some(Tok::Expr(_)) => " but found an expression".to_string(),
+            None => " but reached the end of the file".to_string(),
+        };
+        ImportError::new(self.line(), format!("{}{}", message.into(), found))
+    }
+
+    /// Skip a `{ ... }` block, including nested braces
+    fn skip_block(&mut self) -> Result<(), ImportError> {
+        self.expect_sym("{")?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next() {
+                Some(Tok::Sym("{")) => depth += 1,
+                Some(Tok::Sym("}")) => depth -= 1,
+                Some(_) => {}
+                None => return Err(self.error("Expected '}'")),
+            }
+        }
+        Ok(())
+    }
+
+    /// `schema.name` or `name`; the schema prefix is dropped
+    fn qualified_name(&mut self, what: &str) -> Result<String, ImportError> {
+        let mut name = self.word(what)?;
+        while self.is_sym(".") && matches!(self.peek_at(1), Some(Tok::Word(_))) {
+            self.pos += 1;
+            name = self.word(what)?;
+        }
+        Ok(name)
+    }
+
+    fn settings(&mut self) -> Result<Vec<Setting>, ImportError> {
+        let mut settings = Vec::new();
+        if !self.eat_sym("[") {
+            return Ok(settings);
+        }
+        loop {
+            if self.eat_sym("]") {
+                return Ok(settings);
+            }
+            let mut words = Vec::new();
+            while let Some(Tok::Word(w)) = self.peek() {
+                words.push(w.to_lowercase());
+                self.pos += 1;
+            }
+            if words.is_empty() {
+                return Err(self.error("Expected a setting"));
+            }
+            let mut value = Vec::new();
+            if self.eat_sym(":") {
+                while !self.is_sym(",") && !self.is_sym("]") {
+                    value.push(self.next().ok_or_else(|| self.error("Expected ']'"))?);
+                }
+            }
+            settings.push(Setting { key: words.join(" "), value });
+            if !self.eat_sym(",") && !self.is_sym("]") {
+                return Err(self.error("Expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn parse(&mut self) -> Result<(), ImportError> {
+        while let Some(tok) = self.peek().cloned() {
+            let line = self.line();
+            let Tok::Word(keyword) = tok else {
+                return Err(self.error("Expected Table, Ref, Enum or Project"));
+            };
+            self.pos += 1;
+            match keyword.to_lowercase().as_str() {
+                "project" => {
+                    let name = self.word("project name")?;
+                    self.schema.title = Some(name);
+                    self.skip_block()?;
+                }
+                "table" => self.table()?,
+                "ref" => {
+                    if matches!(self.peek(), Some(Tok::Word(_))) {
+                        self.pos += 1;
+                    }
+                    if self.eat_sym(":") {
+                        self.reference()?;
+                    } else {
+                        self.expect_sym("{")?;
+                        while !self.eat_sym("}") {
+                            self.reference()?;
+                        }
+                    }
+                }
+                "enum" => self.enumeration()?,
+                "tablegroup" | "note" => {
+                    while !self.is_sym("{") && self.peek().is_some() {
+                        self.pos += 1;
+                    }
+                    self.skip_block()?;
+                    self.warnings.push(format!("line {}: {} blocks are not kept", line, keyword));
+                }
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.error("Expected Table, Ref, Enum or Project"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn table(&mut self) -> Result<(), ImportError> {
+        let original = self.qualified_name("table name")?;
+        let name = identifier(&original);
+        if matches!(self.peek(), Some(Tok::Word(w)) if w.eq_ignore_ascii_case("as")) {
+            self.pos += 1;
+            let alias = self.word("table alias")?;
+            self.aliases.insert(alias, name.clone());
+        }
+        self.aliases.insert(original, name.clone());
+
+        let mut table = Table { name, columns: Vec::new(), attributes: Vec::new(), span: None };
+        for setting in self.settings()? {
+            match (setting.key.as_str(), setting.value.first()) {
+                ("headercolor", Some(Tok::Word(color))) => table.attributes.push(TableAttribute::Color(color.clone())),
+                ("note", _) => self.warnings.push(format!("Note on table '{}' is not kept", table.name)),
+                _ => {}
+            }
+        }
+
+        self.expect_sym("{")?;
+        let mut indexes = Vec::new();
+        while !self.eat_sym("}") {
+            let keyword = match self.peek() {
+                Some(Tok::Word(w)) => w.to_lowercase(),
+                _ => return Err(self.error("Expected a column")),
+            };
+            if keyword == "note" && matches!(self.peek_at(1), Some(Tok::Sym(":")) | Some(Tok::Sym("{"))) {
+                self.pos += 1;
+                if self.eat_sym(":") {
+                    self.next();
+                } else {
+                    self.skip_block()?;
+                }
+                self.warnings.push(format!("Note on table '{}' is not kept", table.name));
+            } else if keyword == "indexes" && matches!(self.peek_at(1), Some(Tok::Sym("{"))) {
+                self.pos += 2;
+                while !self.eat_sym("}") {
+                    indexes.push(self.index()?);
+                }
+            } else {
+                let column = self.column(&table.name)?;
+                table.columns.push(column);
+            }
+        }
+
+        for (line, columns, settings) in indexes {
+            let keys: Vec<&str> = settings.iter().map(|s| s.key.as_str()).collect();
+            let attribute = if keys.contains(&"pk") || keys.contains(&"primary key") {
+                Attribute::PrimaryKey
+            } else if keys.contains(&"unique") && columns.len() == 1 {
+                Attribute::Unique
+            } else {
+                if keys.contains(&"unique") {
+                    self.warnings.push(format!(
+                        "Composite unique index ({}) on '{}' is kept as separate indexes",
+                        columns.join(", "),
+                        table.name
+                    ));
+                }
+                Attribute::Indexed
+            };
+            for name in &columns {
+                let column = table.columns.iter_mut()
+                    .find(|c| c.name == identifier(name))
+                    .ok_or_else(|| ImportError::new(line, format!("Index on unknown column '{}.{}'", table.name, name)))?;
+                if !column.attributes.contains(&attribute) {
+                    column.attributes.push(attribute.clone());
+                }
+            }
+        }
+
+        self.schema.tables.push(table);
+        Ok(())
+    }
+
+    fn column(&mut self, table: &str) -> Result<Column, ImportError> {
+        let line = self.line();
+        let name = identifier(&self.word("column name")?);
+
+        let mut type_name = self.qualified_name("column type")?;
+        if self.eat_sym("(") {
+            while !self.eat_sym(")") {
+                self.next().ok_or_else(|| self.error("Expected ')'"))?;
+            }
+        }
+        if self.is_sym("[") && matches!(self.peek_at(1), Some(Tok::Sym("]"))) {
+            self.pos += 2;
+            type_name.push_str("_array");
+        }
+
+        // Enum columns keep the enum's name as a custom type
+        let datatype = datatype_from_sql(&type_name);
+        let mut attributes = Vec::new();
+        if type_name.to_lowercase().ends_with("serial") {
+            attributes.push(Attribute::AutoIncrement);
+        }
+
+        let mut not_null = false;
+        for setting in self.settings()? {
+            match setting.key.as_str() {
+                "pk" | "primary key" => attributes.push(Attribute::PrimaryKey),
+                "unique" => attributes.push(Attribute::Unique),
+                "increment" => {
+                    if !attributes.contains(&Attribute::AutoIncrement) {
+                        attributes.push(Attribute::AutoIncrement);
+                    }
+                }
+                "not null" => not_null = true,
+                "null" => attributes.push(Attribute::Nullable),
+                "default" => attributes.push(Attribute::Default(default_value(&setting.value))),
+                "note" => self.warnings.push(format!("Note on column '{}.{}' is not kept", table, name)),
+                "ref" => {
+                    let mut tokens = setting.value.iter();
+                    let relationship_type = match tokens.next() {
+                        Some(Tok::Sym("<")) => RelationshipType::OneToMany,
+                        Some(Tok::Sym(">")) => RelationshipType::ManyToOne,
+                        Some(Tok::Sym("-")) => RelationshipType::OneToOne,
+                        Some(Tok::Sym("<>")) => RelationshipType::ManyToMany,
+                        _ => return Err(ImportError::new(line, format!("Invalid ref on '{}.{}'", table, name))),
+                    };
+                    let words: Vec<&str> = tokens.filter_map(|t| match t {
+                        Tok::Word(w) => Some(w.as_str()),
+                        _ => None,
+                    }).collect();
+                    let [.., to_table, to_column] = words[..] else {
+                        return Err(ImportError::new(line, format!("Invalid ref on '{}.{}'", table, name)));
+                    };
+                    self.refs.push(PendingRef {
+                        line,
+                        from_table: table.to_string(),
+                        from_columns: vec![name.clone()],
+                        to_table: to_table.to_string(),
+                        to_columns: vec![to_column.to_string()],
+                        relationship_type,
+                    });
+                }
+                other => self.warnings.push(format!("Setting '{}' on '{}.{}' is not kept", other, table, name)),
+            }
+        }
+
+        if !not_null && !attributes.contains(&Attribute::PrimaryKey) && !attributes.contains(&Attribute::Nullable) {
+            attributes.push(Attribute::Nullable);
+        }
+
+        Ok(Column { name, datatype, attributes, span: None })
+    }
+
+    /// One entry of an `Indexes` block: `col`, `(a, b)` or an expression
+    fn index(&mut self) -> Result<(usize, Vec<String>, Vec<Setting>), ImportError> {
+        let line = self.line();
+        let mut columns = Vec::new();
+        let mut expression = false;
+        if self.eat_sym("(") {
+            while !self.eat_sym(")") {
+                match self.next() {
+                    Some(Tok::Word(w)) => columns.push(w),
+                    Some(Tok::Expr(_)) => expression = true,
+                    Some(Tok::Sym(",")) => {}
+                    _ => return Err(ImportError::new(line, "Expected an index column")),
+                }
+            }
+        } else {
+            match self.next() {
+                Some(Tok::Word(w)) => columns.push(w),
+                Some(Tok::Expr(_)) => expression = true,
+                _ => return Err(ImportError::new(line, "Expected an index column")),
+            }
+        }
+        let settings = self.settings()?;
+        if expression {
+            self.warnings.push(format!("line {}: expression indexes are not kept", line));
+        }
+        Ok((line, columns, settings))
+    }
+
+    /// `table.column` or `table.(a, b)`
+    fn endpoint(&mut self) -> Result<(String, Vec<String>), ImportError> {
+        let mut parts = vec![self.word("table name")?];
+        while self.eat_sym(".") {
+            if self.eat_sym("(") {
+                let mut columns = Vec::new();
+                while !self.eat_sym(")") {
+                    if !self.eat_sym(",") {
+                        columns.push(self.word("column name")?);
+                    }
+                }
+                let table = parts.pop().unwrap_or_default();
+                return Ok((table, columns));
+            }
+            parts.push(self.word("column name")?);
+        }
+        if parts.len() < 2 {
+            return Err(self.error("Expected 'table.column'"));
+        }
+        let column = parts.pop().unwrap_or_default();
+        let table = parts.pop().unwrap_or_default();
+        Ok((table, vec![column]))
+    }
+
+    fn reference(&mut self) -> Result<(), ImportError> {
+        let line = self.line();
+        let (from_table, from_columns) = self.endpoint()?;
+        let relationship_type = match self.next() {
+            Some(Tok::Sym("<")) => RelationshipType::OneToMany,
+            Some(Tok::Sym(">")) => RelationshipType::ManyToOne,
+            Some(Tok::Sym("-")) => RelationshipType::OneToOne,
+            Some(Tok::Sym("<>")) => RelationshipType::ManyToMany,
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("Expected '<', '>', '-' or '<>'"));
+            }
+        };
+        let (to_table, to_columns) = self.endpoint()?;
+        self.settings()?;
+        if from_columns.len() != to_columns.len() {
+            return Err(ImportError::new(line, "Composite ref has a different number of columns on each side"));
+        }
+        self.refs.push(PendingRef { line, from_table, from_columns, to_table, to_columns, relationship_type });
+        Ok(())
+    }
+
+    fn enumeration(&mut self) -> Result<(), ImportError> {
+        let name = self.qualified_name("enum name")?;
+        self.expect_sym("{")?;
+        while !self.eat_sym("}") {
+            match self.next() {
+                Some(Tok::Word(_)) | Some(Tok::Str(_)) => {
+                    self.settings()?;
+                }
+                _ => return Err(self.error("Expected an enum value")),
+            }
+        }
+        self.warnings.push(format!("Enum '{}' is kept as a custom type without its values", name));
+        Ok(())
+    }
+
+    /// Turn refs into relationships, marking the referencing column as `[fk]`
+    fn resolve_refs(&mut self) -> Result<(), ImportError> {
+        for pending in std::mem::take(&mut self.refs) {
+            let table_name = |name: &str| self.aliases.get(name).cloned().unwrap_or_else(|| identifier(name));
+            let from_table = table_name(&pending.from_table);
+            let to_table = table_name(&pending.to_table);
+
+            for (from, to) in pending.from_columns.iter().zip(&pending.to_columns) {
+                let (from, to) = (identifier(from), identifier(to));
+                for (table, column) in [(&from_table, &from), (&to_table, &to)] {
+                    let exists = self.schema.tables.iter()
+                        .any(|t| t.name == *table && t.columns.iter().any(|c| c.name == *column));
+                    if !exists {
+                        return Err(ImportError::new(pending.line, format!("Ref to unknown column '{}.{}'", table, column)));
+                    }
+                }
+
+                let referencing = match pending.relationship_type {
+                    RelationshipType::OneToMany => Some((&to_table, &to)),
+                    RelationshipType::ManyToOne | RelationshipType::OneToOne => Some((&from_table, &from)),
+                    RelationshipType::ManyToMany => None,
+                };
+                if let Some((table, column)) = referencing {
+                    let column = self.schema.tables.iter_mut()
+                        .filter(|t| t.name == *table)
+                        .flat_map(|t| t.columns.iter_mut())
+                        .find(|c| c.name == *column);
+                    if let Some(column) = column {
+                        if !column.is_foreign_key() {
+                            column.attributes.push(Attribute::ForeignKey);
+                        }
+                    }
+                }
+
+                self.schema.relationships.push(Relationship {
+                    from_table: from_table.clone(),
+                    from_field: from,
+                    to_table: to_table.clone(),
+                    to_field: to,
+                    relationship_type: pending.relationship_type,
+                    span: None,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_value(value: &[Tok]) -> DefaultValue {
+    match value {
+        [Tok::Str(s)] => DefaultValue::String(s.clone()),
+        [Tok::Expr(e)] => match e.trim().to_lowercase().as_str() {
+            "now()" | "current_timestamp" | "current_timestamp()" => DefaultValue::Now,
+            _ => DefaultValue::String(e.clone()),
+        },
+        [Tok::Sym("-"), Tok::Word(n)] => n.parse::<i64>()
+            .map(|n| DefaultValue::Number(-n))
+            .unwrap_or_else(|_| DefaultValue::String(format!("-{}", n))),
+        [Tok::Word(w)] => match w.to_lowercase().as_str() {
+            "true" => DefaultValue::True,
+            "false" => DefaultValue::False,
+            "null" => DefaultValue::Null,
+            _ => w.parse::<i64>().map(DefaultValue::Number).unwrap_or_else(|_| DefaultValue::String(w.clone())),
+        },
+        // Decimals such as `1.5` and other literals are kept as text
+        _ => DefaultValue::String(value.iter().map(|t| match t {
+            Tok::Word(w) | Tok::Str(w) | Tok::Expr(w) => w.clone(),
+            Tok::Sym(s) => s.to_string(),
+        }).collect()),
+    }
+}
+
+/// Build a schema from DBML. Notes, enum values and composite unique
+/// indexes have no .frd equivalent and are reported as warnings.
+pub fn from_dbml(source: &str) -> Result<Imported, ImportError> {
+    let mut parser = DbmlParser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        schema: Schema::new(),
+        aliases: HashMap::new(),
+        refs: Vec::new(),
+        warnings: Vec::new(),
+    };
+    parser.parse()?;
+    parser.resolve_refs()?;
+    Ok(Imported { schema: parser.schema, warnings: parser.warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_export_and_import_round_trip() {
+        let schema = Parser::new(r##"
+#title "Blog"
+table Users [color="#ff0000"] {
+  id: int [pk, autoincrement],
+  email: str [unique, indexed],
+  bio: str [nullable, default="it's me"],
+  created_at: datetime [default=NOW]
+}
+table Posts {
+  id: int [pk],
+  user_id: int [fk],
+  views: int [default=0]
+}
+Users.id > Posts.user_id
+"##).parse().unwrap();
+
+        let dbml = to_dbml(&schema);
+        assert!(dbml.starts_with("Project \"Blog\" {\n}\n\nTable Users [headercolor: #ff0000] {\n"));
+        assert!(dbml.contains("  id int [pk, increment]\n"));
+        assert!(dbml.contains("  bio varchar [null, default: 'it\\'s me']\n"));
+        assert!(dbml.contains("  created_at timestamp [default: `now()`, not null]\n"));
+        assert!(dbml.contains("  Indexes {\n    email\n  }\n"));
+        assert!(dbml.contains("Ref: Users.id < Posts.user_id"));
+
+        let mut original = schema.clone();
+        for table in &mut original.tables {
+            table.span = None;
+            for column in &mut table.columns {
+                column.span = None;
+            }
+        }
+        for rel in &mut original.relationships {
+            rel.span = None;
+        }
+        let imported = from_dbml(&dbml).unwrap();
+        assert!(imported.warnings.is_empty());
+        assert_eq!(imported.schema, original);
+    }
+
+    #[test]
+    fn test_import_dbdiagram_features() {
+        let imported = from_dbml(r#"
+// Sample from dbdiagram.io
+Enum order_status {
+  created
+  shipped [note: 'on its way']
+}
+
+Table users as U {
+  id integer [primary key]
+  name varchar(255) [not null, note: 'full name']
+}
+
+Table orders {
+  id serial [pk]
+  user_id integer [not null, ref: > U.id]
+  status order_status
+  total decimal(10, 2) [default: -1]
+  Indexes {
+    (user_id, status) [unique]
+    `lower(status)`
+  }
+  Note: '''
+  All orders
+  '''
+}
+
+Ref: orders.id - users.id
+"#).unwrap();
+
+        let orders = &imported.schema.tables[1];
+        assert_eq!(orders.columns[0].attributes, vec![Attribute::AutoIncrement, Attribute::PrimaryKey, Attribute::ForeignKey]);
+        assert_eq!(orders.columns[1].attributes, vec![Attribute::Indexed, Attribute::ForeignKey]);
+        assert_eq!(orders.columns[2].datatype, DataType::Custom("order_status".to_string()));
+        assert_eq!(orders.columns[2].attributes, vec![Attribute::Nullable, Attribute::Indexed]);
+        assert_eq!(orders.columns[3].datatype, DataType::Decimal);
+        assert_eq!(orders.columns[3].attributes[0], Attribute::Default(DefaultValue::Number(-1)));
+
+        let rel = &imported.schema.relationships[0];
+        assert_eq!((rel.from_table.as_str(), rel.from_field.as_str()), ("orders", "user_id"));
+        assert_eq!((rel.to_table.as_str(), rel.relationship_type), ("users", RelationshipType::ManyToOne));
+        assert_eq!(imported.schema.relationships[1].relationship_type, RelationshipType::OneToOne);
+        assert_eq!(imported.warnings.len(), 5);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let err = from_dbml("Table users {\n  id int\n}\nRef: users.id > posts.user_id").unwrap_err();
+        assert_eq!(err, ImportError::new(4, "Ref to unknown column 'posts.user_id'"));
+        assert_eq!(from_dbml("Table users {\n  id int [pk\n").unwrap_err().line, 2);
+    }
+}
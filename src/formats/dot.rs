@@ -0,0 +1,164 @@
+use super::xml_escape;
+use crate::ast::*;
+
+const HEADER_COLOR: &str = "#3498db";
+const NODE_COLOR: &str = "#8e44ad";
+
+/// Graphviz arrow shapes for the (from, to) ends of a relationship
+fn arrows(relationship_type: RelationshipType) -> (&'static str, &'static str) {
+    match relationship_type {
+        RelationshipType::OneToOne => ("teetee", "teetee"),
+        RelationshipType::OneToMany => ("teetee", "crow"),
+        RelationshipType::ManyToOne => ("crow", "teetee"),
+        RelationshipType::ManyToMany => ("crow", "crow"),
+    }
+}
+
+/// Quoted DOT identifier
+fn id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn field_label(datatype: &DataType, attributes: &[Attribute]) -> String {
+    let markers: Vec<String> = attributes.iter().map(|a| a.to_string()).collect();
+    if markers.is_empty() {
+        datatype.to_string()
+    } else {
+        format!("{} [{}]", datatype, markers.join(", "))
+    }
+}
+
+/// HTML-like table label with a header row and one port per field
+fn html_label(name: &str, color: &str, fields: &[(&str, String, bool)]) -> String {
+    let mut label = String::from("<<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\" CELLPADDING=\"4\">\n");
+    label.push_str(&format!(
+        "      <TR><TD COLSPAN=\"2\" BGCOLOR=\"{}\"><FONT COLOR=\"white\"><B>{}</B></FONT></TD></TR>\n",
+        xml_escape(color),
+        xml_escape(name)
+    ));
+    for (field, details, key) in fields {
+        let field_name = if *key { format!("<U>{}</U>", xml_escape(field)) } else { xml_escape(field) };
+        label.push_str(&format!(
+            "      <TR><TD PORT=\"{}\" ALIGN=\"LEFT\">{}</TD><TD ALIGN=\"LEFT\"><FONT COLOR=\"#555555\">{}</FONT></TD></TR>\n",
+            xml_escape(field),
+            field_name,
+            xml_escape(details)
+        ));
+    }
+    label.push_str("    </TABLE>>");
+    label
+}
+
+/// Render a schema as a Graphviz digraph. Tables use HTML-like labels with a
+/// port per column so relationships connect the joined columns; graph nodes
+/// and edges are grouped in their own cluster.
+pub fn to_dot(schema: &Schema) -> String {
+    let mut output = String::from("digraph ERD {\n");
+    output.push_str("  graph [rankdir=LR, fontname=\"Helvetica\"");
+    if let Some(title) = &schema.title {
+        output.push_str(&format!(", label={}, labelloc=t, fontsize=20", id(title)));
+    }
+    output.push_str("];\n");
+    output.push_str("  node [shape=plaintext, fontname=\"Helvetica\", fontsize=11];\n");
+    output.push_str("  edge [fontname=\"Helvetica\", fontsize=10];\n");
+
+    for table in &schema.tables {
+        let fields: Vec<(&str, String, bool)> = table.columns.iter()
+            .map(|c| (c.name.as_str(), field_label(&c.datatype, &c.attributes), c.is_primary_key()))
+            .collect();
+        let color = table.color().unwrap_or(HEADER_COLOR);
+        output.push_str(&format!("\n  {} [label={}];\n", id(&table.name), html_label(&table.name, color, &fields)));
+    }
+
+    if !schema.relationships.is_empty() {
+        output.push('\n');
+    }
+    for rel in &schema.relationships {
+        let (tail, head) = arrows(rel.relationship_type);
+        output.push_str(&format!(
+            "  {}:{}:e -> {}:{}:w [dir=both, arrowtail={}, arrowhead={}];\n",
+            id(&rel.from_table),
+            id(&rel.from_field),
+            id(&rel.to_table),
+            id(&rel.to_field),
+            tail,
+            head
+        ));
+    }
+
+    if !schema.nodes.is_empty() || !schema.edges.is_empty() {
+        output.push_str("\n  subgraph cluster_graph {\n");
+        output.push_str("    label=\"Graph model\";\n    style=dashed;\n    color=\"#999999\";\n");
+        for node in &schema.nodes {
+            let fields: Vec<(&str, String, bool)> = node.fields.iter()
+                .map(|f| (f.name.as_str(), field_label(&f.datatype, &f.attributes), f.attributes.contains(&Attribute::PrimaryKey)))
+                .collect();
+            output.push_str(&format!("\n    {} [label={}];\n", id(&node.name), html_label(&node.name, NODE_COLOR, &fields)));
+        }
+        if !schema.edges.is_empty() {
+            output.push('\n');
+        }
+        for edge in &schema.edges {
+            let dir = match edge.edge_type {
+                EdgeType::Outgoing => "forward",
+                EdgeType::Incoming => "back",
+                EdgeType::Bidirectional => "both",
+            };
+            let mut label = edge.name.clone();
+            for property in &edge.properties {
+                label.push_str(&format!("\\n{}: {}", property.name, property.datatype));
+            }
+            output.push_str(&format!(
+                "    {} -> {} [label=\"{}\", dir={}];\n",
+                id(&edge.from_node),
+                id(&edge.to_node),
+                label.replace('"', "\\\""),
+                dir
+            ));
+        }
+        output.push_str("  }\n");
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_dot_export() {
+        let schema = Parser::new(r##"
+#title "Shop"
+table Users [color="#ff0000"] {
+  id: int [pk],
+  name: str
+}
+table Orders {
+  id: int [pk],
+  user_id: int [fk]
+}
+Users.id > Orders.user_id
+node Person {
+  id: int [pk]
+}
+edge KNOWS (from: Person, to: Person) {
+  since: date
+}
+"##).parse().unwrap();
+
+        let dot = to_dot(&schema);
+        assert!(dot.starts_with("digraph ERD {\n"));
+        assert!(dot.contains("label=\"Shop\""));
+        assert!(dot.contains("BGCOLOR=\"#ff0000\"><FONT COLOR=\"white\"><B>Users</B>"));
+        assert!(dot.contains("<TD PORT=\"id\" ALIGN=\"LEFT\"><U>id</U></TD>"));
+        assert!(dot.contains("<TD PORT=\"user_id\" ALIGN=\"LEFT\">user_id</TD><TD ALIGN=\"LEFT\"><FONT COLOR=\"#555555\">int [fk]</FONT>"));
+        assert!(dot.contains("  \"Users\":\"id\":e -> \"Orders\":\"user_id\":w [dir=both, arrowtail=teetee, arrowhead=crow];\n"));
+
+        let cluster = &dot[dot.find("subgraph cluster_graph").unwrap()..];
+        assert!(cluster.contains("\"Person\" [label=<<TABLE"));
+        assert!(cluster.contains("\"Person\" -> \"Person\" [label=\"KNOWS\\nsince: date\", dir=both];"));
+    }
+}
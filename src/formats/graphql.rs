@@ -0,0 +1,221 @@
+use super::{lower_first, unique_name};
+use crate::ast::*;
+use crate::codegen::pascal_case;
+use std::collections::{HashMap, HashSet};
+
+/// GraphQL scalar for a datatype, and whether it has to be declared
+fn scalar(datatype: &DataType) -> (String, bool) {
+    match datatype {
+        DataType::String => ("String".to_string(), false),
+        DataType::Int => ("Int".to_string(), false),
+        DataType::Bool => ("Boolean".to_string(), false),
+        DataType::Double | DataType::Float => ("Float".to_string(), false),
+        DataType::Decimal => ("Decimal".to_string(), true),
+        DataType::Date => ("Date".to_string(), true),
+        DataType::Time => ("Time".to_string(), true),
+        DataType::DateTime => ("DateTime".to_string(), true),
+        DataType::Blob | DataType::TinyBlob | DataType::LargeBlob => ("Bytes".to_string(), true),
+        DataType::Custom(name) => (pascal_case(name), true),
+    }
+}
+
+fn field_type(datatype: &DataType, attributes: &[Attribute]) -> String {
+    let (name, _) = scalar(datatype);
+    let nullable = attributes.contains(&Attribute::Nullable) && !attributes.contains(&Attribute::PrimaryKey);
+    if nullable { name } else { format!("{}!", name) }
+}
+
+/// Fields of one object type, in order, plus the names already taken
+#[derive(Default)]
+struct ObjectType {
+    fields: Vec<(String, String)>,
+    used: HashSet<String>,
+}
+
+impl ObjectType {
+    fn push(&mut self, base: String, field_type: String) {
+        let name = unique_name(&mut self.used, base);
+        self.fields.push((name, field_type));
+    }
+}
+
+/// Render tables and graph nodes as GraphQL object types. Relationships
+/// become list or single fields by cardinality; edges become connection
+/// fields whose edge type carries the edge properties.
+pub fn to_graphql(schema: &Schema) -> String {
+    let mut order: Vec<String> = Vec::new();
+    let mut types: HashMap<String, ObjectType> = HashMap::new();
+    let mut scalars: Vec<String> = Vec::new();
+    let mut declare = |datatype: &DataType| {
+        let (name, custom) = scalar(datatype);
+        if custom && !scalars.contains(&name) {
+            scalars.push(name);
+        }
+    };
+
+    for table in &schema.tables {
+        let mut object = ObjectType::default();
+        for column in &table.columns {
+            declare(&column.datatype);
+            object.push(column.name.clone(), field_type(&column.datatype, &column.attributes));
+        }
+        order.push(table.name.clone());
+        types.insert(table.name.clone(), object);
+    }
+    for node in &schema.nodes {
+        let mut object = ObjectType::default();
+        for field in &node.fields {
+            declare(&field.datatype);
+            object.push(field.name.clone(), field_type(&field.datatype, &field.attributes));
+        }
+        order.push(node.name.clone());
+        types.insert(node.name.clone(), object);
+    }
+    for property in schema.edges.iter().flat_map(|e| &e.properties) {
+        declare(&property.datatype);
+    }
+
+    let column_nullable = |table: &str, column: &str| schema.tables.iter()
+        .find(|t| t.name == table)
+        .and_then(|t| t.columns.iter().find(|c| c.name == column))
+        .is_some_and(|c| c.attributes.contains(&Attribute::Nullable));
+
+    // A relationship field on `owner` pointing at `target`
+    let mut relation_field = |owner: &str, target: &str, link: &str, many: bool, nullable: bool| {
+        let base = if many {
+            lower_first(target)
+        } else {
+            link.strip_suffix("_id").or_else(|| link.strip_suffix("Id"))
+                .filter(|stem| !stem.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| lower_first(target))
+        };
+        let field_type = match (many, nullable) {
+            (true, _) => format!("[{}!]!", target),
+            (false, true) => target.to_string(),
+            (false, false) => format!("{}!", target),
+        };
+        if let Some(object) = types.get_mut(owner) {
+            object.push(base, field_type);
+        }
+    };
+
+    for rel in &schema.relationships {
+        let (from_many, to_many) = match rel.relationship_type {
+            RelationshipType::OneToOne => (false, false),
+            RelationshipType::OneToMany => (false, true),
+            RelationshipType::ManyToOne => (true, false),
+            RelationshipType::ManyToMany => (true, true),
+        };
+        // Each side sees the other: a list when the other side is "many"
+        let from_nullable = rel.relationship_type == RelationshipType::OneToOne || column_nullable(&rel.from_table, &rel.from_field);
+        let to_nullable = rel.relationship_type == RelationshipType::OneToOne || column_nullable(&rel.to_table, &rel.to_field);
+        relation_field(&rel.from_table, &rel.to_table, &rel.from_field, to_many, from_nullable);
+        relation_field(&rel.to_table, &rel.from_table, &rel.to_field, from_many, to_nullable);
+    }
+
+    // Connection and edge types, named after the edge and, when it is
+    // followed in both directions between different nodes, the target
+    let mut edge_types = String::new();
+    for edge in &schema.edges {
+        let directions: Vec<(&str, &str)> = match edge.edge_type {
+            EdgeType::Outgoing => vec![(&edge.from_node, &edge.to_node)],
+            EdgeType::Incoming => vec![(&edge.to_node, &edge.from_node)],
+            EdgeType::Bidirectional if edge.from_node == edge.to_node => vec![(&edge.from_node, &edge.to_node)],
+            EdgeType::Bidirectional => vec![(&edge.from_node, &edge.to_node), (&edge.to_node, &edge.from_node)],
+        };
+        for (source, target) in &directions {
+            let base = if directions.len() > 1 {
+                format!("{}{}", pascal_case(&edge.name), target)
+            } else {
+                pascal_case(&edge.name)
+            };
+            edge_types.push_str(&format!("\ntype {}Edge {{\n  node: {}!\n", base, target));
+            for property in &edge.properties {
+                edge_types.push_str(&format!("  {}: {}\n", property.name, field_type(&property.datatype, &property.attributes)));
+            }
+            edge_types.push_str("}\n");
+            edge_types.push_str(&format!("\ntype {}Connection {{\n  edges: [{}Edge!]!\n  totalCount: Int!\n}}\n", base, base));
+            if let Some(object) = types.get_mut(*source) {
+                let field = {
+                    let mut parts = edge.name.split('_').map(|p| p.to_lowercase());
+                    let first = parts.next().unwrap_or_default();
+                    first + &parts.map(|p| pascal_case(&p)).collect::<String>()
+                };
+                object.push(field, format!("{}Connection!", base));
+            }
+        }
+    }
+
+    let mut output = String::new();
+    if let Some(title) = &schema.title {
+        output.push_str(&format!("# {}\n", title));
+    }
+    if !scalars.is_empty() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        for name in &scalars {
+            output.push_str(&format!("scalar {}\n", name));
+        }
+    }
+    for name in &order {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&format!("type {} {{\n", name));
+        for (field, field_type) in &types[name].fields {
+            output.push_str(&format!("  {}: {}\n", field, field_type));
+        }
+        output.push_str("}\n");
+    }
+    output.push_str(&edge_types);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_graphql_types() {
+        let schema = Parser::new(r#"
+#title "Blog"
+table Users {
+  id: int [pk],
+  balance: decimal [nullable]
+}
+table Posts {
+  id: int [pk],
+  user_id: int [fk],
+  published: datetime
+}
+table Tags {
+  id: int [pk]
+}
+Users.id > Posts.user_id
+Posts.id <> Tags.id
+node Person {
+  name: str
+}
+node Company {
+  name: str
+}
+edge WORKS_AT (from: Person, to: Company) {
+  since: date,
+  role: str [nullable]
+}
+"#).parse().unwrap();
+
+        let sdl = to_graphql(&schema);
+        assert!(sdl.starts_with("# Blog\n\nscalar Decimal\nscalar DateTime\nscalar Date\n\ntype Users {\n"));
+        assert!(sdl.contains("type Users {\n  id: Int!\n  balance: Decimal\n  posts: [Posts!]!\n}\n"));
+        assert!(sdl.contains("type Posts {\n  id: Int!\n  user_id: Int!\n  published: DateTime!\n  user: Users!\n  tags: [Tags!]!\n}\n"));
+        assert!(sdl.contains("type Tags {\n  id: Int!\n  posts: [Posts!]!\n}\n"));
+        assert!(sdl.contains("type WorksAtPersonEdge {\n  node: Person!\n  since: Date!\n  role: String\n}\n"));
+        assert!(sdl.contains("type WorksAtCompanyConnection {\n  edges: [WorksAtCompanyEdge!]!\n  totalCount: Int!\n}\n"));
+        assert!(sdl.contains("type Person {\n  name: String!\n  worksAt: WorksAtCompanyConnection!\n}\n"));
+        assert!(sdl.contains("type Company {\n  name: String!\n  worksAt: WorksAtPersonConnection!\n}\n"));
+    }
+}
@@ -0,0 +1,167 @@
+use super::{strip_xml_declaration, xml_escape};
+
+const STYLE: &str = r#"html, body { margin: 0; height: 100%; overflow: hidden; font-family: Arial, sans-serif; }
+#toolbar { position: fixed; top: 10px; left: 10px; z-index: 1; display: flex; gap: 6px; align-items: center;
+  background: rgba(255, 255, 255, 0.92); border: 1px solid #ccc; border-radius: 6px; padding: 6px 8px; }
+#toolbar input { width: 220px; padding: 4px 6px; }
+#toolbar button { min-width: 28px; }
+#status { font-size: 12px; color: #555; }
+#viewport { width: 100%; height: 100%; cursor: grab; }
+#viewport.dragging { cursor: grabbing; }
+#viewport svg { width: 100%; height: 100%; display: block; }
+.table-group, .rel, .rel-label { transition: opacity 0.15s; }
+svg.dim .table-group:not(.active), svg.dim .rel:not(.active), svg.dim .rel-label:not(.active) { opacity: 0.2; }
+.table-group.match .table { stroke-width: 4; stroke-dasharray: none; }
+"#;
+
+const SCRIPT: &str = r#"(function () {
+  const viewport = document.getElementById('viewport');
+  const svg = viewport.querySelector('svg');
+  const search = document.getElementById('search');
+  const status = document.getElementById('status');
+  const tables = Array.from(svg.querySelectorAll('.table-group'));
+  const rels = Array.from(svg.querySelectorAll('.rel, .rel-label'));
+  svg.removeAttribute('width');
+  svg.removeAttribute('height');
+
+  // Pan and zoom by moving the viewBox
+  const initial = svg.getAttribute('viewBox').split(/[\s,]+/).map(Number);
+  let view = initial.slice();
+  function apply() { svg.setAttribute('viewBox', view.join(' ')); }
+  function toSvg(clientX, clientY) {
+    const rect = svg.getBoundingClientRect();
+    const scale = Math.max(view[2] / rect.width, view[3] / rect.height);
+    const offsetX = (rect.width * scale - view[2]) / 2;
+    const offsetY = (rect.height * scale - view[3]) / 2;
+    return [view[0] - offsetX + (clientX - rect.left) * scale, view[1] - offsetY + (clientY - rect.top) * scale, scale];
+  }
+  function zoom(factor, clientX, clientY) {
+    const [x, y] = toSvg(clientX, clientY);
+    view = [x - (x - view[0]) * factor, y - (y - view[1]) * factor, view[2] * factor, view[3] * factor];
+    apply();
+  }
+  function center() {
+    const rect = svg.getBoundingClientRect();
+    return [rect.left + rect.width / 2, rect.top + rect.height / 2];
+  }
+  viewport.addEventListener('wheel', function (e) {
+    e.preventDefault();
+    zoom(e.deltaY > 0 ? 1.15 : 1 / 1.15, e.clientX, e.clientY);
+  }, { passive: false });
+  let drag = null;
+  viewport.addEventListener('mousedown', function (e) {
+    drag = { x: e.clientX, y: e.clientY, view: view.slice(), scale: toSvg(e.clientX, e.clientY)[2] };
+    viewport.classList.add('dragging');
+  });
+  window.addEventListener('mousemove', function (e) {
+    if (!drag) return;
+    view = [drag.view[0] - (e.clientX - drag.x) * drag.scale, drag.view[1] - (e.clientY - drag.y) * drag.scale, view[2], view[3]];
+    apply();
+  });
+  window.addEventListener('mouseup', function () {
+    drag = null;
+    viewport.classList.remove('dragging');
+  });
+  document.getElementById('zoom-in').onclick = function () { zoom(1 / 1.3, ...center()); };
+  document.getElementById('zoom-out').onclick = function () { zoom(1.3, ...center()); };
+  document.getElementById('fit').onclick = function () { view = initial.slice(); apply(); };
+
+  // Hovering a table highlights it, its relationships and the tables at their other ends
+  function clearHighlight() {
+    svg.classList.remove('dim');
+    tables.concat(rels).forEach(function (el) { el.classList.remove('active'); });
+    svg.querySelectorAll('path.relationship-selected').forEach(function (p) { p.setAttribute('class', 'relationship'); });
+  }
+  function highlight(name) {
+    clearHighlight();
+    svg.classList.add('dim');
+    const linked = new Set([name]);
+    rels.forEach(function (el) {
+      if (el.dataset.from === name || el.dataset.to === name) {
+        el.classList.add('active');
+        linked.add(el.dataset.from);
+        linked.add(el.dataset.to);
+        el.querySelectorAll('path.relationship').forEach(function (p) { p.setAttribute('class', 'relationship-selected'); });
+      }
+    });
+    tables.forEach(function (el) { if (linked.has(el.dataset.table)) el.classList.add('active'); });
+  }
+  tables.forEach(function (el) {
+    el.addEventListener('mouseenter', function () { if (!drag) highlight(el.dataset.table); });
+    el.addEventListener('mouseleave', function () { clearHighlight(); applySearch(false); });
+  });
+
+  // Search matches table names, column names and types
+  function applySearch(focus) {
+    const query = search.value.trim().toLowerCase();
+    tables.forEach(function (el) { el.classList.remove('match'); });
+    if (!query) { status.textContent = ''; return; }
+    const matches = tables.filter(function (el) { return el.textContent.toLowerCase().includes(query); });
+    matches.forEach(function (el) { el.classList.add('match', 'active'); });
+    svg.classList.add('dim');
+    status.textContent = matches.length + ' match' + (matches.length === 1 ? '' : 'es');
+    if (focus && matches.length) {
+      const box = matches[0].getBBox();
+      const size = Math.max(box.width, box.height) * 3;
+      view = [box.x + box.width / 2 - size / 2, box.y + box.height / 2 - size / 2, size, size];
+      apply();
+    }
+  }
+  search.addEventListener('input', function () { clearHighlight(); applySearch(false); });
+  search.addEventListener('keydown', function (e) { if (e.key === 'Enter') applySearch(true); });
+  search.addEventListener('mousedown', function (e) { e.stopPropagation(); });
+  document.getElementById('toolbar').addEventListener('mousedown', function (e) { e.stopPropagation(); });
+})();
+"#;
+
+/// Self-contained page around an annotated SVG diagram, with pan and zoom,
+/// hover highlighting of a table's relationships and a search box. The
+/// script and styles are inline so the file works offline.
+pub fn to_html(title: &str, svg: &str) -> String {
+    let title = if title.is_empty() { "FreeERD diagram" } else { title };
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n\
+<div id=\"toolbar\">\n<input id=\"search\" type=\"search\" placeholder=\"Search tables and columns\">\n\
+<button id=\"zoom-in\" title=\"Zoom in\">+</button>\n<button id=\"zoom-out\" title=\"Zoom out\">&#8722;</button>\n\
+<button id=\"fit\" title=\"Fit to window\">Fit</button>\n<span id=\"status\"></span>\n</div>\n\
+<div id=\"viewport\">\n{}\n</div>\n<script>\n{}</script>\n</body>\n</html>\n",
+        xml_escape(title), STYLE, strip_xml_declaration(svg), SCRIPT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_page() {
+        let svg = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n<svg viewBox=\"0 0 10 10\"><g class=\"table-group\" data-table=\"Users\"></g></svg>";
+        let html = to_html("Shop & Co", svg);
+        assert!(html.contains("<title>Shop &amp; Co</title>"));
+        assert!(html.contains("<div id=\"viewport\">\n<svg viewBox=\"0 0 10 10\"><g class=\"table-group\" data-table=\"Users\"></g></svg>\n</div>\n"));
+        assert!(!html.contains("<?xml"));
+        assert!(!html.contains("src=\"http"));
+        assert!(html.contains("<script>\n(function () {"));
+    }
+
+    #[test]
+    fn test_schema_text_is_escaped() {
+        let schema = crate::parser::Parser::new(
+            "#title \"A <b>&</b> \\\"q\\\" </script>\"\ntable Users {\n  name: str [default=\"<i>&\\\"x\\\"\"]\n}\n"
+        ).parse().unwrap();
+        let erd_graph = crate::renderer::ErdGraph::from_schema(&schema).unwrap();
+        let title = schema.title.clone().unwrap();
+        let svg = crate::renderer::export_svg_with(&erd_graph, &title, &crate::renderer::Theme::light(), crate::renderer::SvgOptions {
+            annotate: true,
+            ..Default::default()
+        }).unwrap();
+        let html = to_html(&title, &svg);
+
+        for text in [&svg, &html] {
+            assert!(text.contains(">A &lt;b&gt;&amp;&lt;/b&gt; &quot;q&quot; &lt;/script&gt;</text>"));
+            assert!(text.contains("[default=&quot;&lt;i&gt;&amp;&quot;x&quot;&quot;]</text>"));
+            assert!(!text.contains("<b>"));
+        }
+        assert_eq!(html.matches("</script>").count(), 1);
+    }
+}
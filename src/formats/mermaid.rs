@@ -0,0 +1,490 @@
+use super::{datatype_from_sql, identifier, ImportError};
+use crate::ast::*;
+
+/// Mermaid cardinality between two entities, e.g. `||--o{` for one-to-many
+fn cardinality(relationship_type: RelationshipType) -> &'static str {
+    match relationship_type {
+        RelationshipType::OneToMany => "||--o{",
+        RelationshipType::ManyToOne => "}o--||",
+        RelationshipType::ManyToMany => "}o--o{",
+        RelationshipType::OneToOne => "||--||",
+    }
+}
+
+/// Key markers Mermaid understands; everything else goes in the comment
+fn key_markers(attributes: &[Attribute]) -> Vec<&'static str> {
+    let mut keys = Vec::new();
+    if attributes.contains(&Attribute::PrimaryKey) {
+        keys.push("PK");
+    }
+    if attributes.contains(&Attribute::ForeignKey) {
+        keys.push("FK");
+    }
+    if attributes.contains(&Attribute::Unique) {
+        keys.push("UK");
+    }
+    keys
+}
+
+fn attribute_comment(attributes: &[Attribute]) -> Option<String> {
+    let extra: Vec<String> = attributes.iter().filter_map(|a| match a {
+        Attribute::Nullable => Some("nullable".to_string()),
+        Attribute::Indexed => Some("indexed".to_string()),
+        Attribute::AutoIncrement => Some("autoincrement".to_string()),
+        Attribute::Default(DefaultValue::String(s)) => Some(format!("default={}", quote_default(s))),
+        Attribute::Default(value) => Some(format!("default={}", value)),
+        _ => None,
+    }).collect();
+    (!extra.is_empty()).then(|| extra.join(", "))
+}
+
+/// Single-quoted with `''` for a quote. Mermaid comments can't hold double
+/// quotes, so those (and `#`) are written as Mermaid entity codes.
+fn quote_default(s: &str) -> String {
+    format!("'{}'", s.replace('#', "#35;").replace('"', "#quot;").replace('\'', "''"))
+}
+
+/// Mermaid types are single words
+fn mermaid_type(datatype: &DataType) -> String {
+    datatype.to_string().split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+fn push_attribute_line(output: &mut String, datatype: &DataType, name: &str, attributes: &[Attribute]) {
+    output.push_str(&format!("        {} {}", mermaid_type(datatype), name));
+    let keys = key_markers(attributes);
+    if !keys.is_empty() {
+        output.push_str(&format!(" {}", keys.join(", ")));
+    }
+    if let Some(comment) = attribute_comment(attributes) {
+        output.push_str(&format!(" \"{}\"", comment));
+    }
+    output.push('\n');
+}
+
+/// Render a schema as a Mermaid `erDiagram`. Relationship labels carry the
+/// joined fields as `from:to` so the diagram can be imported again.
+pub fn to_mermaid(schema: &Schema) -> String {
+    let mut output = String::new();
+
+    if let Some(title) = &schema.title {
+        output.push_str(&format!("---\ntitle: {}\n---\n", title));
+    }
+    output.push_str("erDiagram\n");
+
+    for table in &schema.tables {
+        output.push_str(&format!("    {} {{\n", table.name));
+        for column in &table.columns {
+            push_attribute_line(&mut output, &column.datatype, &column.name, &column.attributes);
+        }
+        output.push_str("    }\n");
+    }
+
+    for rel in &schema.relationships {
+        output.push_str(&format!(
+            "    {} {} {} : \"{}:{}\"\n",
+            rel.from_table,
+            cardinality(rel.relationship_type),
+            rel.to_table,
+            rel.from_field,
+            rel.to_field
+        ));
+    }
+
+    // Graph nodes and edges are shown as entities so the whole schema is visible
+    if !schema.nodes.is_empty() {
+        output.push_str("    %% Graph nodes and edges\n");
+    }
+    for node in &schema.nodes {
+        output.push_str(&format!("    {} {{\n", node.name));
+        for field in &node.fields {
+            push_attribute_line(&mut output, &field.datatype, &field.name, &field.attributes);
+        }
+        output.push_str("    }\n");
+    }
+    for edge in &schema.edges {
+        let (from, to) = match edge.edge_type {
+            EdgeType::Incoming => (&edge.to_node, &edge.from_node),
+            _ => (&edge.from_node, &edge.to_node),
+        };
+        output.push_str(&format!("    {} }}o--o{{ {} : \"{}\"\n", from, to, edge.name));
+    }
+
+    output
+}
+
+/// Relationship line before its ends are resolved to columns
+struct PendingRelationship {
+    line: usize,
+    from_table: String,
+    to_table: String,
+    relationship_type: RelationshipType,
+    label: String,
+}
+
+fn unquote(s: &str) -> &str {
+    s.trim().trim_matches('"')
+}
+
+/// Entity name without a display alias, e.g. `CUSTOMER["Customer"]`
+fn entity_name(s: &str) -> String {
+    let name = s.split('[').next().unwrap_or(s);
+    identifier(unquote(name))
+}
+
+fn parse_cardinality(symbol: &str, line: usize) -> Result<RelationshipType, ImportError> {
+    let (left, right) = symbol.split_once("--")
+        .or_else(|| symbol.split_once(".."))
+        .ok_or_else(|| ImportError::new(line, format!("'{}' is not a Mermaid cardinality", symbol)))?;
+    let valid_left = ["|o", "||", "}o", "}|"].contains(&left);
+    let valid_right = ["o|", "||", "o{", "|{"].contains(&right);
+    if !valid_left || !valid_right {
+        return Err(ImportError::new(line, format!("'{}' is not a Mermaid cardinality", symbol)));
+    }
+    Ok(match (left.starts_with('}'), right.ends_with('{')) {
+        (false, true) => RelationshipType::OneToMany,
+        (true, false) => RelationshipType::ManyToOne,
+        (true, true) => RelationshipType::ManyToMany,
+        (false, false) => RelationshipType::OneToOne,
+    })
+}
+
+fn parse_default(value: &str) -> DefaultValue {
+    match value.to_uppercase().as_str() {
+        "NOW" => DefaultValue::Now,
+        "TRUE" => DefaultValue::True,
+        "FALSE" => DefaultValue::False,
+        "NULL" => DefaultValue::Null,
+        _ => match value.parse::<i64>() {
+            Ok(n) => DefaultValue::Number(n),
+            Err(_) => match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+                Some(quoted) => DefaultValue::String(quoted.replace("''", "'").replace("#quot;", "\"").replace("#35;", "#")),
+                None => DefaultValue::String(value.to_string()),
+            },
+        },
+    }
+}
+
+/// Comment parts split on commas outside single-quoted defaults
+fn split_comment(comment: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_string = false;
+    for c in comment.chars() {
+        match c {
+            '\'' => in_string = !in_string,
+            ',' if !in_string => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current.trim().to_string());
+    parts
+}
+
+/// `type name [PK, FK, UK] ["comment"]`
+fn parse_attribute(text: &str, line: usize) -> Result<Column, ImportError> {
+    let (head, comment) = match text.find('"') {
+        Some(pos) => (&text[..pos], Some(text[pos..].trim().trim_matches('"'))),
+        None => (text, None),
+    };
+    let mut words = head.split_whitespace();
+    let (Some(datatype), Some(name)) = (words.next(), words.next()) else {
+        return Err(ImportError::new(line, format!("Expected '<type> <name>' but found '{}'", text.trim())));
+    };
+
+    let mut attributes = Vec::new();
+    let keys = words.collect::<Vec<_>>().join(" ");
+    for key in keys.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+        attributes.push(match key {
+            "PK" => Attribute::PrimaryKey,
+            "FK" => Attribute::ForeignKey,
+            "UK" => Attribute::Unique,
+            other => return Err(ImportError::new(line, format!("Unknown key '{}' (expected PK, FK or UK)", other))),
+        });
+    }
+    // Comments written by the exporter carry the remaining attributes
+    for part in split_comment(comment.unwrap_or("")) {
+        match part.as_str() {
+            "nullable" => attributes.push(Attribute::Nullable),
+            "indexed" => attributes.push(Attribute::Indexed),
+            "autoincrement" => attributes.push(Attribute::AutoIncrement),
+            _ => {
+                if let Some(value) = part.strip_prefix("default=") {
+                    attributes.push(Attribute::Default(parse_default(value)));
+                }
+            }
+        }
+    }
+
+    Ok(Column { name: identifier(name), datatype: datatype_from_sql(datatype), attributes, span: None })
+}
+
+fn table_mut<'a>(schema: &'a mut Schema, name: &str) -> &'a mut Table {
+    if let Some(pos) = schema.tables.iter().position(|t| t.name == name) {
+        return &mut schema.tables[pos];
+    }
+    schema.tables.push(Table { name: name.to_string(), columns: Vec::new(), attributes: Vec::new(), span: None });
+    schema.tables.last_mut().unwrap()
+}
+
+fn key_column(table: &Table) -> &Column {
+    table.columns.iter().find(|c| c.is_primary_key()).unwrap_or(&table.columns[0])
+}
+
+/// Pick the column on the "many" side that references `one`, adding a
+/// `<one>_id` column if the diagram doesn't have one
+fn referencing_column(schema: &mut Schema, many: &str, one: &str, one_key: &Column) -> String {
+    let prefix = one.to_lowercase();
+    let table = table_mut(schema, many);
+    let existing = table.columns.iter()
+        .find(|c| c.is_foreign_key() && c.name.to_lowercase().starts_with(&prefix))
+        .or_else(|| table.columns.iter().find(|c| c.name.to_lowercase() == format!("{}_id", prefix)));
+    if let Some(column) = existing {
+        return column.name.clone();
+    }
+    let name = format!("{}_id", prefix);
+    table.columns.push(Column {
+        name: name.clone(),
+        datatype: one_key.datatype.clone(),
+        attributes: vec![Attribute::ForeignKey],
+        span: None,
+    });
+    name
+}
+
+fn resolve_relationship(schema: &mut Schema, pending: PendingRelationship) -> Result<Relationship, ImportError> {
+    let has_column = |schema: &Schema, table: &str, column: &str| {
+        schema.tables.iter().any(|t| t.name == table && t.columns.iter().any(|c| c.name == column))
+    };
+
+    // Labels written by the exporter name the joined fields
+    if let Some((from_field, to_field)) = pending.label.split_once(':') {
+        if has_column(schema, &pending.from_table, from_field) && has_column(schema, &pending.to_table, to_field) {
+            return Ok(Relationship {
+                from_table: pending.from_table,
+                from_field: from_field.to_string(),
+                to_table: pending.to_table,
+                to_field: to_field.to_string(),
+                relationship_type: pending.relationship_type,
+                span: None,
+            });
+        }
+    }
+
+    let find = |schema: &Schema, name: &str| -> Result<Column, ImportError> {
+        let table = schema.tables.iter().find(|t| t.name == name)
+            .ok_or_else(|| ImportError::new(pending.line, format!("Unknown entity '{}'", name)))?;
+        Ok(key_column(table).clone())
+    };
+    let from_key = find(schema, &pending.from_table)?;
+    let to_key = find(schema, &pending.to_table)?;
+
+    // Otherwise the "many" side (or the right side of one-to-one) holds the reference
+    let (from_field, to_field) = match pending.relationship_type {
+        RelationshipType::OneToMany | RelationshipType::OneToOne => {
+            let to_field = referencing_column(schema, &pending.to_table, &pending.from_table, &from_key);
+            (from_key.name, to_field)
+        }
+        RelationshipType::ManyToOne => {
+            let from_field = referencing_column(schema, &pending.from_table, &pending.to_table, &to_key);
+            (from_field, to_key.name)
+        }
+        RelationshipType::ManyToMany => (from_key.name, to_key.name),
+    };
+
+    Ok(Relationship {
+        from_table: pending.from_table,
+        from_field,
+        to_table: pending.to_table,
+        to_field,
+        relationship_type: pending.relationship_type,
+        span: None,
+    })
+}
+
+/// Build a schema from a Mermaid `erDiagram`. The diagram may be on its own
+/// or inside a Markdown ```mermaid block; the frontmatter title is kept.
+pub fn from_mermaid(source: &str) -> Result<Schema, ImportError> {
+    let lines: Vec<&str> = source.lines().collect();
+    let start = lines.iter().position(|l| l.trim() == "erDiagram")
+        .ok_or_else(|| ImportError::new(1, "No 'erDiagram' found"))?;
+
+    let mut schema = Schema::new();
+    schema.title = lines[..start].iter()
+        .rev()
+        .find_map(|l| l.trim().strip_prefix("title:"))
+        .map(|t| unquote(t).to_string());
+
+    let mut current: Option<String> = None;
+    let mut pending = Vec::new();
+
+    for (idx, raw) in lines.iter().enumerate().skip(start + 1) {
+        let line = idx + 1;
+        let text = raw.trim();
+        if text.starts_with("```") {
+            break;
+        }
+        if text.is_empty() || text.starts_with("%%") || text.starts_with("direction ") {
+            continue;
+        }
+
+        if let Some(table) = &current {
+            if text == "}" {
+                current = None;
+            } else {
+                let column = parse_attribute(text, line)?;
+                table_mut(&mut schema, table).columns.push(column);
+            }
+            continue;
+        }
+
+        if let Some(name) = text.strip_suffix('{') {
+            let name = entity_name(name);
+            table_mut(&mut schema, &name);
+            current = Some(name);
+            continue;
+        }
+
+        let (ends, label) = text.split_once(':')
+            .ok_or_else(|| ImportError::new(line, format!("Expected a relationship 'A ||--o{{ B : label' but found '{}'", text)))?;
+        let parts: Vec<&str> = ends.split_whitespace().collect();
+        let [from, symbol, to] = parts[..] else {
+            return Err(ImportError::new(line, format!("Expected 'A <cardinality> B' but found '{}'", ends.trim())));
+        };
+        let (from, to) = (entity_name(from), entity_name(to));
+        table_mut(&mut schema, &from);
+        table_mut(&mut schema, &to);
+        pending.push(PendingRelationship {
+            line,
+            from_table: from,
+            to_table: to,
+            relationship_type: parse_cardinality(symbol, line)?,
+            label: unquote(label).to_string(),
+        });
+    }
+
+    if let Some(table) = current {
+        return Err(ImportError::new(lines.len(), format!("Entity '{}' is missing its closing '}}'", table)));
+    }
+
+    // Entities that only appear in relationships still need a key to join on
+    for table in &mut schema.tables {
+        if table.columns.is_empty() {
+            table.columns.push(Column {
+                name: "id".to_string(),
+                datatype: DataType::Int,
+                attributes: vec![Attribute::PrimaryKey],
+                span: None,
+            });
+        }
+    }
+
+    for rel in pending {
+        let rel = resolve_relationship(&mut schema, rel)?;
+        schema.relationships.push(rel);
+    }
+
+    Ok(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_export_and_import_round_trip() {
+        let schema = Parser::new(r#"
+#title "Blog"
+table Users {
+  id: int [pk, autoincrement],
+  email: str [unique],
+  bio: str [nullable, default="n/a"]
+}
+table Posts {
+  id: int [pk],
+  user_id: int [fk]
+}
+Users.id > Posts.user_id
+"#).parse().unwrap();
+
+        let mermaid = to_mermaid(&schema);
+        assert!(mermaid.starts_with("---\ntitle: Blog\n---\nerDiagram\n"));
+        assert!(mermaid.contains("        int id PK \"autoincrement\"\n"));
+        assert!(mermaid.contains("        str email UK\n"));
+        assert!(mermaid.contains("        str bio \"nullable, default='n/a'\"\n"));
+        assert!(mermaid.contains("    Users ||--o{ Posts : \"id:user_id\"\n"));
+
+        let mut original = schema.clone();
+        for table in &mut original.tables {
+            table.span = None;
+            for column in &mut table.columns {
+                column.span = None;
+            }
+        }
+        for rel in &mut original.relationships {
+            rel.span = None;
+        }
+        assert_eq!(from_mermaid(&mermaid).unwrap(), original);
+    }
+
+    #[test]
+    fn test_defaults_with_commas_and_quotes_round_trip() {
+        let schema = Parser::new(r##"
+table Notes {
+  body: str [default="a, \"b\", it's #1", nullable],
+  raw: str [default="#quot;"]
+}
+"##).parse().unwrap();
+
+        let mermaid = to_mermaid(&schema);
+        assert!(mermaid.contains("        str body \"default='a, #quot;b#quot;, it''s #35;1', nullable\"\n"));
+
+        let imported = from_mermaid(&mermaid).unwrap();
+        let columns = &imported.tables[0].columns;
+        assert_eq!(columns[0].attributes, vec![
+            Attribute::Default(DefaultValue::String("a, \"b\", it's #1".to_string())),
+            Attribute::Nullable,
+        ]);
+        assert_eq!(columns[1].attributes, vec![Attribute::Default(DefaultValue::String("#quot;".to_string()))]);
+    }
+
+    #[test]
+    fn test_import_infers_foreign_keys() {
+        let source = r#"
+# Docs
+
+```mermaid
+erDiagram
+    CUSTOMER ||--o{ ORDER : places
+    CUSTOMER {
+        string name
+        int custNumber PK
+    }
+    ORDER }|..|| DELIVERY_ADDRESS : uses
+```
+"#;
+        let schema = from_mermaid(source).unwrap();
+        let names: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["CUSTOMER", "ORDER", "DELIVERY_ADDRESS"]);
+
+        let order = &schema.tables[1];
+        assert_eq!(order.columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["id", "customer_id", "delivery_address_id"]);
+        assert_eq!(schema.relationships[0].from_field, "custNumber");
+        assert_eq!(schema.relationships[0].to_field, "customer_id");
+        assert_eq!(schema.relationships[1].relationship_type, RelationshipType::ManyToOne);
+        assert_eq!(schema.relationships[1].from_field, "delivery_address_id");
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert_eq!(from_mermaid("graph TD").unwrap_err().line, 1);
+        let err = from_mermaid("erDiagram\n    A <--> B : x").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(from_mermaid("erDiagram\n    A {\n        int id XK\n    }").unwrap_err().message.contains("XK"));
+    }
+}
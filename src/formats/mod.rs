@@ -0,0 +1,119 @@
+// Text formats other tools understand: exporters turn a Schema into their
+// syntax, importers build a Schema that can be written out as .frd
+pub mod mermaid;
+pub mod dot;
+pub mod plantuml;
+pub mod dbml;
+pub mod prisma;
+pub mod graphql;
+pub mod cypher;
+pub mod sqlite;
+pub mod html;
+
+use crate::ast::{DataType, Schema};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A problem in an imported file, reported with its 1-based line number
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ImportError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        ImportError { line, message: message.into() }
+    }
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// An imported schema plus notes about anything the .frd format can't hold
+#[derive(Debug, Clone, PartialEq)]
+pub struct Imported {
+    pub schema: Schema,
+    pub warnings: Vec<String>,
+}
+
+/// Make a name from another tool usable as a .frd identifier
+pub fn identifier(name: &str) -> String {
+    let mut ident: String = name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_numeric()) {
+        ident.insert(0, '_');
+    }
+    // Keywords can't be used as names
+    if matches!(ident.to_lowercase().as_str(), "table" | "edge" | "node") {
+        ident.push('_');
+    }
+    ident
+}
+
+/// Escape text for XML and HTML-like labels
+pub fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// SVG markup without its `<?xml ... ?>` declaration, for inlining in HTML
+pub fn strip_xml_declaration(svg: &str) -> &str {
+    let svg = svg.trim_start();
+    svg.strip_prefix("<?xml")
+        .and_then(|rest| rest.split_once("?>"))
+        .map_or(svg, |(_, rest)| rest)
+        .trim()
+}
+
+/// `Posts` as `posts`, for field names derived from type names
+pub fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// `base`, or `base2`, `base3`... if a type already has a field by that name
+pub fn unique_name(used: &mut HashSet<String>, base: String) -> String {
+    let mut name = base.clone();
+    let mut n = 2;
+    while used.contains(&name) {
+        name = format!("{}{}", base, n);
+        n += 1;
+    }
+    used.insert(name.clone());
+    name
+}
+
+/// Map a SQL-style type name such as `varchar(255)` or `timestamptz` onto a
+/// datatype. Unknown types are kept by name as custom types.
+pub fn datatype_from_sql(name: &str) -> DataType {
+    let base = name.split('(').next().unwrap_or(name).trim().to_lowercase();
+    match base.as_str() {
+        "varchar" | "char" | "character" | "character varying" | "nvarchar" | "nchar"
+        | "text" | "tinytext" | "mediumtext" | "longtext" | "string" | "str" | "citext" => DataType::String,
+        "int" | "integer" | "smallint" | "bigint" | "tinyint" | "mediumint" | "int2" | "int4" | "int8"
+        | "serial" | "smallserial" | "bigserial" => DataType::Int,
+        "bool" | "boolean" => DataType::Bool,
+        "double" | "double precision" | "float8" => DataType::Double,
+        "float" | "real" | "float4" => DataType::Float,
+        "decimal" | "numeric" | "money" => DataType::Decimal,
+        "date" => DataType::Date,
+        "time" | "timetz" => DataType::Time,
+        "datetime" | "timestamp" | "timestamptz" => DataType::DateTime,
+        "blob" | "bytea" | "binary" | "varbinary" => DataType::Blob,
+        "tinyblob" => DataType::TinyBlob,
+        "longblob" | "mediumblob" | "largeblob" => DataType::LargeBlob,
+        _ => DataType::Custom(identifier(name.split('(').next().unwrap_or(name).trim())),
+    }
+}
@@ -0,0 +1,127 @@
+use crate::ast::*;
+
+/// Crow's-foot line between two entities, e.g. `||--o{` for one-to-many
+fn crows_foot(relationship_type: RelationshipType) -> &'static str {
+    match relationship_type {
+        RelationshipType::OneToOne => "||--||",
+        RelationshipType::OneToMany => "||--o{",
+        RelationshipType::ManyToOne => "}o--||",
+        RelationshipType::ManyToMany => "}o--o{",
+    }
+}
+
+/// `name : type <<FK>>`, with the IE `*` marker on key fields
+fn field_line(name: &str, datatype: &DataType, primary_key: bool, foreign_key: bool) -> String {
+    let mut line = format!("  {}{} : {}", if primary_key { "* " } else { "" }, name, datatype);
+    if foreign_key {
+        line.push_str(" <<FK>>");
+    }
+    line.push('\n');
+    line
+}
+
+/// Entity block with key fields above the `--` separator
+fn push_entity(output: &mut String, header: &str, fields: &[(&str, &DataType, bool, bool)]) {
+    output.push_str(&format!("{} {{\n", header));
+    let (keys, rest): (Vec<_>, Vec<_>) = fields.iter().partition(|(_, _, pk, _)| *pk);
+    for (name, datatype, pk, fk) in &keys {
+        output.push_str(&field_line(name, datatype, *pk, *fk));
+    }
+    if !keys.is_empty() && !rest.is_empty() {
+        output.push_str("  --\n");
+    }
+    for (name, datatype, pk, fk) in &rest {
+        output.push_str(&field_line(name, datatype, *pk, *fk));
+    }
+    output.push_str("}\n\n");
+}
+
+/// Render a schema as a PlantUML IE diagram: `entity` blocks with `*` on
+/// primary keys and `<<FK>>` on foreign keys, joined by crow's-foot lines
+pub fn to_plantuml(schema: &Schema) -> String {
+    let mut output = String::from("@startuml\n");
+    if let Some(title) = &schema.title {
+        output.push_str(&format!("title {}\n", title));
+    }
+    output.push_str("hide circle\nskinparam linetype ortho\n\n");
+
+    for table in &schema.tables {
+        let fields: Vec<(&str, &DataType, bool, bool)> = table.columns.iter()
+            .map(|c| (c.name.as_str(), &c.datatype, c.is_primary_key(), c.is_foreign_key()))
+            .collect();
+        let header = match table.color() {
+            Some(color) => format!("entity {} {}", table.name, color),
+            None => format!("entity {}", table.name),
+        };
+        push_entity(&mut output, &header, &fields);
+    }
+
+    for node in &schema.nodes {
+        let fields: Vec<(&str, &DataType, bool, bool)> = node.fields.iter()
+            .map(|f| {
+                let pk = f.attributes.contains(&Attribute::PrimaryKey);
+                let fk = f.attributes.contains(&Attribute::ForeignKey);
+                (f.name.as_str(), &f.datatype, pk, fk)
+            })
+            .collect();
+        push_entity(&mut output, &format!("entity {} <<node>>", node.name), &fields);
+    }
+
+    for rel in &schema.relationships {
+        output.push_str(&format!(
+            "{} {} {} : {} → {}\n",
+            rel.from_table,
+            crows_foot(rel.relationship_type),
+            rel.to_table,
+            rel.from_field,
+            rel.to_field
+        ));
+    }
+
+    for edge in &schema.edges {
+        let arrow = match edge.edge_type {
+            EdgeType::Outgoing => "..>",
+            EdgeType::Incoming => "<..",
+            EdgeType::Bidirectional => "<..>",
+        };
+        output.push_str(&format!("{} {} {} : {}\n", edge.from_node, arrow, edge.to_node, edge.name));
+    }
+
+    output.push_str("@enduml\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_plantuml_export() {
+        let schema = Parser::new(r#"
+#title "Shop"
+table Users {
+  email: str,
+  id: int [pk]
+}
+table Orders {
+  id: int [pk],
+  user_id: int [fk]
+}
+Users.id > Orders.user_id
+node Person {
+  id: int [pk]
+}
+edge KNOWS (from: Person, to: Person) {}
+"#).parse().unwrap();
+
+        let uml = to_plantuml(&schema);
+        assert!(uml.starts_with("@startuml\ntitle Shop\n"));
+        assert!(uml.contains("entity Users {\n  * id : int\n  --\n  email : str\n}\n"));
+        assert!(uml.contains("  user_id : int <<FK>>\n"));
+        assert!(uml.contains("entity Person <<node>> {\n  * id : int\n}\n"));
+        assert!(uml.contains("Users ||--o{ Orders : id → user_id\n"));
+        assert!(uml.contains("Person <..> Person : KNOWS\n"));
+        assert!(uml.ends_with("@enduml\n"));
+    }
+}
@@ -0,0 +1,677 @@
+use super::{identifier, lower_first, unique_name, ImportError, Imported};
+use crate::ast::*;
+use crate::sql;
+use std::collections::{HashMap, HashSet};
+
+/// Prisma scalar type plus the native type attribute needed to keep it exact
+fn prisma_type(datatype: &DataType) -> (String, Option<&'static str>) {
+    let (name, native) = match datatype {
+        DataType::String => ("String", None),
+        DataType::Int => ("Int", None),
+        DataType::Bool => ("Boolean", None),
+        DataType::Double => ("Float", None),
+        DataType::Float => ("Float", Some("@db.Real")),
+        DataType::Decimal => ("Decimal", None),
+        DataType::Date => ("DateTime", Some("@db.Date")),
+        DataType::Time => ("DateTime", Some("@db.Time")),
+        DataType::DateTime => ("DateTime", None),
+        DataType::Blob => ("Bytes", None),
+        DataType::TinyBlob => ("Bytes", Some("@db.TinyBlob")),
+        DataType::LargeBlob => ("Bytes", Some("@db.LongBlob")),
+        DataType::Custom(name) if name == "Json" => ("Json", None),
+        DataType::Custom(name) => return (format!("Unsupported(\"{}\")", name), None),
+    };
+    (name.to_string(), native)
+}
+
+fn default_attribute(value: &DefaultValue) -> Option<String> {
+    let value = match value {
+        DefaultValue::Now => "now()".to_string(),
+        DefaultValue::True => "true".to_string(),
+        DefaultValue::False => "false".to_string(),
+        // Optional fields already default to null
+        DefaultValue::Null => return None,
+        DefaultValue::String(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+        DefaultValue::Number(n) => n.to_string(),
+    };
+    Some(format!("@default({})", value))
+}
+
+/// One line of a model: field name, type and attributes
+struct FieldLine {
+    name: String,
+    field_type: String,
+    attributes: Vec<String>,
+}
+
+/// Render a schema as a Prisma schema. Foreign keys become `@relation`
+/// fields with a back-relation on the referenced model; many-to-many
+/// relationships become implicit list relations.
+pub fn to_prisma(schema: &Schema) -> String {
+    let mut models: Vec<(String, Vec<FieldLine>, Vec<String>)> = Vec::new();
+    let mut used: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for table in &schema.tables {
+        let mut fields = Vec::new();
+        let mut block_attributes = Vec::new();
+        let primary_keys: Vec<&str> = table.columns.iter()
+            .filter(|c| c.is_primary_key())
+            .map(|c| c.name.as_str())
+            .collect();
+
+        for column in &table.columns {
+            let (mut field_type, native) = prisma_type(&column.datatype);
+            if sql::is_nullable(column) {
+                field_type.push('?');
+            }
+            let mut attributes = Vec::new();
+            for attribute in &column.attributes {
+                match attribute {
+                    Attribute::PrimaryKey if primary_keys.len() == 1 => attributes.push("@id".to_string()),
+                    Attribute::Unique => attributes.push("@unique".to_string()),
+                    Attribute::AutoIncrement => attributes.push("@default(autoincrement())".to_string()),
+                    Attribute::Default(value) => attributes.extend(default_attribute(value)),
+                    Attribute::Indexed => block_attributes.push(format!("@@index([{}])", column.name)),
+                    _ => {}
+                }
+            }
+            attributes.extend(native.map(str::to_string));
+            fields.push(FieldLine { name: column.name.clone(), field_type, attributes });
+        }
+        if primary_keys.len() > 1 {
+            block_attributes.insert(0, format!("@@id([{}])", primary_keys.join(", ")));
+        }
+
+        used.insert(table.name.clone(), table.columns.iter().map(|c| c.name.clone()).collect());
+        models.push((table.name.clone(), fields, block_attributes));
+    }
+
+    // Relations between the same pair of models need names to tell them apart
+    let mut pair_counts: HashMap<(String, String), usize> = HashMap::new();
+    for rel in &schema.relationships {
+        let mut pair = [rel.from_table.clone(), rel.to_table.clone()];
+        pair.sort();
+        *pair_counts.entry((pair[0].clone(), pair[1].clone())).or_default() += 1;
+    }
+    let relation_name = |a: &str, b: &str, name: String| -> Option<String> {
+        let mut pair = [a.to_string(), b.to_string()];
+        pair.sort();
+        let count = pair_counts.get(&(pair[0].clone(), pair[1].clone())).copied().unwrap_or(0);
+        (a == b || count > 1).then_some(name)
+    };
+
+    let mut relation_fields: Vec<(String, FieldLine)> = Vec::new();
+    for rel in &schema.relationships {
+        if rel.relationship_type == RelationshipType::ManyToMany {
+            let name = relation_name(&rel.from_table, &rel.to_table, format!("{}_{}", rel.from_table, rel.to_table));
+            let label = name.map(|n| vec![format!("@relation(\"{}\")", n)]).unwrap_or_default();
+            for (owner, other) in [(&rel.from_table, &rel.to_table), (&rel.to_table, &rel.from_table)] {
+                let Some(names) = used.get_mut(owner) else { continue };
+                let field = unique_name(names, lower_first(other));
+                relation_fields.push((owner.clone(), FieldLine { name: field, field_type: format!("{}[]", other), attributes: label.clone() }));
+            }
+            continue;
+        }
+
+        let Some(fk) = sql::foreign_key(schema, rel) else { continue };
+        let nullable = schema.tables.iter()
+            .find(|t| t.name == fk.table)
+            .and_then(|t| t.columns.iter().find(|c| c.name == fk.column))
+            .map(sql::is_nullable)
+            .unwrap_or(false);
+        let name = relation_name(&fk.table, &fk.ref_table, format!("{}_{}", fk.table, fk.column));
+        let named = name.as_ref().map(|n| format!("\"{}\", ", n)).unwrap_or_default();
+
+        let Some(names) = used.get_mut(&fk.table) else { continue };
+        let stem = fk.column.strip_suffix("_id").or_else(|| fk.column.strip_suffix("Id")).filter(|s| !s.is_empty());
+        let base = match stem {
+            Some(stem) if !names.contains(stem) => stem.to_string(),
+            _ => lower_first(&fk.ref_table),
+        };
+        let field = unique_name(names, base);
+        relation_fields.push((fk.table.clone(), FieldLine {
+            name: field,
+            field_type: format!("{}{}", fk.ref_table, if nullable { "?" } else { "" }),
+            attributes: vec![format!("@relation({}fields: [{}], references: [{}])", named, fk.column, fk.ref_column)],
+        }));
+
+        let Some(names) = used.get_mut(&fk.ref_table) else { continue };
+        let back = unique_name(names, lower_first(&fk.table));
+        let back_type = if rel.relationship_type == RelationshipType::OneToOne {
+            format!("{}?", fk.table)
+        } else {
+            format!("{}[]", fk.table)
+        };
+        let attributes = name.map(|n| vec![format!("@relation(\"{}\")", n)]).unwrap_or_default();
+        relation_fields.push((fk.ref_table.clone(), FieldLine { name: back, field_type: back_type, attributes }));
+    }
+    for (model, field) in relation_fields {
+        if let Some((_, fields, _)) = models.iter_mut().find(|(name, _, _)| *name == model) {
+            fields.push(field);
+        }
+    }
+
+    let mut output = String::new();
+    if let Some(title) = &schema.title {
+        output.push_str(&format!("// title: {}\n\n", title));
+    }
+    output.push_str("generator client {\n  provider = \"prisma-client-js\"\n}\n\n");
+    output.push_str("datasource db {\n  provider = \"postgresql\"\n  url      = env(\"DATABASE_URL\")\n}\n");
+
+    for (name, fields, block_attributes) in &models {
+        let name_width = fields.iter().map(|f| f.name.len()).max().unwrap_or(0);
+        let type_width = fields.iter().map(|f| f.field_type.len()).max().unwrap_or(0);
+        output.push_str(&format!("\nmodel {} {{\n", name));
+        for field in fields {
+            let line = format!(
+                "  {:name_width$} {:type_width$} {}",
+                field.name,
+                field.field_type,
+                field.attributes.join(" "),
+            );
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        if !block_attributes.is_empty() {
+            output.push('\n');
+            for attribute in block_attributes {
+                output.push_str(&format!("  {}\n", attribute));
+            }
+        }
+        output.push_str("}\n");
+    }
+
+    if !schema.nodes.is_empty() || !schema.edges.is_empty() {
+        output.push_str("\n// Graph nodes and edges have no Prisma equivalent and were left out\n");
+    }
+
+    output
+}
+
+/// `@name(args)` from a field or block line
+struct FieldAttribute {
+    name: String,
+    args: Option<String>,
+}
+
+/// A relation field, resolved once every model is known
+struct PendingRelation {
+    line: usize,
+    model: String,
+    target: String,
+    list: bool,
+    optional: bool,
+    name: Option<String>,
+    fields: Vec<String>,
+    references: Vec<String>,
+}
+
+/// Remove a `//` comment that isn't inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' if prev != '\\' => in_string = !in_string,
+            '/' if !in_string && prev == '/' => return &line[..i - 1],
+            _ => {}
+        }
+        prev = c;
+    }
+    line
+}
+
+/// Split on `sep` outside quotes and brackets
+fn split_top_level(text: &str, sep: impl Fn(char) -> bool) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    for c in text.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '(' | '[' if !in_string => depth += 1,
+            ')' | ']' if !in_string => depth -= 1,
+            _ if !in_string && depth == 0 && sep(c) => {
+                if !current.trim().is_empty() {
+                    parts.push(current.trim().to_string());
+                }
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+fn parse_attributes(text: &str) -> Vec<FieldAttribute> {
+    split_top_level(text, |c| c == '@')
+        .iter()
+        .map(|attr| match attr.find('(') {
+            Some(pos) => FieldAttribute {
+                name: attr[..pos].trim().to_string(),
+                args: Some(attr[pos + 1..attr.rfind(')').unwrap_or(attr.len())].to_string()),
+            },
+            None => FieldAttribute { name: attr.to_string(), args: None },
+        })
+        .collect()
+}
+
+/// `[a, b]` as a list of names
+fn name_list(text: &str) -> Vec<String> {
+    text.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// `"Name", fields: [a], references: [b]` from `@relation(...)`
+fn relation_args(args: &str) -> (Option<String>, Vec<String>, Vec<String>) {
+    let mut name = None;
+    let mut fields = Vec::new();
+    let mut references = Vec::new();
+    for arg in split_top_level(args, |c| c == ',') {
+        match arg.split_once(':') {
+            Some((key, value)) => match key.trim() {
+                "name" => name = Some(value.trim().trim_matches('"').to_string()),
+                "fields" => fields = name_list(value),
+                "references" => references = name_list(value),
+                _ => {}
+            },
+            None => name = Some(arg.trim_matches('"').to_string()),
+        }
+    }
+    (name, fields, references)
+}
+
+fn parse_default(args: &str) -> Result<Attribute, String> {
+    let args = args.trim();
+    Ok(match args {
+        "autoincrement()" => Attribute::AutoIncrement,
+        "now()" => Attribute::Default(DefaultValue::Now),
+        "true" => Attribute::Default(DefaultValue::True),
+        "false" => Attribute::Default(DefaultValue::False),
+        _ if args.starts_with('"') => Attribute::Default(DefaultValue::String(
+            args.trim_matches('"').replace("\\\"", "\"").replace("\\\\", "\\"),
+        )),
+        _ if args.ends_with(')') => return Err(format!("@default({}) is not kept", args)),
+        _ => match args.parse::<i64>() {
+            Ok(n) => Attribute::Default(DefaultValue::Number(n)),
+            // Enum values and decimals are kept as text
+            Err(_) => Attribute::Default(DefaultValue::String(args.to_string())),
+        },
+    })
+}
+
+fn scalar_type(name: &str, native: Option<&str>) -> Option<DataType> {
+    Some(match (name, native) {
+        ("String", _) => DataType::String,
+        ("Int" | "BigInt", _) => DataType::Int,
+        ("Boolean", _) => DataType::Bool,
+        ("Float", Some("db.Real")) => DataType::Float,
+        ("Float", _) => DataType::Double,
+        ("Decimal", _) => DataType::Decimal,
+        ("DateTime", Some("db.Date")) => DataType::Date,
+        ("DateTime", Some("db.Time" | "db.Timetz")) => DataType::Time,
+        ("DateTime", _) => DataType::DateTime,
+        ("Bytes", Some("db.TinyBlob")) => DataType::TinyBlob,
+        ("Bytes", Some("db.LongBlob" | "db.MediumBlob")) => DataType::LargeBlob,
+        ("Bytes", _) => DataType::Blob,
+        ("Json", _) => DataType::Custom("Json".to_string()),
+        _ => {
+            let inner = name.strip_prefix("Unsupported(")?.strip_suffix(')')?;
+            DataType::Custom(identifier(inner.trim_matches('"')))
+        }
+    })
+}
+
+/// Build a schema from a Prisma schema. Models become tables, scalar fields
+/// columns, and `@relation(fields, references)` relationships. A
+/// `// title: ...` comment above the first block becomes the title.
+pub fn from_prisma(source: &str) -> Result<Imported, ImportError> {
+    let mut schema = Schema::new();
+    schema.title = source.lines()
+        .map(str::trim)
+        .take_while(|l| l.is_empty() || l.starts_with("//"))
+        .find_map(|l| l.trim_start_matches('/').trim().strip_prefix("title:"))
+        .map(|title| title.trim().to_string());
+    let mut warnings = Vec::new();
+    let mut relations = Vec::new();
+    let mut enums = HashSet::new();
+    let mut composite_indexes: Vec<(usize, String, Vec<String>, Attribute)> = Vec::new();
+
+    // First pass: names of models and enums, so field types can be told apart
+    let mut models = HashSet::new();
+    for line in source.lines() {
+        let words: Vec<&str> = strip_comment(line).split_whitespace().collect();
+        match words[..] {
+            ["model", name, "{"] => {
+                models.insert(name.to_string());
+            }
+            ["enum", name, "{"] => {
+                enums.insert(name.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let mut block: Option<(String, String)> = None;
+    for (idx, raw) in source.lines().enumerate() {
+        let line = idx + 1;
+        let text = strip_comment(raw).trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let Some((kind, name)) = &block else {
+            let words: Vec<&str> = text.split_whitespace().collect();
+            match words[..] {
+                [kind @ ("model" | "enum" | "datasource" | "generator" | "type" | "view"), name, "{"] => {
+                    if kind == "model" {
+                        schema.tables.push(Table { name: identifier(name), columns: Vec::new(), attributes: Vec::new(), span: None });
+                    } else if matches!(kind, "type" | "view") {
+                        warnings.push(format!("line {}: {} '{}' is not kept", line, kind, name));
+                    }
+                    block = Some((kind.to_string(), name.to_string()));
+                    continue;
+                }
+                _ => return Err(ImportError::new(line, format!("Expected a model, enum, datasource or generator block but found '{}'", text))),
+            }
+        };
+
+        if text == "}" {
+            if kind == "enum" {
+                warnings.push(format!("Enum '{}' is kept as a custom type without its values", name));
+            }
+            block = None;
+            continue;
+        }
+        if kind != "model" {
+            continue;
+        }
+        let table_name = identifier(name);
+
+        if let Some(attribute) = text.strip_prefix("@@") {
+            let attribute = parse_attributes(&format!("@{}", attribute)).remove(0);
+            let columns = attribute.args.as_deref()
+                .map(|args| split_top_level(args, |c| c == ','))
+                .and_then(|args| args.into_iter().find(|a| a.starts_with('[') || a.starts_with("fields:")))
+                .map(|list| name_list(list.trim_start_matches("fields:")))
+                .unwrap_or_default();
+            let target = match attribute.name.as_str() {
+                "id" => Attribute::PrimaryKey,
+                "unique" if columns.len() == 1 => Attribute::Unique,
+                "unique" => {
+                    warnings.push(format!("Composite unique ({}) on '{}' is kept as separate indexes", columns.join(", "), name));
+                    Attribute::Indexed
+                }
+                "index" => Attribute::Indexed,
+                _ => continue,
+            };
+            composite_indexes.push((line, table_name, columns, target));
+            continue;
+        }
+
+        let parts = split_top_level(text, |c| c.is_whitespace());
+        let [field_name, field_type, rest @ ..] = &parts[..] else {
+            return Err(ImportError::new(line, format!("Expected '<name> <type>' but found '{}'", text)));
+        };
+        let attributes = parse_attributes(&rest.join(" "));
+        let optional = field_type.ends_with('?');
+        let list = field_type.ends_with("[]");
+        let base = field_type.trim_end_matches('?').trim_end_matches("[]");
+
+        if models.contains(base) {
+            let (relation_name, fields, references) = attributes.iter()
+                .find(|a| a.name == "relation")
+                .and_then(|a| a.args.as_deref())
+                .map(relation_args)
+                .unwrap_or_default();
+            relations.push(PendingRelation {
+                line,
+                model: name.clone(),
+                target: base.to_string(),
+                list,
+                optional,
+                name: relation_name,
+                fields,
+                references,
+            });
+            continue;
+        }
+
+        let native = attributes.iter().find(|a| a.name.starts_with("db.")).map(|a| a.name.as_str());
+        let datatype = if enums.contains(base) {
+            DataType::Custom(identifier(base))
+        } else {
+            scalar_type(base, native)
+                .ok_or_else(|| ImportError::new(line, format!("Unknown type '{}'", base)))?
+        };
+        if list {
+            warnings.push(format!("List field '{}.{}' is kept as a single value", name, field_name));
+        }
+
+        let mut column = Column { name: identifier(field_name), datatype, attributes: Vec::new(), span: None };
+        if optional {
+            column.attributes.push(Attribute::Nullable);
+        }
+        for attribute in &attributes {
+            match attribute.name.as_str() {
+                "id" => column.attributes.push(Attribute::PrimaryKey),
+                "unique" => column.attributes.push(Attribute::Unique),
+                "default" => match parse_default(attribute.args.as_deref().unwrap_or("")) {
+                    Ok(attr) => column.attributes.push(attr),
+                    Err(message) => warnings.push(format!("'{}.{}': {}", name, field_name, message)),
+                },
+                "updatedAt" => warnings.push(format!("'{}.{}': @updatedAt is not kept", name, field_name)),
+                _ => {}
+            }
+        }
+        if let Some(table) = schema.tables.iter_mut().find(|t| t.name == table_name) {
+            table.columns.push(column);
+        }
+    }
+
+    if let Some((kind, name)) = block {
+        return Err(ImportError::new(source.lines().count(), format!("{} '{}' is missing its closing '}}'", kind, name)));
+    }
+
+    for (line, table_name, columns, attribute) in composite_indexes {
+        let mut table = schema.tables.iter_mut().find(|t| t.name == table_name);
+        for name in &columns {
+            let column = table.as_deref_mut()
+                .and_then(|t| t.columns.iter_mut().find(|c| c.name == identifier(name)))
+                .ok_or_else(|| ImportError::new(line, format!("Unknown field '{}' on '{}'", name, table_name)))?;
+            if !column.attributes.contains(&attribute) {
+                column.attributes.push(attribute.clone());
+            }
+        }
+    }
+
+    resolve_relations(&mut schema, &relations)?;
+    Ok(Imported { schema, warnings })
+}
+
+/// Turn relation fields into relationships. The side with `fields:` holds the
+/// foreign key; its back-relation decides between one-to-one and one-to-many.
+fn resolve_relations(schema: &mut Schema, relations: &[PendingRelation]) -> Result<(), ImportError> {
+    let back_relation = |rel: &PendingRelation| relations.iter().find(|other| {
+        other.model == rel.target
+            && other.target == rel.model
+            && other.name == rel.name
+            && other.fields.is_empty()
+            && !std::ptr::eq(*other, rel)
+    });
+    let primary_key = |schema: &Schema, model: &str| schema.tables.iter()
+        .find(|t| t.name == identifier(model))
+        .and_then(|t| t.columns.iter().find(|c| c.is_primary_key()))
+        .map(|c| c.name.clone());
+
+    for (idx, rel) in relations.iter().enumerate() {
+        if !rel.fields.is_empty() {
+            if rel.fields.len() != rel.references.len() {
+                return Err(ImportError::new(rel.line, "@relation needs as many references as fields"));
+            }
+            let relationship_type = match back_relation(rel) {
+                Some(back) if !back.list => RelationshipType::OneToOne,
+                _ => RelationshipType::OneToMany,
+            };
+            let (model, target) = (identifier(&rel.model), identifier(&rel.target));
+            for (field, reference) in rel.fields.iter().zip(&rel.references) {
+                let (field, reference) = (identifier(field), identifier(reference));
+                let column = schema.tables.iter_mut()
+                    .find(|t| t.name == model)
+                    .and_then(|t| t.columns.iter_mut().find(|c| c.name == field))
+                    .ok_or_else(|| ImportError::new(rel.line, format!("Unknown field '{}' on '{}'", field, model)))?;
+                if !column.is_foreign_key() {
+                    column.attributes.push(Attribute::ForeignKey);
+                }
+                if rel.optional && !column.attributes.contains(&Attribute::Nullable) {
+                    column.attributes.push(Attribute::Nullable);
+                }
+                schema.relationships.push(Relationship {
+                    from_table: target.clone(),
+                    from_field: reference,
+                    to_table: model.clone(),
+                    to_field: field,
+                    relationship_type,
+                    span: None,
+                });
+            }
+        } else if rel.list {
+            // Implicit many-to-many: both sides are lists; add it once
+            let Some(back) = back_relation(rel) else { continue };
+            let back_idx = relations.iter().position(|r| std::ptr::eq(r, back)).unwrap_or(idx);
+            if !back.list || back_idx < idx {
+                continue;
+            }
+            let from_field = primary_key(schema, &rel.model)
+                .ok_or_else(|| ImportError::new(rel.line, format!("'{}' needs an @id for a many-to-many relation", rel.model)))?;
+            let to_field = primary_key(schema, &rel.target)
+                .ok_or_else(|| ImportError::new(rel.line, format!("'{}' needs an @id for a many-to-many relation", rel.target)))?;
+            schema.relationships.push(Relationship {
+                from_table: identifier(&rel.model),
+                from_field,
+                to_table: identifier(&rel.target),
+                to_field,
+                relationship_type: RelationshipType::ManyToMany,
+                span: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_export_and_import_round_trip() {
+        let schema = Parser::new(r#"
+#title "Blog"
+table Users {
+  id: int [pk, autoincrement],
+  email: str [unique],
+  name: str [nullable, default="anon"],
+  created_at: datetime [default=NOW]
+}
+table Posts {
+  id: int [pk],
+  user_id: int [fk],
+  score: double [default=0],
+  published_on: date,
+  body: str [indexed]
+}
+table Tags {
+  id: int [pk]
+}
+Users.id > Posts.user_id
+Posts.id <> Tags.id
+"#).parse().unwrap();
+
+        let prisma = to_prisma(&schema);
+        assert!(prisma.starts_with("// title: Blog\n\ngenerator client {"));
+        assert!(prisma.contains("model Users {\n  id         Int      @id @default(autoincrement())\n"));
+        assert!(prisma.contains("  name       String?  @default(\"anon\")\n"));
+        assert!(prisma.contains("  created_at DateTime @default(now())\n"));
+        assert!(prisma.contains("  posts      Posts[]\n"));
+        assert!(prisma.contains("  published_on DateTime @db.Date\n"));
+        assert!(prisma.contains("  user         Users    @relation(fields: [user_id], references: [id])\n"));
+        assert!(prisma.contains("  tags         Tags[]\n"));
+        assert!(prisma.contains("\n  @@index([body])\n}\n"));
+
+        let mut original = schema.clone();
+        for table in &mut original.tables {
+            table.span = None;
+            for column in &mut table.columns {
+                column.span = None;
+            }
+        }
+        for rel in &mut original.relationships {
+            rel.span = None;
+        }
+        let imported = from_prisma(&prisma).unwrap();
+        assert!(imported.warnings.is_empty());
+        assert_eq!(imported.schema, original);
+    }
+
+    #[test]
+    fn test_import_relations() {
+        let imported = from_prisma(r#"
+// This is your Prisma schema file
+enum Role {
+  USER
+  ADMIN
+}
+
+model User {
+  id      Int      @id @default(autoincrement())
+  role    Role     @default(USER)
+  token   String   @default(uuid()) // not kept
+  profile Profile?
+  posts   Post[]
+}
+
+model Profile {
+  id     Int   @id
+  user   User  @relation(fields: [userId], references: [id])
+  userId Int   @unique
+}
+
+model Post {
+  id       Int   @id
+  author   User? @relation(fields: [authorId], references: [id])
+  authorId Int?
+
+  @@unique([id, authorId])
+}
+"#).unwrap();
+
+        let schema = &imported.schema;
+        assert_eq!(schema.tables[0].columns[1].datatype, DataType::Custom("Role".to_string()));
+        assert_eq!(schema.tables[0].columns[1].attributes, vec![Attribute::Default(DefaultValue::String("USER".to_string()))]);
+        assert_eq!(schema.relationships.len(), 2);
+        assert_eq!(schema.relationships[0].relationship_type, RelationshipType::OneToOne);
+        assert_eq!((schema.relationships[0].from_table.as_str(), schema.relationships[0].to_field.as_str()), ("User", "userId"));
+        assert_eq!(schema.relationships[1].relationship_type, RelationshipType::OneToMany);
+        assert_eq!(schema.tables[2].columns[1].attributes, vec![Attribute::Nullable, Attribute::Indexed, Attribute::ForeignKey]);
+        assert_eq!(imported.warnings.len(), 3);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let err = from_prisma("model User {\n  id Strin @id\n}").unwrap_err();
+        assert_eq!(err, ImportError::new(2, "Unknown type 'Strin'"));
+        assert_eq!(from_prisma("model User {\n  id Int @id\n").unwrap_err().line, 2);
+    }
+}
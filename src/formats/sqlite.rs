@@ -0,0 +1,279 @@
+use super::{datatype_from_sql, identifier, Imported};
+use crate::ast::*;
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+
+/// Column type from a declared SQLite type. Names the shared mapping
+/// doesn't know fall back to SQLite's type affinity rules, so e.g.
+/// `UNSIGNED BIG INT` is an int and `JSON` stays a custom type.
+fn column_type(declared: &str) -> DataType {
+    let declared = declared.trim();
+    if declared.is_empty() {
+        return DataType::Blob;
+    }
+    let datatype = datatype_from_sql(declared);
+    if !matches!(datatype, DataType::Custom(_)) {
+        return datatype;
+    }
+    let upper = declared.to_uppercase();
+    if upper.contains("INT") {
+        DataType::Int
+    } else if upper.contains("CHAR") || upper.contains("CLOB") || upper.contains("TEXT") {
+        DataType::String
+    } else if upper.contains("BLOB") {
+        DataType::Blob
+    } else if upper.contains("DOUB") {
+        DataType::Double
+    } else if upper.contains("REAL") || upper.contains("FLOA") {
+        DataType::Float
+    } else {
+        datatype
+    }
+}
+
+/// Default from the SQL expression stored in `PRAGMA table_info`
+fn default_value(expression: &str, datatype: &DataType) -> DefaultValue {
+    let mut expression = expression.trim();
+    while expression.len() >= 2 && expression.starts_with('(') && expression.ends_with(')') {
+        expression = expression[1..expression.len() - 1].trim();
+    }
+    if expression.len() >= 2 && expression.starts_with('\'') && expression.ends_with('\'') {
+        return DefaultValue::String(expression[1..expression.len() - 1].replace("''", "'"));
+    }
+    match expression.to_lowercase().as_str() {
+        "null" => DefaultValue::Null,
+        "true" => DefaultValue::True,
+        "false" => DefaultValue::False,
+        "1" if *datatype == DataType::Bool => DefaultValue::True,
+        "0" if *datatype == DataType::Bool => DefaultValue::False,
+        "current_timestamp" | "current_date" | "current_time" => DefaultValue::Now,
+        lower if lower.starts_with("datetime('now'") || lower.starts_with("date('now'") || lower.starts_with("time('now'") => DefaultValue::Now,
+        _ => expression.parse::<i64>()
+            .map(DefaultValue::Number)
+            .unwrap_or_else(|_| DefaultValue::String(expression.to_string())),
+    }
+}
+
+fn push_attribute(column: &mut Column, attribute: Attribute) {
+    if !column.attributes.contains(&attribute) {
+        column.attributes.push(attribute);
+    }
+}
+
+/// One `PRAGMA foreign_key_list` row, before both ends are known to exist
+struct PendingKey {
+    table: String,
+    column: String,
+    ref_table: String,
+    ref_column: Option<String>,
+}
+
+/// Build a schema from the tables of an open SQLite database. Foreign keys
+/// on a unique column become one-to-one relationships, others one-to-many.
+pub fn from_connection(connection: &Connection) -> rusqlite::Result<Imported> {
+    let mut schema = Schema::new();
+    let mut warnings = Vec::new();
+    let mut pending = Vec::new();
+
+    let mut tables = connection.prepare(
+        "SELECT name, COALESCE(sql, '') FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY rowid",
+    )?;
+    let tables: Vec<(String, String)> = tables
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    for (name, sql) in &tables {
+        if sql.to_uppercase().starts_with("CREATE VIRTUAL TABLE") {
+            warnings.push(format!("Virtual table '{}' is not imported", name));
+            continue;
+        }
+        let autoincrement = sql.to_uppercase().contains("AUTOINCREMENT");
+        let mut table = Table { name: identifier(name), columns: Vec::new(), attributes: Vec::new(), span: None };
+
+        // cid, name, type, notnull, dflt_value, pk
+        let mut info = connection.prepare("SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1)")?;
+        let rows: Vec<(String, String, bool, Option<String>, i64)> = info
+            .query_map([name], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        let key_count = rows.iter().filter(|row| row.4 > 0).count();
+
+        for (column_name, declared, not_null, default, pk) in rows {
+            let datatype = column_type(&declared);
+            let mut column = Column { name: identifier(&column_name), datatype, attributes: Vec::new(), span: None };
+            if pk > 0 {
+                column.attributes.push(Attribute::PrimaryKey);
+                // Only an INTEGER PRIMARY KEY aliases the rowid and can autoincrement
+                if autoincrement && key_count == 1 && declared.eq_ignore_ascii_case("integer") {
+                    column.attributes.push(Attribute::AutoIncrement);
+                }
+            } else if !not_null {
+                column.attributes.push(Attribute::Nullable);
+            }
+            if let Some(default) = default {
+                let value = default_value(&default, &column.datatype);
+                column.attributes.push(Attribute::Default(value));
+            }
+            table.columns.push(column);
+        }
+
+        // seq, name, unique, origin (c = CREATE INDEX, u = UNIQUE, pk), partial
+        let mut indexes = connection.prepare("SELECT name, \"unique\", origin FROM pragma_index_list(?1)")?;
+        let indexes: Vec<(String, bool, String)> = indexes
+            .query_map([name], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (index, unique, origin) in indexes {
+            if origin == "pk" {
+                continue;
+            }
+            let mut columns = connection.prepare("SELECT name FROM pragma_index_info(?1) ORDER BY seqno")?;
+            let columns: Vec<Option<String>> = columns
+                .query_map([&index], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            if columns.iter().any(Option::is_none) {
+                warnings.push(format!("Expression index '{}' on '{}' is not kept", index, name));
+                continue;
+            }
+            let attribute = if unique && columns.len() == 1 {
+                Attribute::Unique
+            } else {
+                if unique {
+                    warnings.push(format!(
+                        "Composite unique index ({}) on '{}' is kept as separate indexes",
+                        columns.iter().flatten().cloned().collect::<Vec<_>>().join(", "),
+                        name
+                    ));
+                }
+                Attribute::Indexed
+            };
+            for column_name in columns.iter().flatten() {
+                if let Some(column) = table.columns.iter_mut().find(|c| c.name == identifier(column_name)) {
+                    push_attribute(column, attribute.clone());
+                }
+            }
+        }
+
+        // id, seq, table, from, to, on_update, on_delete, match
+        let mut keys = connection.prepare("SELECT \"table\", \"from\", \"to\" FROM pragma_foreign_key_list(?1) ORDER BY id, seq")?;
+        let keys = keys.query_map([name], |row| Ok(PendingKey {
+            table: table.name.clone(),
+            column: identifier(&row.get::<_, String>(1)?),
+            ref_table: identifier(&row.get::<_, String>(0)?),
+            ref_column: row.get::<_, Option<String>>(2)?.map(|c| identifier(&c)),
+        }))?;
+        for key in keys {
+            pending.push(key?);
+        }
+
+        schema.tables.push(table);
+    }
+
+    for key in pending {
+        let Some(parent) = schema.tables.iter().find(|t| t.name == key.ref_table) else {
+            warnings.push(format!("Foreign key '{}.{}' references unknown table '{}'", key.table, key.column, key.ref_table));
+            continue;
+        };
+        // A missing target column means the parent's primary key
+        let ref_column = key.ref_column.clone().or_else(|| {
+            let keys: Vec<&Column> = parent.columns.iter().filter(|c| c.is_primary_key()).collect();
+            match keys[..] {
+                [only] => Some(only.name.clone()),
+                _ => None,
+            }
+        });
+        let Some(ref_column) = ref_column.filter(|c| parent.columns.iter().any(|p| p.name == *c)) else {
+            warnings.push(format!("Foreign key '{}.{}' has no matching column in '{}'", key.table, key.column, key.ref_table));
+            continue;
+        };
+
+        let Some(column) = schema.tables.iter_mut()
+            .filter(|t| t.name == key.table)
+            .flat_map(|t| t.columns.iter_mut())
+            .find(|c| c.name == key.column)
+        else {
+            continue;
+        };
+        push_attribute(column, Attribute::ForeignKey);
+        let one_to_one = column.attributes.contains(&Attribute::Unique)
+            || (column.is_primary_key() && schema.tables.iter()
+                .find(|t| t.name == key.table)
+                .is_some_and(|t| t.columns.iter().filter(|c| c.is_primary_key()).count() == 1));
+
+        schema.relationships.push(Relationship {
+            from_table: key.ref_table,
+            from_field: ref_column,
+            to_table: key.table,
+            to_field: key.column,
+            relationship_type: if one_to_one { RelationshipType::OneToOne } else { RelationshipType::OneToMany },
+            span: None,
+        });
+    }
+
+    Ok(Imported { schema, warnings })
+}
+
+/// Introspect a SQLite database file, opened read-only
+pub fn from_sqlite(path: &Path) -> rusqlite::Result<Imported> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)?;
+    from_connection(&connection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_introspect_database() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch(r#"
+CREATE TABLE users (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  email VARCHAR(255) NOT NULL UNIQUE,
+  bio TEXT,
+  active BOOLEAN NOT NULL DEFAULT 1,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+CREATE TABLE profiles (
+  id INTEGER PRIMARY KEY,
+  user_id INTEGER NOT NULL UNIQUE REFERENCES users(id),
+  nickname TEXT DEFAULT 'it''s me'
+);
+CREATE TABLE posts (
+  id INTEGER PRIMARY KEY,
+  user_id INTEGER NOT NULL REFERENCES users,
+  score UNSIGNED BIG INT DEFAULT (-1)
+);
+CREATE INDEX posts_score ON posts(score);
+"#).unwrap();
+
+        let imported = from_connection(&connection).unwrap();
+        assert!(imported.warnings.is_empty());
+        let schema = imported.schema;
+        let names: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["users", "profiles", "posts"]);
+
+        let users = &schema.tables[0];
+        assert_eq!(users.columns[0].attributes, vec![Attribute::PrimaryKey, Attribute::AutoIncrement]);
+        assert_eq!(users.columns[1].datatype, DataType::String);
+        assert_eq!(users.columns[1].attributes, vec![Attribute::Unique]);
+        assert_eq!(users.columns[2].attributes, vec![Attribute::Nullable]);
+        assert_eq!(users.columns[3].attributes, vec![Attribute::Default(DefaultValue::True)]);
+        assert_eq!(users.columns[4].attributes, vec![Attribute::Nullable, Attribute::Default(DefaultValue::Now)]);
+
+        let profiles = &schema.tables[1];
+        assert_eq!(profiles.columns[0].attributes, vec![Attribute::PrimaryKey]);
+        assert_eq!(profiles.columns[1].attributes, vec![Attribute::Unique, Attribute::ForeignKey]);
+        assert_eq!(profiles.columns[2].attributes, vec![Attribute::Nullable, Attribute::Default(DefaultValue::String("it's me".to_string()))]);
+
+        let posts = &schema.tables[2];
+        assert_eq!(posts.columns[2].datatype, DataType::Int);
+        assert_eq!(posts.columns[2].attributes, vec![Attribute::Nullable, Attribute::Default(DefaultValue::Number(-1)), Attribute::Indexed]);
+
+        let relationships: Vec<(&str, &str, &str, &str, RelationshipType)> = schema.relationships.iter()
+            .map(|r| (r.from_table.as_str(), r.from_field.as_str(), r.to_table.as_str(), r.to_field.as_str(), r.relationship_type))
+            .collect();
+        assert_eq!(relationships, vec![
+            ("users", "id", "profiles", "user_id", RelationshipType::OneToOne),
+            ("users", "id", "posts", "user_id", RelationshipType::OneToMany),
+        ]);
+    }
+}
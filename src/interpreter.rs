@@ -84,7 +84,7 @@ impl ValidationError {
                 output.push_str("   \x1b[1;34m|\x1b[0m ");
                 output.push_str(&" ".repeat(span.column - 1));
                 output.push_str(&format!("\x1b[1;31m{}\x1b[0m", "^".repeat(span.length.max(1))));
-                output.push_str("\n");
+                output.push('\n');
             }
         }
         
@@ -377,6 +377,7 @@ mod tests {
                 attributes: attrs,
                 span: None,
             }).collect(),
+            attributes: vec![],
             span: None,
         }
     }
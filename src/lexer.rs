@@ -37,6 +37,7 @@ pub enum Token {
     Identifier(String),
     String(String),
     Number(i64),
+    HexColor(String),   // #3b82f6
     
     // Relationship Operators
     OneToMany,      // >
@@ -77,6 +78,7 @@ impl fmt::Display for Token {
             Token::Identifier(s) => write!(f, "IDENTIFIER({})", s),
             Token::String(s) => write!(f, "STRING(\"{}\")", s),
             Token::Number(n) => write!(f, "NUMBER({})", n),
+            Token::HexColor(c) => write!(f, "COLOR({})", c),
             Token::OneToMany => write!(f, ">"),
             Token::ManyToOne => write!(f, "<"),
             Token::ManyToMany => write!(f, "<>"),
@@ -112,7 +114,7 @@ pub struct Lexer {
 impl Lexer {
     pub fn new(input: &str) -> Self {
         let chars: Vec<char> = input.chars().collect();
-        let current_char = chars.get(0).copied();
+        let current_char = chars.first().copied();
         Lexer {
             input: chars,
             position: 0,
@@ -204,6 +206,22 @@ impl Lexer {
         result.parse().unwrap_or(0)
     }
     
+    fn read_hex_color(&mut self) -> String {
+        let mut result = String::from("#");
+        self.advance(); // Skip #
+        
+        while let Some(ch) = self.current_char {
+            if ch.is_ascii_hexdigit() {
+                result.push(ch);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        
+        result
+    }
+    
     fn read_comment(&mut self) -> String {
         let mut result = String::new();
         self.advance(); // Skip first /
@@ -307,6 +325,15 @@ impl Lexer {
                     tokens.push(Spanned::new(Token::Title, span));
                     for _ in 0..6 { self.advance(); }
                 }
+                '#' if self.peek(1).map(|c| c.is_ascii_hexdigit()).unwrap_or(false) => {
+                    // Hex colour literal, e.g. #3b82f6
+                    let start_line = self.line;
+                    let start_col = self.column;
+                    let color = self.read_hex_color();
+                    let length = self.column - start_col;
+                    let span = Span::new(start_line, start_col, length);
+                    tokens.push(Spanned::new(Token::HexColor(color), span));
+                }
                 '#' => {
                     // Unknown directive, skip it
                     self.advance();
@@ -462,4 +489,18 @@ mod tests {
         assert_eq!(tokens[3].value, Token::Identifier("String".to_string()));
         assert_eq!(tokens[4].value, Token::RightParen);
     }
+    
+    #[test]
+    fn test_hex_color_tokenization() {
+        let input = r##"table Users [color=#3b82f6] {"##;
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize();
+        
+        assert_eq!(tokens[0].value, Token::Table);
+        assert_eq!(tokens[2].value, Token::LeftBracket);
+        assert_eq!(tokens[3].value, Token::Identifier("color".to_string()));
+        assert_eq!(tokens[4].value, Token::Equals);
+        assert_eq!(tokens[5].value, Token::HexColor("#3b82f6".to_string()));
+        assert_eq!(tokens[6].value, Token::RightBracket);
+    }
 }
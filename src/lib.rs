@@ -0,0 +1,159 @@
+//! FreeERD as a library: parse `.frd` schemas, check them, lay them out and
+//! render diagrams without running the command-line tool.
+//!
+//! ```no_run
+//! let source = std::fs::read_to_string("schema.frd").unwrap();
+//! let schema = free_erd::parse(&source).unwrap();
+//! free_erd::validate(&schema).unwrap();
+//! let layout = free_erd::layout(&schema).unwrap();
+//! let svg = free_erd::render_svg(&schema, &layout, &free_erd::Theme::light()).unwrap();
+//! ```
+//!
+//! The interactive window is behind the `viewer` feature, which is on by default.
+
+mod ast;
+mod lexer;
+mod parser;
+mod interpreter;
+mod renderer;
+mod writer;
+#[cfg(feature = "viewer")]
+mod editor;
+mod diff;
+mod sql;
+mod migrate;
+mod formats;
+mod codegen;
+mod docs;
+mod seed;
+mod security;
+mod pdf;
+mod cli;
+
+pub use ast::{
+    Attribute, Column, DataType, DefaultValue, Edge, EdgeProperty, EdgeType, Node, NodeField,
+    Relationship, RelationshipType, Schema, Table, TableAttribute,
+};
+pub use interpreter::ValidationError;
+pub use lexer::{Span, Token};
+pub use parser::ParseError;
+pub use security::SecurityError;
+pub use renderer::{Layout, RelationshipLayout, TableLayout, Theme};
+
+/// Entry point of the `free-erd` binary; not part of the library API
+#[doc(hidden)]
+pub use cli::main as run_cli;
+
+use interpreter::Interpreter;
+use parser::Parser;
+
+/// A problem found while parsing or validating a schema
+#[derive(Debug)]
+pub enum Diagnostic {
+    Parse(ParseError),
+    Validation(ValidationError),
+}
+
+impl Diagnostic {
+    /// Where in the source the problem is, when known
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Diagnostic::Parse(error) => Some(error.span()),
+            Diagnostic::Validation(error) => error.span(),
+        }
+    }
+
+    /// Rust-style message with the offending source line underlined
+    pub fn format_with_source(&self, source: &str, filename: &str) -> String {
+        match self {
+            Diagnostic::Parse(error) => error.format_with_source(source, filename),
+            Diagnostic::Validation(error) => error.format_with_source(source, filename),
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Diagnostic::Parse(error) => write!(f, "{}", error),
+            Diagnostic::Validation(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Parse `.frd` source. Parsing stops at the first error.
+pub fn parse(source: &str) -> Result<Schema, Vec<Diagnostic>> {
+    Parser::new(source).parse().map_err(|error| vec![Diagnostic::Parse(error)])
+}
+
+/// Check that names are unique and relationships point at existing tables
+/// and columns, reporting every problem found
+pub fn validate(schema: &Schema) -> Result<(), Vec<Diagnostic>> {
+    Interpreter::new(schema.clone())
+        .validate()
+        .map_err(|errors| errors.into_iter().map(Diagnostic::Validation).collect())
+}
+
+/// Check a schema from an untrusted source against size limits, identifier
+/// rules and relationship cycles. Stricter than `validate`, which accepts
+/// tables that reference each other in a cycle.
+pub fn check_limits(schema: &Schema) -> Result<(), SecurityError> {
+    security::SecurityValidator::validate(schema)
+}
+
+/// Place the tables and route the relationships of a valid schema
+pub fn layout(schema: &Schema) -> Result<Layout, Box<dyn std::error::Error>> {
+    let erd_graph = renderer::ErdGraph::from_schema(schema)?;
+    Ok(renderer::compute_layout(&erd_graph))
+}
+
+/// Render a schema to SVG with the tables where `layout` put them. Tables
+/// missing from the layout are added in a column to the right.
+pub fn render_svg(schema: &Schema, layout: &Layout, theme: &Theme) -> Result<String, Box<dyn std::error::Error>> {
+    let erd_graph = renderer::ErdGraph::from_schema(schema)?;
+    let title = schema.title.clone().unwrap_or_default();
+    renderer::export_svg_at(&erd_graph, layout, &title, theme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_api() {
+        let schema = parse("table Users {\n  id: int [pk]\n}\ntable Orders {\n  id: int [pk],\n  user_id: int [fk]\n}\nUsers.id > Orders.user_id\n").unwrap();
+        validate(&schema).unwrap();
+
+        let orders: &crate::Table = &schema.tables[1];
+        let user_id: &crate::Column = &orders.columns[1];
+        assert_eq!(user_id.name, "user_id");
+        assert_eq!(user_id.datatype, crate::DataType::Int);
+        assert!(user_id.attributes.contains(&crate::Attribute::ForeignKey));
+        let rel: &crate::Relationship = &schema.relationships[0];
+        assert_eq!(rel.relationship_type, crate::RelationshipType::OneToMany);
+
+        check_limits(&schema).unwrap();
+
+        let layout = layout(&schema).unwrap();
+        assert_eq!(layout.tables.len(), 2);
+        assert_eq!(layout.relationships.len(), 1);
+        let svg = render_svg(&schema, &layout, &Theme::light()).unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains(">Orders</text>"));
+
+        let errors = parse("table Users {\n  id int\n}\n").unwrap_err();
+        assert_eq!(errors[0].span().map(|s| s.line), Some(2));
+        assert!(matches!(errors[0], Diagnostic::Parse(crate::ParseError::UnexpectedToken { .. })));
+
+        let bad = parse("table Users {\n  id: int\n}\nUsers.id > Orders.user_id\n").unwrap();
+        let errors = validate(&bad).unwrap_err();
+        assert_eq!(errors[0].to_string(), "Table 'Orders' not found");
+
+        let cyclic = parse("table A {\n  id: int [pk],\n  b_id: int\n}\ntable B {\n  id: int [pk],\n  a_id: int\n}\nA.id > B.a_id\nB.id > A.b_id\n").unwrap();
+        validate(&cyclic).unwrap();
+        assert!(matches!(check_limits(&cyclic), Err(SecurityError::CyclicRelationship(_))));
+        assert!(matches!(&errors[0], Diagnostic::Validation(crate::ValidationError::TableNotFound { name, .. }) if name == "Orders"));
+    }
+}
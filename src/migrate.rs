@@ -0,0 +1,495 @@
+use crate::ast::*;
+use crate::diff::{Change, SchemaDiff};
+use crate::sql::{self, Dialect, ForeignKey};
+use std::collections::HashSet;
+
+/// An index created for a `[unique]` or `[indexed]` column
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ColumnIndex {
+    table: String,
+    column: String,
+    unique: bool,
+}
+
+impl ColumnIndex {
+    fn name(&self) -> String {
+        let prefix = if self.unique { "uq" } else { "idx" };
+        format!("{}_{}_{}", prefix, self.table, self.column)
+    }
+}
+
+fn column_indexes(schema: &Schema) -> Vec<ColumnIndex> {
+    let mut indexes = Vec::new();
+    for table in &schema.tables {
+        for column in &table.columns {
+            for (attribute, unique) in [(Attribute::Unique, true), (Attribute::Indexed, false)] {
+                if column.attributes.contains(&attribute) {
+                    indexes.push(ColumnIndex { table: table.name.clone(), column: column.name.clone(), unique });
+                }
+            }
+        }
+    }
+    indexes
+}
+
+fn primary_key(table: &Table) -> Vec<&str> {
+    table.columns.iter().filter(|c| c.is_primary_key()).map(|c| c.name.as_str()).collect()
+}
+
+/// Type changes that can't lose data; anything else is reported as narrowing
+fn is_widening(from: &DataType, to: &DataType) -> bool {
+    matches!(
+        (from, to),
+        (_, DataType::String)
+            | (DataType::Bool, DataType::Int)
+            | (DataType::Int, DataType::Double | DataType::Decimal)
+            | (DataType::Float, DataType::Double)
+            | (DataType::Date, DataType::DateTime)
+            | (DataType::TinyBlob, DataType::Blob | DataType::LargeBlob)
+            | (DataType::Blob, DataType::LargeBlob)
+    )
+}
+
+/// Ordered SQL statements that turn one schema into another, plus warnings
+/// for changes that can lose data or fail on a populated database
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    pub statements: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Statement builder; keeps the dialect next to the output
+struct Builder {
+    dialect: Dialect,
+    statements: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Builder {
+    fn q(&self, ident: &str) -> String {
+        self.dialect.quote(ident)
+    }
+
+    fn push(&mut self, statement: String) {
+        self.statements.push(statement);
+    }
+
+    fn add_foreign_key(&mut self, fk: &ForeignKey) {
+        let statement = format!(
+            "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({});",
+            self.q(&fk.table),
+            self.q(&fk.constraint_name()),
+            self.q(&fk.column),
+            self.q(&fk.ref_table),
+            self.q(&fk.ref_column)
+        );
+        self.push(statement);
+    }
+
+    fn drop_foreign_key(&mut self, fk: &ForeignKey) {
+        let keyword = match self.dialect {
+            Dialect::Postgres => "CONSTRAINT",
+            Dialect::MySql => "FOREIGN KEY",
+        };
+        let statement = format!("ALTER TABLE {} DROP {} {};", self.q(&fk.table), keyword, self.q(&fk.constraint_name()));
+        self.push(statement);
+    }
+
+    fn create_index(&mut self, index: &ColumnIndex) {
+        let statement = format!(
+            "CREATE {}INDEX {} ON {} ({});",
+            if index.unique { "UNIQUE " } else { "" },
+            self.q(&index.name()),
+            self.q(&index.table),
+            self.q(&index.column)
+        );
+        self.push(statement);
+    }
+
+    fn drop_index(&mut self, index: &ColumnIndex) {
+        let statement = match self.dialect {
+            Dialect::Postgres => format!("DROP INDEX {};", self.q(&index.name())),
+            Dialect::MySql => format!("DROP INDEX {} ON {};", self.q(&index.name()), self.q(&index.table)),
+        };
+        self.push(statement);
+    }
+
+    fn create_table(&mut self, table: &Table, inline_fks: &[&ForeignKey]) {
+        let mut lines: Vec<String> = table.columns.iter().map(|c| self.dialect.column_definition(c)).collect();
+        let pk = primary_key(table);
+        if !pk.is_empty() {
+            let cols: Vec<String> = pk.iter().map(|c| self.q(c)).collect();
+            lines.push(format!("PRIMARY KEY ({})", cols.join(", ")));
+        }
+        for fk in inline_fks {
+            lines.push(format!(
+                "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+                self.q(&fk.constraint_name()),
+                self.q(&fk.column),
+                self.q(&fk.ref_table),
+                self.q(&fk.ref_column)
+            ));
+        }
+        let statement = format!("CREATE TABLE {} (\n    {}\n);", self.q(&table.name), lines.join(",\n    "));
+        self.push(statement);
+    }
+
+    fn drop_primary_key(&mut self, table: &str) {
+        let statement = match self.dialect {
+            // The constraint may not have the default <table>_pkey name, so look it up
+            Dialect::Postgres => {
+                let literal = |s: &str| format!("'{}'", s.replace('\'', "''"));
+                format!(
+                    "DO $$\nDECLARE pkey name;\nBEGIN\n    SELECT conname INTO pkey FROM pg_constraint WHERE conrelid = {}::regclass AND contype = 'p';\n    EXECUTE format('ALTER TABLE %I DROP CONSTRAINT %I', {}, pkey);\nEND $$;",
+                    literal(&self.q(table)),
+                    literal(table)
+                )
+            }
+            Dialect::MySql => format!("ALTER TABLE {} DROP PRIMARY KEY;", self.q(table)),
+        };
+        self.push(statement);
+    }
+
+    fn add_primary_key(&mut self, table: &str, columns: &[&str]) {
+        let cols: Vec<String> = columns.iter().map(|c| self.q(c)).collect();
+        let statement = format!("ALTER TABLE {} ADD PRIMARY KEY ({});", self.q(table), cols.join(", "));
+        self.push(statement);
+    }
+
+    fn alter_column(&mut self, table: &str, old: &Column, new: &Column) {
+        let name = format!("{}.{}", table, new.name);
+        let old_type = self.dialect.column_type(&old.datatype, false);
+        let new_type = self.dialect.column_type(&new.datatype, false);
+        let type_changed = old_type != new_type;
+        let null_changed = sql::is_nullable(old) != sql::is_nullable(new);
+        let default_changed = sql::default_value(old) != sql::default_value(new);
+
+        if type_changed && !is_widening(&old.datatype, &new.datatype) {
+            self.warnings.push(format!("Type of {} narrows from {} to {}; existing values may be truncated or fail to convert", name, old.datatype, new.datatype));
+        }
+        if null_changed && !sql::is_nullable(new) {
+            self.warnings.push(format!("{} becomes NOT NULL; this fails if any row holds NULL", name));
+        }
+        if old.attributes.contains(&Attribute::AutoIncrement) != new.attributes.contains(&Attribute::AutoIncrement) {
+            self.warnings.push(format!("Auto-increment change on {} is not migrated", name));
+        }
+
+        let table_q = self.q(table);
+        let column_q = self.q(&new.name);
+        match self.dialect {
+            Dialect::MySql => {
+                if type_changed || null_changed || default_changed {
+                    let definition = self.dialect.column_definition(new);
+                    self.push(format!("ALTER TABLE {} MODIFY COLUMN {};", table_q, definition));
+                }
+            }
+            Dialect::Postgres => {
+                if type_changed {
+                    self.push(format!("ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}::{};", table_q, column_q, new_type, column_q, new_type));
+                }
+                if null_changed {
+                    let action = if sql::is_nullable(new) { "DROP NOT NULL" } else { "SET NOT NULL" };
+                    self.push(format!("ALTER TABLE {} ALTER COLUMN {} {};", table_q, column_q, action));
+                }
+                if default_changed {
+                    let action = match sql::default_value(new) {
+                        Some(value) => format!("SET DEFAULT {}", sql::sql_literal(value)),
+                        None => "DROP DEFAULT".to_string(),
+                    };
+                    self.push(format!("ALTER TABLE {} ALTER COLUMN {} {};", table_q, column_q, action));
+                }
+            }
+        }
+    }
+}
+
+impl Migration {
+    /// Statements are ordered so every step only depends on earlier ones:
+    /// constraints and indexes are dropped before the columns and tables they
+    /// use, referenced tables are created before the tables pointing at them,
+    /// and new foreign keys and indexes come last
+    pub fn between(old: &Schema, new: &Schema, dialect: Dialect) -> Self {
+        let diff = SchemaDiff::between(old, new);
+        let mut b = Builder { dialect, statements: Vec::new(), warnings: Vec::new() };
+
+        let removed_tables: HashSet<&str> = diff.changes.iter().filter_map(|c| match c {
+            Change::TableRemoved(t) => Some(t.name.as_str()),
+            _ => None,
+        }).collect();
+        let added_tables: HashSet<&str> = diff.changes.iter().filter_map(|c| match c {
+            Change::TableAdded(t) => Some(t.name.as_str()),
+            _ => None,
+        }).collect();
+        let removed_columns: HashSet<(&str, &str)> = diff.changes.iter().filter_map(|c| match c {
+            Change::ColumnRemoved { table, column } => Some((table.as_str(), column.name.as_str())),
+            _ => None,
+        }).collect();
+
+        let old_fks = sql::foreign_keys(old);
+        let new_fks = sql::foreign_keys(new);
+        let old_indexes = column_indexes(old);
+        let new_indexes = column_indexes(new);
+
+        // Primary keys that change on surviving tables
+        let pk_changes: Vec<(&str, Vec<&str>, Vec<&str>)> = new.tables.iter()
+            .filter_map(|table| {
+                let before = old.tables.iter().find(|t| t.name == table.name)?;
+                let (old_pk, new_pk) = (primary_key(before), primary_key(table));
+                (old_pk != new_pk).then_some((table.name.as_str(), old_pk, new_pk))
+            })
+            .collect();
+        // Unique and primary keys on surviving tables that go away or are new.
+        // Foreign keys using them have to be dropped before or added after them.
+        let surviving = |table: &str| !removed_tables.contains(table) && !added_tables.contains(table);
+        let unique_keys = |from: &[ColumnIndex], to: &[ColumnIndex]| -> HashSet<(String, String)> {
+            from.iter()
+                .filter(|i| i.unique && !to.contains(i) && surviving(&i.table))
+                .map(|i| (i.table.clone(), i.column.clone()))
+                .collect()
+        };
+        let mut dropped_keys = unique_keys(&old_indexes, &new_indexes);
+        let mut created_keys = unique_keys(&new_indexes, &old_indexes);
+        for (table, old_pk, new_pk) in &pk_changes {
+            dropped_keys.extend(old_pk.iter().map(|c| (table.to_string(), c.to_string())));
+            created_keys.extend(new_pk.iter().map(|c| (table.to_string(), c.to_string())));
+        }
+        let references = |keys: &HashSet<(String, String)>, fk: &ForeignKey| keys.contains(&(fk.ref_table.clone(), fk.ref_column.clone()));
+
+        // 1. Foreign keys and indexes that go away. Dropping a table takes its
+        // foreign keys with it, unless they use a key that is dropped first.
+        for fk in old_fks.iter().filter(|fk| !new_fks.contains(fk)) {
+            if !removed_tables.contains(fk.table.as_str()) || references(&dropped_keys, fk) {
+                b.drop_foreign_key(fk);
+            }
+        }
+        for index in old_indexes.iter().filter(|i| !new_indexes.contains(i)) {
+            let gone = removed_tables.contains(index.table.as_str())
+                || removed_columns.contains(&(index.table.as_str(), index.column.as_str()));
+            if !gone {
+                b.drop_index(index);
+            }
+        }
+
+        // 2. Primary keys that change on surviving tables
+        for (table, old_pk, _) in &pk_changes {
+            if !old_pk.is_empty() {
+                b.drop_primary_key(table);
+            }
+        }
+
+        // 3. Dropped columns, then dropped tables (referencing tables first)
+        for change in &diff.changes {
+            if let Change::ColumnRemoved { table, column } = change {
+                b.warnings.push(format!("Column {}.{} is dropped; its data will be lost", table, column.name));
+                let statement = format!("ALTER TABLE {} DROP COLUMN {};", b.q(table), b.q(&column.name));
+                b.push(statement);
+            }
+        }
+        for name in sql::dependency_order(old).iter().rev().filter(|name| removed_tables.contains(name.as_str())) {
+            b.warnings.push(format!("Table {} is dropped; its data will be lost", name));
+            let statement = format!("DROP TABLE {};", b.q(name));
+            b.push(statement);
+        }
+
+        // 4. New tables, referenced tables first; foreign keys to tables that
+        // don't exist yet (reference cycles) or to keys that are only added
+        // in step 6 are added afterwards
+        let mut available: HashSet<&str> = old.tables.iter()
+            .map(|t| t.name.as_str())
+            .filter(|name| !removed_tables.contains(name))
+            .collect();
+        let mut deferred_fks = Vec::new();
+        for name in sql::dependency_order(new).iter().filter(|name| added_tables.contains(name.as_str())) {
+            let Some(table) = new.tables.iter().find(|t| t.name == *name) else { continue };
+            available.insert(table.name.as_str());
+            let (inline, deferred): (Vec<&ForeignKey>, Vec<&ForeignKey>) = new_fks.iter()
+                .filter(|fk| fk.table == table.name)
+                .partition(|fk| available.contains(fk.ref_table.as_str()) && !references(&created_keys, fk));
+            b.create_table(table, &inline);
+            deferred_fks.extend(deferred);
+        }
+
+        // 5. New and changed columns on existing tables
+        for change in &diff.changes {
+            match change {
+                Change::ColumnAdded { table, column } => {
+                    if !sql::is_nullable(column) && sql::default_value(column).is_none() && !column.attributes.contains(&Attribute::AutoIncrement) {
+                        b.warnings.push(format!("Column {}.{} is NOT NULL without a default; adding it fails if the table has rows", table, column.name));
+                    }
+                    let statement = format!("ALTER TABLE {} ADD COLUMN {};", b.q(table), dialect.column_definition(column));
+                    b.push(statement);
+                }
+                Change::ColumnChanged { table, old, new } => b.alter_column(table, old, new),
+                _ => {}
+            }
+        }
+        for (table, _, new_pk) in &pk_changes {
+            if !new_pk.is_empty() {
+                b.add_primary_key(table, new_pk);
+            }
+        }
+
+        // 6. Indexes that are new, then the foreign keys that may rely on them
+        for index in new_indexes.iter().filter(|i| !old_indexes.contains(i)) {
+            b.create_index(index);
+        }
+        for fk in new_fks.iter().filter(|fk| !old_fks.contains(fk) && !added_tables.contains(fk.table.as_str())) {
+            b.add_foreign_key(fk);
+        }
+        for fk in deferred_fks {
+            b.add_foreign_key(fk);
+        }
+
+        let graph_changes = diff.changes.iter().any(|c| matches!(
+            c,
+            Change::NodeAdded(_) | Change::NodeRemoved(_) | Change::NodeFieldAdded { .. } | Change::NodeFieldRemoved { .. }
+                | Change::NodeFieldChanged { .. } | Change::EdgeAdded(_) | Change::EdgeRemoved(_) | Change::EdgeChanged { .. }
+        ));
+        if graph_changes {
+            b.warnings.push("Graph node and edge changes have no SQL equivalent and are not migrated".to_string());
+        }
+        for change in &diff.changes {
+            if let Change::RelationshipAdded(rel) | Change::RelationshipChanged { new: rel, .. } = change {
+                if rel.relationship_type == RelationshipType::ManyToMany {
+                    b.warnings.push(format!(
+                        "Many-to-many {}.{} <> {}.{} needs a junction table; no foreign key is generated",
+                        rel.from_table, rel.from_field, rel.to_table, rel.to_field
+                    ));
+                }
+            }
+        }
+
+        Migration { statements: b.statements, warnings: b.warnings }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// The migration as a SQL script, warnings first as comments
+    pub fn to_sql(&self) -> String {
+        let mut output = String::from("-- Generated by FreeERD\n");
+        for warning in &self.warnings {
+            output.push_str(&format!("-- WARNING: {}\n", warning));
+        }
+        for statement in &self.statements {
+            output.push('\n');
+            output.push_str(statement);
+            output.push('\n');
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn parse(source: &str) -> Schema {
+        Parser::new(source).parse().unwrap()
+    }
+
+    const OLD: &str = r#"
+table Users {
+  id: int [pk, autoincrement],
+  email: str,
+  nickname: str [nullable],
+  score: double
+}
+table Logs {
+  id: int [pk]
+}
+"#;
+
+    const NEW: &str = r#"
+table Users {
+  id: int [pk, autoincrement],
+  email: str [unique],
+  score: int
+}
+table Comments {
+  id: int [pk],
+  post_id: int [fk]
+}
+table Posts {
+  id: int [pk, autoincrement],
+  user_id: int [fk],
+  title: str [indexed]
+}
+Users.id > Posts.user_id
+Posts.id > Comments.post_id
+"#;
+
+    #[test]
+    fn test_postgres_migration_order() {
+        let migration = Migration::between(&parse(OLD), &parse(NEW), Dialect::Postgres);
+        assert_eq!(migration.statements, vec![
+            "ALTER TABLE \"Users\" DROP COLUMN \"nickname\";".to_string(),
+            "DROP TABLE \"Logs\";".to_string(),
+            "CREATE TABLE \"Posts\" (\n    \"id\" SERIAL NOT NULL,\n    \"user_id\" INTEGER NOT NULL,\n    \"title\" TEXT NOT NULL,\n    PRIMARY KEY (\"id\"),\n    CONSTRAINT \"fk_Posts_user_id\" FOREIGN KEY (\"user_id\") REFERENCES \"Users\" (\"id\")\n);".to_string(),
+            "CREATE TABLE \"Comments\" (\n    \"id\" INTEGER NOT NULL,\n    \"post_id\" INTEGER NOT NULL,\n    PRIMARY KEY (\"id\"),\n    CONSTRAINT \"fk_Comments_post_id\" FOREIGN KEY (\"post_id\") REFERENCES \"Posts\" (\"id\")\n);".to_string(),
+            "ALTER TABLE \"Users\" ALTER COLUMN \"score\" TYPE INTEGER USING \"score\"::INTEGER;".to_string(),
+            "CREATE UNIQUE INDEX \"uq_Users_email\" ON \"Users\" (\"email\");".to_string(),
+            "CREATE INDEX \"idx_Posts_title\" ON \"Posts\" (\"title\");".to_string(),
+        ]);
+        assert_eq!(migration.warnings.len(), 3);
+        assert!(migration.warnings[2].contains("Users.score narrows"));
+    }
+
+    #[test]
+    fn test_down_migration_reverses() {
+        let migration = Migration::between(&parse(NEW), &parse(OLD), Dialect::MySql);
+        // Constraints on dropped tables go with them; children are dropped before their parents
+        assert_eq!(migration.statements, vec![
+            "DROP INDEX `uq_Users_email` ON `Users`;".to_string(),
+            "DROP TABLE `Comments`;".to_string(),
+            "DROP TABLE `Posts`;".to_string(),
+            "CREATE TABLE `Logs` (\n    `id` INT NOT NULL,\n    PRIMARY KEY (`id`)\n);".to_string(),
+            "ALTER TABLE `Users` ADD COLUMN `nickname` VARCHAR(255);".to_string(),
+            "ALTER TABLE `Users` MODIFY COLUMN `score` DOUBLE NOT NULL;".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_primary_key_change() {
+        let old = parse("table Users {\n  id: int [pk],\n  email: str\n}\n");
+        let new = parse("table Users {\n  id: int,\n  email: str [pk]\n}\n");
+        let migration = Migration::between(&old, &new, Dialect::Postgres);
+        assert_eq!(migration.statements, vec![
+            "DO $$\nDECLARE pkey name;\nBEGIN\n    SELECT conname INTO pkey FROM pg_constraint WHERE conrelid = '\"Users\"'::regclass AND contype = 'p';\n    EXECUTE format('ALTER TABLE %I DROP CONSTRAINT %I', 'Users', pkey);\nEND $$;".to_string(),
+            "ALTER TABLE \"Users\" ADD PRIMARY KEY (\"email\");".to_string(),
+        ]);
+        let migration = Migration::between(&old, &new, Dialect::MySql);
+        assert_eq!(migration.statements[0], "ALTER TABLE `Users` DROP PRIMARY KEY;");
+    }
+
+    #[test]
+    fn test_new_foreign_key_to_new_unique_key() {
+        let old = parse("table Users {\n  id: int [pk],\n  email: str\n}\n");
+        let new = parse("table Users {\n  id: int [pk],\n  email: str [unique]\n}\ntable Invites {\n  id: int [pk],\n  email: str [fk]\n}\nUsers.email > Invites.email\n");
+
+        // The foreign key waits for the unique index it points at
+        let up = Migration::between(&old, &new, Dialect::Postgres);
+        assert_eq!(up.statements, vec![
+            "CREATE TABLE \"Invites\" (\n    \"id\" INTEGER NOT NULL,\n    \"email\" TEXT NOT NULL,\n    PRIMARY KEY (\"id\")\n);".to_string(),
+            "CREATE UNIQUE INDEX \"uq_Users_email\" ON \"Users\" (\"email\");".to_string(),
+            "ALTER TABLE \"Invites\" ADD CONSTRAINT \"fk_Invites_email\" FOREIGN KEY (\"email\") REFERENCES \"Users\" (\"email\");".to_string(),
+        ]);
+
+        // ...and goes before the index on the way down
+        let down = Migration::between(&new, &old, Dialect::Postgres);
+        assert_eq!(down.statements, vec![
+            "ALTER TABLE \"Invites\" DROP CONSTRAINT \"fk_Invites_email\";".to_string(),
+            "DROP INDEX \"uq_Users_email\";".to_string(),
+            "DROP TABLE \"Invites\";".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_identical_schemas_need_no_migration() {
+        let migration = Migration::between(&parse(NEW), &parse(NEW), Dialect::Postgres);
+        assert!(migration.is_empty());
+        assert!(migration.warnings.is_empty());
+    }
+}
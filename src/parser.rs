@@ -52,7 +52,7 @@ impl ParseError {
             output.push_str("   \x1b[1;34m|\x1b[0m ");
             output.push_str(&" ".repeat(span.column - 1));
             output.push_str(&format!("\x1b[1;31m{}\x1b[0m", "^".repeat(span.length.max(1))));
-            output.push_str("\n");
+            output.push('\n');
         }
         
         output
@@ -98,11 +98,8 @@ impl Parser {
     }
     
     fn skip_comments_and_newlines(&mut self) {
-        loop {
-            match self.current_token() {
-                Token::Newline | Token::Comment(_) => self.advance(),
-                _ => break,
-            }
+        while matches!(self.current_token(), Token::Newline | Token::Comment(_)) {
+            self.advance();
         }
     }
     
@@ -245,12 +242,19 @@ impl Parser {
             });
         };
         
+        let mut table = Table::with_span(table_name, table_name_span);
+        
         self.skip_newlines();
+        
+        // Parse optional table-level attributes
+        if matches!(self.current_token(), Token::LeftBracket) {
+            table.attributes = self.parse_table_attributes()?;
+            self.skip_newlines();
+        }
+        
         self.expect_token(Token::LeftBrace)?;
         self.skip_comments_and_newlines();
         
-        let mut table = Table::with_span(table_name, table_name_span);
-        
         while !matches!(self.current_token(), Token::RightBrace | Token::Eof) {
             let column = self.parse_column()?;
             table.columns.push(column);
@@ -268,6 +272,89 @@ impl Parser {
         Ok(table)
     }
     
+    fn parse_table_attributes(&mut self) -> Result<Vec<TableAttribute>, ParseError> {
+        self.expect_token(Token::LeftBracket)?;
+        self.skip_newlines();
+        
+        let mut attributes = Vec::new();
+        
+        while !matches!(self.current_token(), Token::RightBracket | Token::Eof) {
+            let attribute = self.parse_table_attribute()?;
+            attributes.push(attribute);
+            
+            self.skip_newlines();
+            
+            if matches!(self.current_token(), Token::Comma) {
+                self.advance();
+                self.skip_newlines();
+            }
+        }
+        
+        self.expect_token(Token::RightBracket)?;
+        
+        Ok(attributes)
+    }
+    
+    fn parse_table_attribute(&mut self) -> Result<TableAttribute, ParseError> {
+        let attr_span = self.current_span();
+        let attr_name = if let Token::Identifier(name) = self.current_token() {
+            let name = name.to_lowercase();
+            self.advance();
+            name
+        } else {
+            return Err(ParseError::UnexpectedToken {
+                expected: "table attribute".to_string(),
+                found: self.current_token().clone(),
+                span: self.current_span(),
+            });
+        };
+        
+        self.expect_token(Token::Equals)?;
+        
+        match attr_name.as_str() {
+            "color" | "colour" => {
+                let value_span = self.current_span();
+                let color = match self.current_token() {
+                    Token::HexColor(c) | Token::String(c) => c.clone(),
+                    _ => {
+                        return Err(ParseError::UnexpectedToken {
+                            expected: "hex colour (e.g. #3b82f6)".to_string(),
+                            found: self.current_token().clone(),
+                            span: self.current_span(),
+                        });
+                    }
+                };
+                
+                if !Self::is_hex_color(&color) {
+                    return Err(ParseError::InvalidAttribute {
+                        name: format!("Colour '{}' must be #rgb or #rrggbb", color),
+                        span: value_span,
+                    });
+                }
+                
+                self.advance();
+                Ok(TableAttribute::Color(color.to_lowercase()))
+            }
+            "icon" => {
+                if let Token::String(icon) = self.current_token() {
+                    let icon = icon.clone();
+                    self.advance();
+                    Ok(TableAttribute::Icon(icon))
+                } else {
+                    Err(ParseError::UnexpectedToken {
+                        expected: "icon name string".to_string(),
+                        found: self.current_token().clone(),
+                        span: self.current_span(),
+                    })
+                }
+            }
+            _ => Err(ParseError::InvalidAttribute {
+                name: attr_name,
+                span: attr_span,
+            }),
+        }
+    }
+    
     fn parse_node(&mut self) -> Result<Node, ParseError> {
         self.expect_token(Token::Node)?;
         self.skip_newlines();
@@ -381,6 +468,13 @@ impl Parser {
         s.chars().all(|c| c.is_lowercase() || c.is_numeric() || c == '_')
     }
     
+    fn is_hex_color(s: &str) -> bool {
+        match s.strip_prefix('#') {
+            Some(hex) => (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            None => false,
+        }
+    }
+    
     fn is_upper_snake_case(s: &str) -> bool {
         if s.is_empty() {
             return false;
@@ -824,7 +918,7 @@ impl Parser {
         // Pattern 2: <-[NAME]-  (Incoming)
         // Pattern 3: <-[NAME]-> (Bidirectional)
         
-        let _edge_type = if matches!(self.current_token(), Token::OneToOne) {
+        if matches!(self.current_token(), Token::OneToOne) {
             // Could be -[NAME]->
             self.advance();
             self.skip_newlines();
@@ -879,7 +973,7 @@ impl Parser {
                 });
             };
             
-            return Ok(Edge::new(edge_name, from_node, to_node, EdgeType::Outgoing));
+            Ok(Edge::new(edge_name, from_node, to_node, EdgeType::Outgoing))
             
         } else if matches!(self.current_token(), Token::ManyToOne) {
             // Could be <-[NAME]- or <-[NAME]->
@@ -938,15 +1032,15 @@ impl Parser {
                 });
             };
             
-            return Ok(Edge::new(edge_name, from_node, to_node, edge_type));
+            Ok(Edge::new(edge_name, from_node, to_node, edge_type))
             
         } else {
-            return Err(ParseError::UnexpectedToken {
+            Err(ParseError::UnexpectedToken {
                 expected: "edge operator (-, <-)".to_string(),
                 found: self.current_token().clone(),
                 span: self.current_span(),
-            });
-        };
+            })
+        }
     }
 }
 
@@ -979,6 +1073,33 @@ mod tests {
         assert_eq!(schema.tables[0].columns.len(), 2);
     }
     
+    #[test]
+    fn test_parse_table_style_attributes() {
+        let input = r##"
+        table Users [color=#3B82F6, icon="user"] {
+            id: int [pk]
+        }
+        table AuditLog [color="#999"] { id: int }
+        "##;
+        let mut parser = Parser::new(input);
+        let schema = parser.parse().unwrap();
+        
+        assert_eq!(schema.tables[0].color(), Some("#3b82f6"));
+        assert_eq!(schema.tables[0].icon(), Some("user"));
+        assert_eq!(schema.tables[0].columns.len(), 1);
+        assert_eq!(schema.tables[1].color(), Some("#999"));
+        assert!(schema.tables[1].icon().is_none());
+    }
+    
+    #[test]
+    fn test_invalid_table_attributes() {
+        let input = r##"table Users [color=#12345] { id: int }"##;
+        assert!(Parser::new(input).parse().is_err());
+        
+        let input = r#"table Users [shape="round"] { id: int }"#;
+        assert!(Parser::new(input).parse().is_err());
+    }
+    
     #[test]
     fn test_parse_relationship() {
         let input = r#"
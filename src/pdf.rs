@@ -0,0 +1,349 @@
+// Minimal PDF writer: vector paths and text in one embedded TrueType font
+use ab_glyph::{Font as _, FontRef};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+/// A TrueType font embedded whole, with metrics read through `ab_glyph`
+pub struct Font<'a> {
+    name: String,
+    data: &'a [u8],
+    face: FontRef<'a>,
+}
+
+impl<'a> Font<'a> {
+    pub fn new(name: &str, data: &'a [u8]) -> Result<Self, String> {
+        let face = FontRef::try_from_slice(data).map_err(|e| format!("Invalid font '{}': {}", name, e))?;
+        let name: String = name.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect();
+        Ok(Font { name, data, face })
+    }
+
+    fn units_per_em(&self) -> f32 {
+        self.face.units_per_em().unwrap_or(1000.0)
+    }
+
+    /// Glyph id for a character, or `None` when the font lacks it
+    fn glyph(&self, c: char) -> Option<u16> {
+        let id = self.face.glyph_id(c).0;
+        (id != 0).then_some(id)
+    }
+
+    /// Advance width of a glyph in thousandths of the font size
+    fn advance(&self, glyph: u16) -> f32 {
+        self.face.h_advance_unscaled(ab_glyph::GlyphId(glyph)) * 1000.0 / self.units_per_em()
+    }
+
+    /// Width of `text` set at `size`, skipping characters the font lacks
+    pub fn text_width(&self, text: &str, size: f32) -> f32 {
+        text.chars().filter_map(|c| self.glyph(c)).map(|g| self.advance(g)).sum::<f32>() * size / 1000.0
+    }
+}
+
+/// Horizontal placement of text relative to its x coordinate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// How a closed shape is painted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Paint {
+    Fill,
+    FillStroke,
+}
+
+/// Content stream of one page. Coordinates start at the top left corner
+/// with y growing downwards, like the canvas and SVG output.
+pub struct Page {
+    width: f32,
+    height: f32,
+    content: String,
+    glyphs: BTreeMap<u16, char>,
+}
+
+/// Number formatting that keeps content streams short
+fn num(value: f32) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+impl Page {
+    pub fn new(width: f32, height: f32) -> Self {
+        let mut content = String::new();
+        // Flip the y axis so drawing code can use screen coordinates
+        let _ = writeln!(content, "1 0 0 -1 0 {} cm", num(height));
+        Page { width, height, content, glyphs: BTreeMap::new() }
+    }
+
+    pub fn save(&mut self) {
+        self.content.push_str("q\n");
+    }
+
+    pub fn restore(&mut self) {
+        self.content.push_str("Q\n");
+    }
+
+    /// Scale and then translate everything drawn afterwards
+    pub fn transform(&mut self, scale: f32, dx: f32, dy: f32) {
+        let _ = writeln!(self.content, "{} 0 0 {} {} {} cm", num(scale), num(scale), num(dx), num(dy));
+    }
+
+    /// Restrict drawing to a rectangle until the next `restore`
+    pub fn clip(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let _ = writeln!(self.content, "{} {} {} {} re W n", num(x), num(y), num(width), num(height));
+    }
+
+    pub fn stroke_color(&mut self, (r, g, b): (u8, u8, u8)) {
+        let _ = writeln!(self.content, "{} {} {} RG", num(r as f32 / 255.0), num(g as f32 / 255.0), num(b as f32 / 255.0));
+    }
+
+    pub fn fill_color(&mut self, (r, g, b): (u8, u8, u8)) {
+        let _ = writeln!(self.content, "{} {} {} rg", num(r as f32 / 255.0), num(g as f32 / 255.0), num(b as f32 / 255.0));
+    }
+
+    pub fn line_width(&mut self, width: f32) {
+        let _ = writeln!(self.content, "{} w", num(width));
+    }
+
+    /// Dash pattern for strokes; an empty pattern draws solid lines
+    pub fn dash(&mut self, pattern: &[f32]) {
+        let pattern: Vec<String> = pattern.iter().map(|v| num(*v)).collect();
+        let _ = writeln!(self.content, "[{}] 0 d", pattern.join(" "));
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.polyline(&[(x1, y1), (x2, y2)]);
+    }
+
+    pub fn polyline(&mut self, points: &[(f32, f32)]) {
+        for (i, (x, y)) in points.iter().enumerate() {
+            let op = if i == 0 { "m" } else { "l" };
+            let _ = writeln!(self.content, "{} {} {}", num(*x), num(*y), op);
+        }
+        if !points.is_empty() {
+            self.content.push_str("S\n");
+        }
+    }
+
+    /// Rectangle with rounded corners
+    pub fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, radius: f32, paint: Paint) {
+        let r = radius.min(width / 2.0).min(height / 2.0).max(0.0);
+        if r == 0.0 {
+            let _ = writeln!(self.content, "{} {} {} {} re", num(x), num(y), num(width), num(height));
+        } else {
+            // Bezier control point distance for a quarter circle
+            let k = r * 0.552_284_8;
+            let (right, bottom) = (x + width, y + height);
+            let c = &mut self.content;
+            let _ = writeln!(c, "{} {} m", num(x + r), num(y));
+            let _ = writeln!(c, "{} {} l", num(right - r), num(y));
+            let _ = writeln!(c, "{} {} {} {} {} {} c", num(right - r + k), num(y), num(right), num(y + r - k), num(right), num(y + r));
+            let _ = writeln!(c, "{} {} l", num(right), num(bottom - r));
+            let _ = writeln!(c, "{} {} {} {} {} {} c", num(right), num(bottom - r + k), num(right - r + k), num(bottom), num(right - r), num(bottom));
+            let _ = writeln!(c, "{} {} l", num(x + r), num(bottom));
+            let _ = writeln!(c, "{} {} {} {} {} {} c", num(x + r - k), num(bottom), num(x), num(bottom - r + k), num(x), num(bottom - r));
+            let _ = writeln!(c, "{} {} l", num(x), num(y + r));
+            let _ = writeln!(c, "{} {} {} {} {} {} c h", num(x), num(y + r - k), num(x + r - k), num(y), num(x + r), num(y));
+        }
+        self.content.push_str(match paint {
+            Paint::Fill => "f\n",
+            Paint::FillStroke => "B\n",
+        });
+    }
+
+    /// Text in the fill colour with its baseline at `y`. Characters the
+    /// font has no glyph for are left out.
+    pub fn text(&mut self, font: &Font, x: f32, y: f32, size: f32, align: Align, text: &str) {
+        let glyphs: Vec<(u16, char)> = text.chars().filter_map(|c| font.glyph(c).map(|g| (g, c))).collect();
+        if glyphs.is_empty() {
+            return;
+        }
+        let width = font.text_width(text, size);
+        let x = match align {
+            Align::Left => x,
+            Align::Center => x - width / 2.0,
+            Align::Right => x - width,
+        };
+        let mut hex = String::new();
+        for (glyph, c) in glyphs {
+            let _ = write!(hex, "{:04X}", glyph);
+            self.glyphs.entry(glyph).or_insert(c);
+        }
+        // The text matrix flips glyphs back upright inside the flipped page
+        let _ = writeln!(self.content, "BT /F1 {} Tf 1 0 0 -1 {} {} Tm <{}> Tj ET", num(size), num(x), num(y), hex);
+    }
+}
+
+/// Stream object, compressed
+fn stream(dictionary: &str, data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    let compressed = encoder.write_all(data).and_then(|_| encoder.finish()).unwrap_or_default();
+    let mut object = format!("<< {} /Filter /FlateDecode /Length {} >>\nstream\n", dictionary, compressed.len()).into_bytes();
+    object.extend_from_slice(&compressed);
+    object.extend_from_slice(b"\nendstream");
+    object
+}
+
+/// PDF text string: plain when printable ASCII, else UTF-16 with a byte order mark
+fn text_string(text: &str) -> String {
+    if text.chars().all(|c| (' '..='~').contains(&c)) {
+        format!("({})", text.replace('\\', "\\\\").replace('(', "\\(").replace(')', "\\)"))
+    } else {
+        let mut hex = String::from("<FEFF");
+        for unit in text.encode_utf16() {
+            let _ = write!(hex, "{:04X}", unit);
+        }
+        hex.push('>');
+        hex
+    }
+}
+
+/// `ToUnicode` CMap so text in the PDF can be searched and copied
+fn to_unicode(glyphs: &BTreeMap<u16, char>) -> String {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+/CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    let entries: Vec<(&u16, &char)> = glyphs.iter().collect();
+    for chunk in entries.chunks(100) {
+        let _ = writeln!(cmap, "{} beginbfchar", chunk.len());
+        for (glyph, c) in chunk {
+            let mut unicode = String::new();
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                let _ = write!(unicode, "{:04X}", unit);
+            }
+            let _ = writeln!(cmap, "<{:04X}> <{}>", glyph, unicode);
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+    cmap
+}
+
+/// Write pages into a PDF file that embeds `font` as a CID font
+pub fn write(font: &Font, title: &str, pages: &[Page]) -> Vec<u8> {
+    let mut glyphs = BTreeMap::new();
+    for page in pages {
+        for (glyph, c) in &page.glyphs {
+            glyphs.entry(*glyph).or_insert(*c);
+        }
+    }
+
+    // Fixed objects first, then a page object and content stream per page
+    const CATALOG: usize = 1;
+    const PAGES: usize = 2;
+    const FONT: usize = 3;
+    const CID_FONT: usize = 4;
+    const DESCRIPTOR: usize = 5;
+    const FONT_FILE: usize = 6;
+    const TO_UNICODE: usize = 7;
+    const INFO: usize = 8;
+    let first_page = 9;
+
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+    let kids: Vec<String> = (0..pages.len()).map(|i| format!("{} 0 R", first_page + i * 2)).collect();
+    objects.push(format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES).into_bytes());
+    objects.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), pages.len()).into_bytes());
+    objects.push(format!(
+        "<< /Type /Font /Subtype /Type0 /BaseFont /{} /Encoding /Identity-H /DescendantFonts [{} 0 R] /ToUnicode {} 0 R >>",
+        font.name, CID_FONT, TO_UNICODE
+    ).into_bytes());
+
+    let widths: Vec<String> = glyphs.keys().map(|g| format!("{} [{}]", g, num(font.advance(*g)))).collect();
+    objects.push(format!(
+        "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{} /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> \
+/FontDescriptor {} 0 R /CIDToGIDMap /Identity /DW 500 /W [{}] >>",
+        font.name, DESCRIPTOR, widths.join(" ")
+    ).into_bytes());
+
+    let scale = 1000.0 / font.units_per_em();
+    let ascent = font.face.ascent_unscaled() * scale;
+    let descent = font.face.descent_unscaled() * scale;
+    objects.push(format!(
+        "<< /Type /FontDescriptor /FontName /{} /Flags 32 /FontBBox [-500 {} 1500 {}] /ItalicAngle 0 \
+/Ascent {} /Descent {} /CapHeight {} /StemV 80 /FontFile2 {} 0 R >>",
+        font.name, num(descent), num(ascent), num(ascent), num(descent), num(ascent * 0.7), FONT_FILE
+    ).into_bytes());
+    objects.push(stream(&format!("/Length1 {}", font.data.len()), font.data));
+    objects.push(stream("", to_unicode(&glyphs).as_bytes()));
+    objects.push(format!("<< /Title {} /Producer (FreeERD) >>", text_string(title)).into_bytes());
+
+    for (i, page) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 {} 0 R >> >> /Contents {} 0 R >>",
+            PAGES, num(page.width), num(page.height), FONT, first_page + i * 2 + 1
+        ).into_bytes());
+        objects.push(stream("", page.content.as_bytes()));
+    }
+
+    let mut output = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(output.len());
+        output.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        output.extend_from_slice(object);
+        output.extend_from_slice(b"\nendobj\n");
+    }
+    let xref = output.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(table, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        table,
+        "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1, CATALOG, INFO, xref
+    );
+    output.extend_from_slice(table.as_bytes());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_font() -> Vec<u8> {
+        epaint_default_fonts::UBUNTU_LIGHT.to_vec()
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let data = test_font();
+        let font = Font::new("Ubuntu-Light", &data).unwrap();
+        assert!(font.text_width("Users", 10.0) > 0.0);
+        assert_eq!(font.text_width("🔑", 10.0), 0.0);
+
+        let mut page = Page::new(595.0, 842.0);
+        page.fill_color((0, 0, 0));
+        page.rect(10.0, 10.0, 100.0, 40.0, 8.0, Paint::FillStroke);
+        page.text(&font, 20.0, 30.0, 12.0, Align::Left, "🔑 id");
+        assert!(page.content.contains(" Tj ET\n"));
+        assert_eq!(page.glyphs.values().collect::<String>(), " di");
+
+        let pdf = write(&font, "Shop", &[page, Page::new(595.0, 842.0)]);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("/Title (Shop)"));
+
+        // Every xref entry points at the start of its object
+        let start: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        let xref = &pdf[start..];
+        let entries: Vec<usize> = String::from_utf8_lossy(xref).lines().skip(3)
+            .take_while(|line| !line.starts_with("trailer"))
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        assert_eq!(entries.len(), 12);
+        for (i, offset) in entries.iter().enumerate() {
+            assert!(pdf[*offset..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()));
+        }
+    }
+}
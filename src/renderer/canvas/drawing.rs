@@ -1,18 +1,24 @@
 use egui::{Color32, Stroke, Pos2, Rect, FontId, Align2};
-use crate::renderer::graph::ErdGraph;
+use crate::renderer::graph::{DiffStatus, ErdGraph, SearchMatch};
 use crate::renderer::layout::LayoutEngine;
+use crate::renderer::theme::Theme;
 use petgraph::graph::NodeIndex;
+use std::collections::HashSet;
 
 pub struct DrawingContext<'a> {
     pub erd_graph: &'a ErdGraph,
     pub layout_engine: &'a LayoutEngine,
     pub zoom: f32,
     pub pan_offset: Pos2,
-    pub selected_table: Option<NodeIndex>,
+    pub selection: &'a HashSet<NodeIndex>,
+    pub theme: &'a Theme,
     #[allow(dead_code)]
     pub label_offsets: &'a [Option<(f32, f32)>],
     #[allow(dead_code)]
     pub cached_label_positions: &'a [Option<(f32, f32)>],
+    pub emphasized: Option<&'a HashSet<NodeIndex>>, // Focus/filter set; everything else is dimmed
+    pub search_matches: &'a [SearchMatch],
+    pub editing: bool, // Show column handles for drawing relationships
 }
 
 impl<'a> DrawingContext<'a> {
@@ -22,6 +28,22 @@ impl<'a> DrawingContext<'a> {
             y * self.zoom + self.pan_offset.y,
         )
     }
+    
+    /// Whether a table falls outside the current focus/filter set
+    pub fn is_dimmed(&self, node_idx: NodeIndex) -> bool {
+        self.emphasized.is_some_and(|set| !set.contains(&node_idx))
+    }
+    
+    /// An edge is dimmed unless both of its tables are emphasized
+    pub fn is_edge_dimmed(&self, from_table: &str, to_table: &str) -> bool {
+        self.emphasized.is_some_and(|set| super::utils::edge_outside_set(self.erd_graph, set, from_table, to_table))
+    }
+}
+
+/// Wash out a rect by painting the canvas background over it
+pub fn dim_rect(theme: &Theme, ui: &mut egui::Ui, rect: Rect, rounding: f32) {
+    let bg = theme.background;
+    ui.painter().rect_filled(rect, rounding, Color32::from_rgba_unmultiplied(bg.r, bg.g, bg.b, 190));
 }
 
 pub fn draw_table(ctx: &DrawingContext, ui: &mut egui::Ui, node_idx: NodeIndex) {
@@ -34,20 +56,29 @@ pub fn draw_table(ctx: &DrawingContext, ui: &mut egui::Ui, node_idx: NodeIndex)
     let rect = Rect::from_min_size(pos, size);
     
     // Check if this table is selected
-    let is_selected = ctx.selected_table == Some(node_idx);
+    let is_selected = ctx.selection.contains(&node_idx);
     
     // Draw table background with shadow
     let shadow_rect = rect.translate(egui::vec2(3.0, 3.0));
-    ui.painter().rect_filled(shadow_rect, 8.0, Color32::from_black_alpha(30));
-    ui.painter().rect_filled(rect, 8.0, Color32::WHITE);
+    ui.painter().rect_filled(shadow_rect, 8.0, ctx.theme.table_shadow.to_color32());
+    ui.painter().rect_filled(rect, 8.0, ctx.theme.table_fill.to_color32());
+    
+    // Custom table colour from the `color=` attribute, if any
+    let custom_color = table.color.as_deref().and_then(super::utils::parse_hex_rgb);
+    let custom_base = custom_color.map(|(r, g, b)| Color32::from_rgb(r, g, b));
+    
+    // In compare mode added/removed/changed tables take their diff colour instead
+    let diff_color = super::utils::diff_color(table.status);
+    let custom_color = diff_color.or(custom_color);
+    let custom_base = diff_color.map(|(r, g, b)| Color32::from_rgb(r, g, b)).or(custom_base);
     
     // Use different colors for selected vs normal tables
     let border_color = if is_selected {
-        Color32::from_rgb(255, 200, 0) // Golden yellow for selected
+        ctx.theme.selected.to_color32()
     } else {
-        Color32::from_rgb(52, 152, 219) // Default blue
+        custom_base.unwrap_or(ctx.theme.table_border.to_color32())
     };
-    let border_width = if is_selected { 3.5 } else { 2.0 };
+    let border_width = if is_selected { 3.5 } else if diff_color.is_some() { 3.0 } else { 2.0 };
     
     ui.painter().rect_stroke(rect, 8.0, Stroke::new(border_width * ctx.zoom, border_color));
     
@@ -55,35 +86,75 @@ pub fn draw_table(ctx: &DrawingContext, ui: &mut egui::Ui, node_idx: NodeIndex)
     if is_selected {
         // Draw outer glow
         let glow_rect = rect.expand(4.0 * ctx.zoom);
-        ui.painter().rect_stroke(glow_rect, 10.0, Stroke::new(2.0 * ctx.zoom, Color32::from_rgba_premultiplied(255, 200, 0, 100)));
+        let glow = ctx.theme.selected;
+        ui.painter().rect_stroke(glow_rect, 10.0, Stroke::new(2.0 * ctx.zoom, Color32::from_rgba_unmultiplied(glow.r, glow.g, glow.b, 100)));
     }
     
     let header_height = 40.0 * ctx.zoom;
     let header_rect = Rect::from_min_size(rect.min, egui::vec2(size.x, header_height));
     let header_color = if is_selected {
-        Color32::from_rgb(255, 180, 0) // Brighter for selected
+        ctx.theme.selected_header.to_color32()
     } else {
-        Color32::from_rgb(52, 152, 219)
+        custom_base.unwrap_or(ctx.theme.header_fill.to_color32())
     };
     ui.painter().rect_filled(header_rect, 8.0, header_color);
     
-    // Draw table name
+    // Dark text on light custom colours so the name stays readable
+    let header_text_color = match custom_color {
+        Some((r, g, b)) if !is_selected => {
+            if super::utils::is_light_color(r, g, b) { Color32::from_rgb(44, 62, 80) } else { Color32::WHITE }
+        }
+        _ => ctx.theme.header_text.to_color32(),
+    };
+    
+    // Draw table name, prefixed with its icon if one is set
+    let header_text = match &table.icon {
+        Some(icon) => format!("{} {}", super::utils::icon_glyph(icon), table.name),
+        None => table.name.clone(),
+    };
     let text_pos = header_rect.center();
     ui.painter().text(
         text_pos,
         Align2::CENTER_CENTER,
-        &table.name,
+        &header_text,
         FontId::proportional(16.0 * ctx.zoom),
-        Color32::WHITE,
+        header_text_color,
     );
     
     // Draw columns
     let mut y_offset = header_height + 10.0 * ctx.zoom;
     let row_height = 25.0 * ctx.zoom;
     
-    for column in &table.columns {
+    for (col_idx, column) in table.columns.iter().enumerate() {
         let col_pos = Pos2::new(rect.min.x + 15.0 * ctx.zoom, rect.min.y + y_offset);
         
+        // Highlight columns that match the current search
+        if ctx.search_matches.iter().any(|m| m.node == node_idx && m.column == Some(col_idx)) {
+            let sel = ctx.theme.selected;
+            let row_rect = Rect::from_min_size(
+                Pos2::new(rect.min.x + 6.0 * ctx.zoom, col_pos.y - 4.0 * ctx.zoom),
+                egui::vec2(size.x - 12.0 * ctx.zoom, row_height - 2.0 * ctx.zoom),
+            );
+            ui.painter().rect_filled(row_rect, 4.0 * ctx.zoom, Color32::from_rgba_unmultiplied(sel.r, sel.g, sel.b, 60));
+        }
+        
+        // Tint added/removed/changed rows; whole-table changes are shown by the header instead
+        let row_color = super::utils::diff_color(column.status).filter(|_| column.status != table.status);
+        if let Some((r, g, b)) = row_color {
+            let row_rect = Rect::from_min_size(
+                Pos2::new(rect.min.x + 6.0 * ctx.zoom, col_pos.y - 4.0 * ctx.zoom),
+                egui::vec2(size.x - 12.0 * ctx.zoom, row_height - 2.0 * ctx.zoom),
+            );
+            ui.painter().rect_filled(row_rect, 4.0 * ctx.zoom, Color32::from_rgba_unmultiplied(r, g, b, 60));
+            if column.status == DiffStatus::Removed {
+                let y = row_rect.center().y;
+                ui.painter().line_segment(
+                    [Pos2::new(row_rect.min.x + 6.0 * ctx.zoom, y), Pos2::new(row_rect.max.x - 6.0 * ctx.zoom, y)],
+                    Stroke::new(1.0 * ctx.zoom, Color32::from_rgb(r, g, b)),
+                );
+            }
+        }
+        
         // Column name
         let col_name = if column.attributes.contains(&"PK".to_string()) {
             format!("🔑 {}", column.name)
@@ -96,7 +167,7 @@ pub fn draw_table(ctx: &DrawingContext, ui: &mut egui::Ui, node_idx: NodeIndex)
             Align2::LEFT_TOP,
             &col_name,
             FontId::proportional(12.0 * ctx.zoom),
-            Color32::from_rgb(44, 62, 80),
+            ctx.theme.column_text.to_color32(),
         );
         
         // Data type and attributes
@@ -113,11 +184,46 @@ pub fn draw_table(ctx: &DrawingContext, ui: &mut egui::Ui, node_idx: NodeIndex)
             Align2::RIGHT_TOP,
             &type_text,
             FontId::proportional(10.0 * ctx.zoom),
-            Color32::from_rgb(127, 140, 141),
+            ctx.theme.type_text.to_color32(),
         );
         
         y_offset += row_height;
     }
+    
+    // Relationship handles on the right edge of each column row
+    if ctx.editing {
+        for col_idx in 0..table.columns.len() {
+            let (x, y) = super::interaction::column_anchor(layout, col_idx);
+            let center = ctx.transform_point(x, y);
+            ui.painter().circle_filled(center, 4.0 * ctx.zoom.max(0.5), ctx.theme.table_fill.to_color32());
+            ui.painter().circle_stroke(center, 4.0 * ctx.zoom.max(0.5), Stroke::new(1.5, ctx.theme.selected.to_color32()));
+        }
+    }
+    
+    // Removed tables are ghosts: washed out, with the red outline kept on top
+    if table.status == DiffStatus::Removed {
+        let bg = ctx.theme.background;
+        ui.painter().rect_filled(rect, 8.0, Color32::from_rgba_unmultiplied(bg.r, bg.g, bg.b, 140));
+        ui.painter().rect_stroke(rect, 8.0, Stroke::new(border_width * ctx.zoom, border_color));
+    }
+    
+    if ctx.is_dimmed(node_idx) {
+        dim_rect(ctx.theme, ui, rect.expand(5.0 * ctx.zoom), 10.0);
+    }
+}
+
+/// Draw the relationship being dragged out of a column handle
+pub fn draw_connection_preview(ctx: &DrawingContext, ui: &mut egui::Ui, from: Pos2, to: Pos2) {
+    let stroke = Stroke::new(2.0 * ctx.zoom.max(0.5), ctx.theme.selected.to_color32());
+    ui.painter().line_segment([from, to], stroke);
+    ui.painter().circle_filled(to, 4.0, ctx.theme.selected.to_color32());
+}
+
+/// Draw the rubber-band selection rectangle (screen coordinates)
+pub fn draw_selection_box(ctx: &DrawingContext, ui: &mut egui::Ui, rect: Rect) {
+    let sel = ctx.theme.selected;
+    ui.painter().rect_filled(rect, 0.0, Color32::from_rgba_unmultiplied(sel.r, sel.g, sel.b, 30));
+    ui.painter().rect_stroke(rect, 0.0, Stroke::new(1.0, sel.to_color32()));
 }
 
 pub fn draw_title(ctx: &DrawingContext, ui: &mut egui::Ui, title: &str, title_position: (f32, f32), drag_target: &super::types::DragTarget) {
@@ -144,17 +250,17 @@ pub fn draw_title(ctx: &DrawingContext, ui: &mut egui::Ui, title: &str, title_po
     
     // Draw background with slight shadow
     let shadow_rect = bg_rect.translate(egui::vec2(2.0, 2.0));
-    ui.painter().rect_filled(shadow_rect, 6.0, Color32::from_black_alpha(40));
+    ui.painter().rect_filled(shadow_rect, 6.0, ctx.theme.table_shadow.to_color32());
     
     // Background color based on drag state
     let bg_color = if *drag_target == super::types::DragTarget::Title {
-        Color32::from_rgb(70, 130, 180) // Highlighted when dragging
+        ctx.theme.title_drag_fill.to_color32() // Highlighted when dragging
     } else {
-        Color32::from_rgb(52, 152, 219) // Normal blue
+        ctx.theme.title_fill.to_color32()
     };
     
     ui.painter().rect_filled(bg_rect, 6.0, bg_color);
-    ui.painter().rect_stroke(bg_rect, 6.0, Stroke::new(2.0 * ctx.zoom, Color32::WHITE));
+    ui.painter().rect_stroke(bg_rect, 6.0, Stroke::new(2.0 * ctx.zoom, ctx.theme.title_border.to_color32()));
     
     // Draw title text
     ui.painter().text(
@@ -162,7 +268,7 @@ pub fn draw_title(ctx: &DrawingContext, ui: &mut egui::Ui, title: &str, title_po
         Align2::CENTER_CENTER,
         title,
         FontId::proportional(font_size),
-        Color32::WHITE,
+        ctx.theme.title_text.to_color32(),
     );
 }
 
@@ -174,21 +280,25 @@ pub fn draw_edges(ctx: &DrawingContext, ui: &mut egui::Ui) {
             continue;
         }
         
-        // Check if this edge is connected to the selected table
-        let is_connected_to_selected = if let Some(selected_idx) = ctx.selected_table {
-            let selected_table_name = &ctx.erd_graph.graph()[selected_idx].name;
+        // Check if this edge is connected to any selected table
+        let is_connected_to_selected = ctx.selection.iter().any(|selected_idx| {
+            let selected_table_name = &ctx.erd_graph.graph()[*selected_idx].name;
             edge_route.from_table == *selected_table_name || edge_route.to_table == *selected_table_name
-        } else {
-            false
-        };
+        });
         
         // Use brighter color and thicker stroke for selected connections
         let color = if is_connected_to_selected {
-            Color32::from_rgb(255, 200, 0) // Golden yellow
+            ctx.theme.selected.to_color32()
         } else {
-            Color32::from_rgb(52, 73, 94) // Default dark blue
+            ctx.theme.edge.to_color32()
         };
         let stroke_width = if is_connected_to_selected { 3.0 } else { 2.0 };
+        let dimmed = ctx.is_edge_dimmed(&edge_route.from_table, &edge_route.to_table);
+        let (color, marker_color) = if dimmed {
+            (color.gamma_multiply(0.2), ctx.theme.edge.to_color32().gamma_multiply(0.2))
+        } else {
+            (color, ctx.theme.edge.to_color32())
+        };
         let stroke = Stroke::new(stroke_width * ctx.zoom, color);
         
         let mut prev_point = None;
@@ -212,27 +322,27 @@ pub fn draw_edges(ctx: &DrawingContext, ui: &mut egui::Ui) {
         // Draw markers based on relationship type
         match edge_route.relationship_type {
             RelationType::OneToOne => {
-                draw_one_marker(ctx, ui, &edge_route.points, start_idx);
-                draw_one_marker(ctx, ui, &edge_route.points, end_idx);
+                draw_one_marker(ctx, ui, &edge_route.points, start_idx, marker_color);
+                draw_one_marker(ctx, ui, &edge_route.points, end_idx, marker_color);
             }
             RelationType::OneToMany => {
-                draw_one_marker(ctx, ui, &edge_route.points, start_idx);
-                draw_many_marker(ctx, ui, &edge_route.points, end_idx);
+                draw_one_marker(ctx, ui, &edge_route.points, start_idx, marker_color);
+                draw_many_marker(ctx, ui, &edge_route.points, end_idx, marker_color);
             }
             RelationType::ManyToOne => {
-                draw_many_marker(ctx, ui, &edge_route.points, start_idx);
-                draw_one_marker(ctx, ui, &edge_route.points, end_idx);
+                draw_many_marker(ctx, ui, &edge_route.points, start_idx, marker_color);
+                draw_one_marker(ctx, ui, &edge_route.points, end_idx, marker_color);
             }
             RelationType::ManyToMany => {
-                draw_many_marker(ctx, ui, &edge_route.points, start_idx);
-                draw_many_marker(ctx, ui, &edge_route.points, end_idx);
+                draw_many_marker(ctx, ui, &edge_route.points, start_idx, marker_color);
+                draw_many_marker(ctx, ui, &edge_route.points, end_idx, marker_color);
             }
         }
     }
 }
 
 /// Draw "one" marker (single perpendicular line)
-pub fn draw_one_marker(ctx: &DrawingContext, ui: &mut egui::Ui, points: &[crate::renderer::layout::Point], idx: usize) {
+pub fn draw_one_marker(ctx: &DrawingContext, ui: &mut egui::Ui, points: &[crate::renderer::layout::Point], idx: usize, color: Color32) {
     if points.len() < 2 {
         return;
     }
@@ -262,12 +372,12 @@ pub fn draw_one_marker(ctx: &DrawingContext, ui: &mut egui::Ui, points: &[crate:
     // Draw perpendicular line with thicker stroke for visibility
     ui.painter().line_segment(
         [pos + perp * size, pos - perp * size],
-        Stroke::new(2.5 * ctx.zoom, Color32::from_rgb(52, 73, 94)),
+        Stroke::new(2.5 * ctx.zoom, color),
     );
 }
 
 /// Draw "many" marker (crow's foot - three lines)
-pub fn draw_many_marker(ctx: &DrawingContext, ui: &mut egui::Ui, points: &[crate::renderer::layout::Point], idx: usize) {
+pub fn draw_many_marker(ctx: &DrawingContext, ui: &mut egui::Ui, points: &[crate::renderer::layout::Point], idx: usize, color: Color32) {
     if points.len() < 2 {
         return;
     }
@@ -303,7 +413,7 @@ pub fn draw_many_marker(ctx: &DrawingContext, ui: &mut egui::Ui, points: &[crate
     };
     
     // Draw three lines forming crow's foot with thicker strokes for visibility
-    let crow_stroke = Stroke::new(2.5 * ctx.zoom, Color32::from_rgb(52, 73, 94));
+    let crow_stroke = Stroke::new(2.5 * ctx.zoom, color);
     
     // Center line (pointing to the connection point)
     ui.painter().line_segment([base, pos], crow_stroke);
@@ -0,0 +1,215 @@
+use crate::formats::xml_escape;
+use crate::renderer::graph::{DiffStatus, ErdGraph, RelationType};
+use crate::renderer::layout::{EdgeRoute, LayoutEngine, NodeLayout};
+use crate::renderer::theme::{Theme, ThemeColor};
+
+use super::pdf::rgb;
+
+pub struct DrawioContext<'a> {
+    pub erd_graph: &'a ErdGraph,
+    pub layout_engine: &'a LayoutEngine,
+    pub theme: &'a Theme,
+}
+
+/// Header and row heights, matching the canvas so tables keep their size
+const HEADER_HEIGHT: f32 = 40.0;
+const ROW_HEIGHT: f32 = 25.0;
+/// Space below the last row: the canvas sizes tables as 60 + 25 per column
+const FOOTER_HEIGHT: f32 = 20.0;
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// draw.io ER arrow heads at the start and end of a relationship,
+/// the same ends the SVG export puts crow's feet and bars on
+fn arrows(relationship_type: RelationType) -> (&'static str, &'static str) {
+    match relationship_type {
+        RelationType::OneToOne => ("ERone", "ERone"),
+        RelationType::OneToMany => ("ERone", "ERmany"),
+        RelationType::ManyToOne => ("ERmany", "ERone"),
+        RelationType::ManyToMany => ("ERmany", "ERmany"),
+    }
+}
+
+/// Position of a route end on a table's border, as draw.io's relative
+/// `exitX`/`exitY` (or `entryX`/`entryY`) constraint
+fn port(layout: &NodeLayout, point: &crate::renderer::layout::Point) -> (f32, f32) {
+    let x = (point.x - layout.position.x) / layout.size.width.max(1.0);
+    let y = (point.y - layout.position.y) / layout.size.height.max(1.0);
+    (x.clamp(0.0, 1.0), y.clamp(0.0, 1.0))
+}
+
+fn label_text(route: &EdgeRoute) -> String {
+    let rel_text = match route.relationship_type {
+        RelationType::OneToOne => "[1:1]",
+        RelationType::OneToMany => "[1:M]",
+        RelationType::ManyToOne => "[M:1]",
+        RelationType::ManyToMany => "[M:M]",
+    };
+    format!("{} {}.{}:{}.{}",
+        rel_text,
+        route.from_table,
+        route.label.split(':').next().unwrap_or(""),
+        route.to_table,
+        route.label.split(':').nth(1).unwrap_or("")
+    )
+}
+
+/// Tables as swimlanes with one row per column and relationships as ER
+/// edges, placed at the computed layout positions
+pub fn generate_drawio(ctx: &DrawioContext, title: &str, title_position: (f32, f32)) -> String {
+    let theme = ctx.theme;
+    let background = theme.background;
+    let color = |c: ThemeColor| hex(rgb(c, background));
+    let name = if title.is_empty() { "ERD" } else { title };
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<mxfile host=\"FreeERD\">\n");
+    xml.push_str(&format!("  <diagram id=\"erd\" name=\"{}\">\n", xml_escape(name)));
+    xml.push_str(&format!(
+        "    <mxGraphModel grid=\"1\" gridSize=\"10\" guides=\"1\" tooltips=\"1\" connect=\"1\" arrows=\"1\" fold=\"1\" page=\"0\" pageScale=\"1\" background=\"{}\" math=\"0\" shadow=\"0\">\n",
+        color(background)
+    ));
+    xml.push_str("      <root>\n        <mxCell id=\"0\" />\n        <mxCell id=\"1\" parent=\"0\" />\n");
+
+    for node_idx in ctx.erd_graph.graph().node_indices() {
+        let table = &ctx.erd_graph.graph()[node_idx];
+        let Some(layout) = ctx.layout_engine.get_node_layout(node_idx) else { continue };
+
+        // Custom and compare-mode colours override the theme as in the SVG export
+        let custom = super::utils::diff_color(table.status)
+            .or_else(|| table.color.as_deref().and_then(super::utils::parse_hex_rgb));
+        let border = custom.map(hex).unwrap_or_else(|| color(theme.table_border));
+        let header = custom.map(hex).unwrap_or_else(|| color(theme.header_fill));
+        let header_text = match custom {
+            Some((r, g, b)) if super::utils::is_light_color(r, g, b) => "#2c3e50".to_string(),
+            Some(_) => "#ffffff".to_string(),
+            None => color(theme.header_text),
+        };
+        let dashed = if table.status == DiffStatus::Removed { "dashed=1;dashPattern=6 4;" } else { "" };
+
+        let id = format!("table-{}", node_idx.index());
+        xml.push_str(&format!(
+            "        <mxCell id=\"{}\" value=\"{}\" style=\"swimlane;html=1;whiteSpace=wrap;childLayout=stackLayout;horizontal=1;horizontalStack=0;startSize={};resizeParent=1;resizeParentMax=0;resizeLast=0;marginBottom={};collapsible=1;swimlaneLine=0;rounded=1;arcSize=8;absoluteArcSize=1;fontSize=14;fontColor={};fillColor={};swimlaneFillColor={};strokeColor={};strokeWidth=2;{}\" vertex=\"1\" parent=\"1\">\n",
+            id, xml_escape(&table.name), HEADER_HEIGHT, FOOTER_HEIGHT, header_text, header,
+            color(theme.table_fill), border, dashed
+        ));
+        xml.push_str(&format!(
+            "          <mxGeometry x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" as=\"geometry\" />\n        </mxCell>\n",
+            layout.position.x, layout.position.y, layout.size.width, layout.size.height
+        ));
+
+        for (row, column) in table.columns.iter().enumerate() {
+            let type_text = if column.attributes.is_empty() {
+                column.data_type.clone()
+            } else {
+                format!("{} [{}]", column.data_type, column.attributes.join(","))
+            };
+            // Labels are HTML, so the names are escaped once for HTML and again for XML
+            let value = format!(
+                "{}&nbsp;&nbsp;<span style=\"color:{};font-size:9px\">{}</span>",
+                xml_escape(&column.name), color(theme.type_text), xml_escape(&type_text)
+            );
+            let (fill, strike) = match super::utils::diff_color(column.status).filter(|_| column.status != table.status) {
+                Some((r, g, b)) => (
+                    hex(rgb(ThemeColor::rgba(r, g, b, 64), theme.table_fill)),
+                    if column.status == DiffStatus::Removed { "fontStyle=8;" } else { "" },
+                ),
+                None => ("none".to_string(), ""),
+            };
+            xml.push_str(&format!(
+                "        <mxCell id=\"{}-column-{}\" value=\"{}\" style=\"text;html=1;align=left;verticalAlign=middle;spacingLeft=15;spacingRight=15;overflow=hidden;rotatable=0;points=[[0,0.5],[1,0.5]];portConstraint=eastwest;fontSize=11;fontColor={};strokeColor=none;fillColor={};{}\" vertex=\"1\" parent=\"{}\">\n",
+                id, row, xml_escape(&value), color(theme.column_text), fill, strike, id
+            ));
+            xml.push_str(&format!(
+                "          <mxGeometry y=\"{}\" width=\"{}\" height=\"{}\" as=\"geometry\" />\n        </mxCell>\n",
+                HEADER_HEIGHT + row as f32 * ROW_HEIGHT, layout.size.width, ROW_HEIGHT
+            ));
+        }
+    }
+
+    for (idx, route) in ctx.layout_engine.get_edge_routes().iter().enumerate() {
+        if route.points.len() < 2 {
+            continue;
+        }
+        let (Some(source), Some(target)) = (ctx.erd_graph.node_index(&route.from_table), ctx.erd_graph.node_index(&route.to_table)) else { continue };
+        let (Some(source_layout), Some(target_layout)) = (ctx.layout_engine.get_node_layout(source), ctx.layout_engine.get_node_layout(target)) else { continue };
+
+        // Pinning both ends and every bend keeps draw.io from rerouting the line
+        let (exit_x, exit_y) = port(source_layout, &route.points[0]);
+        let (entry_x, entry_y) = port(target_layout, &route.points[route.points.len() - 1]);
+        let (start_arrow, end_arrow) = arrows(route.relationship_type);
+        xml.push_str(&format!(
+            "        <mxCell id=\"relationship-{}\" value=\"{}\" style=\"edgeStyle=none;html=1;rounded=0;startArrow={};endArrow={};startFill=0;endFill=0;startSize=12;endSize=12;strokeColor={};strokeWidth=2;fontSize=11;fontColor={};labelBackgroundColor={};labelBorderColor={};exitX={};exitY={};exitDx=0;exitDy=0;exitPerimeter=0;entryX={};entryY={};entryDx=0;entryDy=0;entryPerimeter=0;\" edge=\"1\" parent=\"1\" source=\"table-{}\" target=\"table-{}\">\n",
+            idx, xml_escape(&label_text(route)), start_arrow, end_arrow,
+            color(theme.edge), color(theme.label_text), color(theme.label_fill), color(theme.label_border),
+            exit_x, exit_y, entry_x, entry_y, source.index(), target.index()
+        ));
+        xml.push_str("          <mxGeometry relative=\"1\" as=\"geometry\">\n");
+        let bends = &route.points[1..route.points.len() - 1];
+        if !bends.is_empty() {
+            xml.push_str("            <Array as=\"points\">\n");
+            for point in bends {
+                xml.push_str(&format!("              <mxPoint x=\"{}\" y=\"{}\" />\n", point.x, point.y));
+            }
+            xml.push_str("            </Array>\n");
+        }
+        xml.push_str("          </mxGeometry>\n        </mxCell>\n");
+    }
+
+    if !title.is_empty() {
+        // Centred on the title position with its baseline there, as in the SVG
+        let width = title.chars().count() as f32 * 18.0 + 40.0;
+        xml.push_str(&format!(
+            "        <mxCell id=\"title\" value=\"{}\" style=\"text;html=1;align=center;verticalAlign=bottom;fontSize=32;fontColor={};strokeColor=none;fillColor=none;\" vertex=\"1\" parent=\"1\">\n",
+            xml_escape(title), color(theme.heading_text)
+        ));
+        xml.push_str(&format!(
+            "          <mxGeometry x=\"{}\" y=\"{}\" width=\"{}\" height=\"48\" as=\"geometry\" />\n        </mxCell>\n",
+            title_position.0 - width / 2.0, title_position.1 - 40.0, width
+        ));
+    }
+
+    xml.push_str("      </root>\n    </mxGraphModel>\n  </diagram>\n</mxfile>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_drawio_keeps_layout() {
+        let schema = Parser::new(r#"
+table Users {
+  id: int [pk],
+  name: str
+}
+table Orders {
+  id: int [pk],
+  user_id: int [fk]
+}
+Users.id > Orders.user_id
+"#).parse().unwrap();
+        let erd_graph = ErdGraph::from_schema(&schema).unwrap();
+        let mut layout_engine = LayoutEngine::new();
+        layout_engine.compute_layout(&erd_graph);
+        let theme = Theme::light();
+        let ctx = DrawioContext { erd_graph: &erd_graph, layout_engine: &layout_engine, theme: &theme };
+        let xml = generate_drawio(&ctx, "Shop <1>", (0.0, 0.0));
+
+        let users = erd_graph.node_index("Users").unwrap();
+        let layout = layout_engine.get_node_layout(users).unwrap();
+        assert!(xml.contains(&format!(
+            "<mxGeometry x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" as=\"geometry\" />",
+            layout.position.x, layout.position.y, layout.size.width, layout.size.height
+        )));
+        assert!(xml.contains(&format!("id=\"table-{}\" value=\"Users\" style=\"swimlane;", users.index())));
+        assert_eq!(xml.matches("-column-").count(), 4);
+        assert!(xml.contains("value=\"user_id&amp;nbsp;&amp;nbsp;&lt;span"));
+        assert!(xml.contains("value=\"[1:M] Users.id:Orders.user_id\""));
+        assert!(xml.contains("startArrow=ERone;endArrow=ERmany;"));
+        assert!(xml.contains("<diagram id=\"erd\" name=\"Shop &lt;1&gt;\">"));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::ast::Schema;
+use petgraph::graph::NodeIndex;
+use std::collections::VecDeque;
+
+/// Number of edits kept for undo before the oldest are dropped
+pub const DEFAULT_HISTORY_LIMIT: usize = 200;
+
+/// Schema plus table positions (by name), so undoing a structural edit also
+/// puts the tables back where they were
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaSnapshot {
+    pub schema: Schema,
+    pub positions: Vec<(String, (f32, f32))>,
+}
+
+/// A reversible canvas edit. Each variant stores the value before and after the edit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditCommand {
+    MoveTable { node: NodeIndex, from: (f32, f32), to: (f32, f32) },
+    MoveLabel { edge: usize, from: Option<(f32, f32)>, to: Option<(f32, f32)> }, // Label offsets; None is automatic placement
+    MoveTitle { from: (f32, f32), to: (f32, f32) },
+    Batch(Vec<EditCommand>), // Several edits undone as one, e.g. a group drag
+    Schema { from: Box<SchemaSnapshot>, to: Box<SchemaSnapshot> },
+}
+
+impl EditCommand {
+    /// The command that reverts this one
+    pub fn inverse(&self) -> EditCommand {
+        match self {
+            EditCommand::MoveTable { node, from, to } => EditCommand::MoveTable { node: *node, from: *to, to: *from },
+            EditCommand::MoveLabel { edge, from, to } => EditCommand::MoveLabel { edge: *edge, from: *to, to: *from },
+            EditCommand::MoveTitle { from, to } => EditCommand::MoveTitle { from: *to, to: *from },
+            EditCommand::Batch(commands) => EditCommand::Batch(commands.iter().rev().map(|c| c.inverse()).collect()),
+            EditCommand::Schema { from, to } => EditCommand::Schema { from: to.clone(), to: from.clone() },
+        }
+    }
+
+    /// True if applying the command would change nothing (e.g. a click without a drag)
+    pub fn is_noop(&self) -> bool {
+        match self {
+            EditCommand::MoveTable { from, to, .. } => from == to,
+            EditCommand::MoveLabel { from, to, .. } => from == to,
+            EditCommand::MoveTitle { from, to } => from == to,
+            EditCommand::Batch(commands) => commands.iter().all(|c| c.is_noop()),
+            EditCommand::Schema { from, to } => from == to,
+        }
+    }
+}
+
+/// Undo/redo stacks of canvas edits, capped at `limit` entries
+pub struct History {
+    undo_stack: VecDeque<EditCommand>,
+    redo_stack: Vec<EditCommand>,
+    limit: usize,
+}
+
+impl History {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Record an edit that has already been applied. Clears the redo stack.
+    pub fn push(&mut self, command: EditCommand) {
+        if command.is_noop() {
+            return;
+        }
+        self.redo_stack.clear();
+        self.undo_stack.push_back(command);
+        while self.undo_stack.len() > self.limit {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Pop the last edit and return the command that reverts it
+    pub fn undo(&mut self) -> Option<EditCommand> {
+        let command = self.undo_stack.pop_back()?;
+        let inverse = command.inverse();
+        self.redo_stack.push(command);
+        Some(inverse)
+    }
+
+    /// Pop the last undone edit and return it for re-applying
+    pub fn redo(&mut self) -> Option<EditCommand> {
+        let command = self.redo_stack.pop()?;
+        self.undo_stack.push_back(command.clone());
+        Some(command)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title_move(from: f32, to: f32) -> EditCommand {
+        EditCommand::MoveTitle { from: (from, 0.0), to: (to, 0.0) }
+    }
+
+    #[test]
+    fn test_undo_redo_round_trip() {
+        let mut history = History::default();
+        history.push(title_move(0.0, 10.0));
+        history.push(EditCommand::MoveLabel { edge: 2, from: None, to: Some((5.0, 5.0)) });
+
+        assert_eq!(
+            history.undo(),
+            Some(EditCommand::MoveLabel { edge: 2, from: Some((5.0, 5.0)), to: None })
+        );
+        assert_eq!(history.undo(), Some(title_move(10.0, 0.0)));
+        assert_eq!(history.undo(), None);
+        assert!(history.can_redo());
+
+        assert_eq!(history.redo(), Some(title_move(0.0, 10.0)));
+        assert!(history.can_undo());
+    }
+
+    #[test]
+    fn test_push_clears_redo() {
+        let mut history = History::default();
+        history.push(title_move(0.0, 10.0));
+        history.undo();
+        history.push(title_move(0.0, 20.0));
+
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let node = NodeIndex::new(0);
+        let batch = EditCommand::Batch(vec![
+            EditCommand::MoveTable { node, from: (0.0, 0.0), to: (5.0, 0.0) },
+            title_move(1.0, 2.0),
+        ]);
+        assert_eq!(batch.inverse(), EditCommand::Batch(vec![
+            title_move(2.0, 1.0),
+            EditCommand::MoveTable { node, from: (5.0, 0.0), to: (0.0, 0.0) },
+        ]));
+        assert!(EditCommand::Batch(vec![title_move(3.0, 3.0)]).is_noop());
+    }
+
+    #[test]
+    fn test_history_limit_and_noops() {
+        let mut history = History::new(3);
+        history.push(title_move(1.0, 1.0));
+        assert!(!history.can_undo());
+
+        for i in 0..5 {
+            history.push(title_move(i as f32, i as f32 + 1.0));
+        }
+        assert_eq!(history.undo(), Some(title_move(5.0, 4.0)));
+        assert_eq!(history.undo(), Some(title_move(4.0, 3.0)));
+        assert_eq!(history.undo(), Some(title_move(3.0, 2.0)));
+        assert_eq!(history.undo(), None);
+    }
+}
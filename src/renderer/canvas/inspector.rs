@@ -0,0 +1,220 @@
+use crate::ast::{Attribute, DataType, RelationshipType, Schema};
+use crate::editor::SchemaEdit;
+
+/// Types offered in the column type picker; custom types are kept as they are
+const DATA_TYPES: [DataType; 12] = [
+    DataType::String,
+    DataType::Int,
+    DataType::Bool,
+    DataType::Double,
+    DataType::Float,
+    DataType::Decimal,
+    DataType::Date,
+    DataType::Time,
+    DataType::DateTime,
+    DataType::Blob,
+    DataType::TinyBlob,
+    DataType::LargeBlob,
+];
+
+/// Attribute toggles shown per column, with their short labels
+const ATTRIBUTE_TOGGLES: [(Attribute, &str); 6] = [
+    (Attribute::PrimaryKey, "PK"),
+    (Attribute::ForeignKey, "FK"),
+    (Attribute::Unique, "UQ"),
+    (Attribute::Nullable, "NULL"),
+    (Attribute::Indexed, "IDX"),
+    (Attribute::AutoIncrement, "AI"),
+];
+
+const RELATIONSHIP_TYPES: [RelationshipType; 4] = [
+    RelationshipType::OneToMany,
+    RelationshipType::ManyToOne,
+    RelationshipType::ManyToMany,
+    RelationshipType::OneToOne,
+];
+
+/// Text typed into the editor panel. Names are only committed when a field
+/// loses focus, so a half-typed rename never reaches the schema.
+#[derive(Default)]
+pub struct InspectorState {
+    loaded: Option<Option<String>>, // Table the buffers were filled from; None forces a reload
+    title: String,
+    table_name: String,
+    column_names: Vec<String>,
+    new_column: String,
+    new_table: String,
+}
+
+impl InspectorState {
+    /// Refill the buffers from the schema on the next frame
+    pub fn reload(&mut self) {
+        self.loaded = None;
+    }
+
+    fn sync(&mut self, schema: &Schema, selected: Option<&str>) {
+        let selected = selected.map(str::to_string);
+        if self.loaded.as_ref() == Some(&selected) {
+            return;
+        }
+        self.title = schema.title.clone().unwrap_or_default();
+        let table = selected.as_deref().and_then(|name| schema.tables.iter().find(|t| t.name == name));
+        self.table_name = table.map(|t| t.name.clone()).unwrap_or_default();
+        self.column_names = table.map(|t| t.columns.iter().map(|c| c.name.clone()).collect()).unwrap_or_default();
+        self.new_column.clear();
+        self.loaded = Some(selected);
+    }
+}
+
+fn relationship_symbol(relationship_type: RelationshipType) -> &'static str {
+    match relationship_type {
+        RelationshipType::OneToMany => ">",
+        RelationshipType::ManyToOne => "<",
+        RelationshipType::ManyToMany => "<>",
+        RelationshipType::OneToOne => "-",
+    }
+}
+
+/// Draw the schema editor panel and return the edits the user made this frame
+pub fn show(ui: &mut egui::Ui, state: &mut InspectorState, schema: &Schema, selected: Option<&str>) -> Vec<SchemaEdit> {
+    state.sync(schema, selected);
+    let mut edits = Vec::new();
+
+    ui.heading("Schema");
+    ui.horizontal(|ui| {
+        ui.label("Title");
+        let response = ui.text_edit_singleline(&mut state.title);
+        let title = Some(state.title.trim().to_string()).filter(|t| !t.is_empty());
+        if response.lost_focus() && title != schema.title {
+            edits.push(SchemaEdit::SetTitle(title));
+        }
+    });
+    ui.horizontal(|ui| {
+        let response = ui.add(egui::TextEdit::singleline(&mut state.new_table).hint_text("new_table").desired_width(140.0));
+        let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.button("➕ Add table").clicked() || enter) && !state.new_table.trim().is_empty() {
+            edits.push(SchemaEdit::AddTable { name: state.new_table.trim().to_string() });
+            state.new_table.clear();
+        }
+    });
+    ui.separator();
+
+    let Some(table) = selected.and_then(|name| schema.tables.iter().find(|t| t.name == name)) else {
+        ui.label("Select a table to edit it.");
+        return edits;
+    };
+
+    ui.horizontal(|ui| {
+        ui.label("Table");
+        let response = ui.text_edit_singleline(&mut state.table_name);
+        let name = state.table_name.trim();
+        if response.lost_focus() && name != table.name && !name.is_empty() {
+            edits.push(SchemaEdit::RenameTable { from: table.name.clone(), to: name.to_string() });
+        }
+    });
+
+    ui.label("Columns");
+    egui::Grid::new("inspector_columns").striped(true).show(ui, |ui| {
+        for (idx, column) in table.columns.iter().enumerate() {
+            if let Some(buffer) = state.column_names.get_mut(idx) {
+                let response = ui.add(egui::TextEdit::singleline(buffer).desired_width(110.0));
+                let name = buffer.trim();
+                if response.lost_focus() && name != column.name && !name.is_empty() {
+                    edits.push(SchemaEdit::RenameColumn {
+                        table: table.name.clone(),
+                        from: column.name.clone(),
+                        to: name.to_string(),
+                    });
+                }
+            }
+
+            let mut datatype = column.datatype.clone();
+            egui::ComboBox::from_id_salt(("inspector_type", idx))
+                .selected_text(datatype.to_string())
+                .width(80.0)
+                .show_ui(ui, |ui| {
+                    for option in DATA_TYPES {
+                        let label = option.to_string();
+                        ui.selectable_value(&mut datatype, option, label);
+                    }
+                });
+            if datatype != column.datatype {
+                edits.push(SchemaEdit::SetColumnType { table: table.name.clone(), column: column.name.clone(), datatype });
+            }
+
+            ui.horizontal(|ui| {
+                for (attribute, label) in &ATTRIBUTE_TOGGLES {
+                    let mut enabled = column.attributes.contains(attribute);
+                    if ui.toggle_value(&mut enabled, *label).changed() {
+                        let mut attributes: Vec<Attribute> = column.attributes.iter()
+                            .filter(|a| *a != attribute)
+                            .cloned()
+                            .collect();
+                        if enabled {
+                            attributes.push(attribute.clone());
+                        }
+                        edits.push(SchemaEdit::SetColumnAttributes {
+                            table: table.name.clone(),
+                            column: column.name.clone(),
+                            attributes,
+                        });
+                    }
+                }
+            });
+
+            if ui.small_button("🗑").on_hover_text("Delete column").clicked() {
+                edits.push(SchemaEdit::DeleteColumn { table: table.name.clone(), column: column.name.clone() });
+            }
+            ui.end_row();
+        }
+    });
+
+    ui.horizontal(|ui| {
+        let response = ui.add(egui::TextEdit::singleline(&mut state.new_column).hint_text("new_column").desired_width(140.0));
+        let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.button("➕ Add column").clicked() || enter) && !state.new_column.trim().is_empty() {
+            edits.push(SchemaEdit::AddColumn {
+                table: table.name.clone(),
+                column: state.new_column.trim().to_string(),
+                datatype: DataType::String,
+            });
+        }
+    });
+
+    ui.separator();
+    ui.label("Relationships");
+    let related: Vec<_> = schema.relationships.iter().enumerate()
+        .filter(|(_, rel)| rel.from_table == table.name || rel.to_table == table.name)
+        .collect();
+    if related.is_empty() {
+        ui.weak("Drag from a column handle onto another table's column to add one.");
+    }
+    for (index, rel) in related {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}.{}", rel.from_table, rel.from_field));
+            let mut relationship_type = rel.relationship_type;
+            egui::ComboBox::from_id_salt(("inspector_rel", index))
+                .selected_text(relationship_symbol(relationship_type))
+                .width(40.0)
+                .show_ui(ui, |ui| {
+                    for option in RELATIONSHIP_TYPES {
+                        ui.selectable_value(&mut relationship_type, option, format!("{}  {}", relationship_symbol(option), option));
+                    }
+                });
+            if relationship_type != rel.relationship_type {
+                edits.push(SchemaEdit::SetRelationshipType { index, relationship_type });
+            }
+            ui.label(format!("{}.{}", rel.to_table, rel.to_field));
+            if ui.small_button("🗑").on_hover_text("Delete relationship").clicked() {
+                edits.push(SchemaEdit::DeleteRelationship { index });
+            }
+        });
+    }
+
+    ui.separator();
+    if ui.button("🗑 Delete table").clicked() {
+        edits.push(SchemaEdit::DeleteTable { name: table.name.clone() });
+    }
+
+    edits
+}
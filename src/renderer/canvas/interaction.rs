@@ -54,8 +54,9 @@ pub fn get_edge_middle_point_simple(points: &[crate::renderer::layout::Point]) -
     }
     
     None
-}#[allow(dead_code)]
+}
 
+#[allow(dead_code)]
 pub fn check_title_click(
     pos: Pos2,
     title_screen: Pos2,
@@ -106,3 +107,26 @@ pub fn check_table_click(
     
     table_rect.contains(pos)
 }
+
+/// World-space centre of the relationship handle on a column row's right edge
+pub fn column_anchor(layout: &crate::renderer::layout::NodeLayout, column_idx: usize) -> (f32, f32) {
+    // Rows start 10px below the 40px header and are 25px tall; text sits near the top
+    let y = layout.position.y + 50.0 + column_idx as f32 * 25.0 + 8.0;
+    (layout.position.x + layout.size.width, y)
+}
+
+/// Index of the column row under a world-space point, if it is inside the table
+pub fn column_at(
+    layout: &crate::renderer::layout::NodeLayout,
+    column_count: usize,
+    world_x: f32,
+    world_y: f32,
+) -> Option<usize> {
+    let inside_x = world_x >= layout.position.x && world_x <= layout.position.x + layout.size.width;
+    let row_top = layout.position.y + 46.0;
+    if !inside_x || world_y < row_top {
+        return None;
+    }
+    let idx = ((world_y - row_top) / 25.0) as usize;
+    (idx < column_count).then_some(idx)
+}
@@ -1,15 +1,33 @@
+// The interactive canvas needs the `viewer` feature; the exporters don't
+#[cfg(feature = "viewer")]
 pub mod types;
+#[cfg(feature = "viewer")]
 pub mod drawing;
 pub mod svg;
+pub mod pdf;
+pub mod drawio;
+#[cfg(feature = "viewer")]
 pub mod interaction;
 pub mod utils;
+#[cfg(feature = "viewer")]
+pub mod history;
+#[cfg(feature = "viewer")]
+pub mod inspector;
 
-use egui::{Color32, Stroke, Pos2, Rect, FontId};
-use super::graph::ErdGraph;
-use super::layout::LayoutEngine;
-use petgraph::graph::NodeIndex;
-use types::DragTarget;
+#[cfg(feature = "viewer")]
+use {
+    egui::{Stroke, Pos2, Rect, FontId},
+    super::graph::{ErdGraph, SearchMatch},
+    super::layout::LayoutEngine,
+    super::theme::Theme,
+    petgraph::graph::NodeIndex,
+    crate::editor::{self, Document, SchemaEdit},
+    std::collections::{HashMap, HashSet},
+    types::DragTarget,
+    history::{EditCommand, History, SchemaSnapshot},
+};
 
+#[cfg(feature = "viewer")]
 pub struct ErdCanvas {
     erd_graph: ErdGraph,
     layout_engine: LayoutEngine,
@@ -20,13 +38,31 @@ pub struct ErdCanvas {
     drag_offset: Pos2,
     label_offsets: Vec<Option<(f32, f32)>>, // Custom offsets for labels in world coordinates
     cached_label_positions: Vec<Option<(f32, f32)>>, // Cached actual label positions in world coordinates
-    selected_table: Option<NodeIndex>, // Currently selected table for highlighting
+    selected_table: Option<NodeIndex>, // Primary selection: focus mode centre and SVG highlight
+    selection: HashSet<NodeIndex>, // All selected tables, including the primary one
     title: String,
     title_position: (f32, f32), // Title position in world coordinates
+    theme: Theme,
+    available_themes: Vec<Theme>, // Built-in themes followed by user themes
+    search_open: bool,
+    search_query: String,
+    search_matches: Vec<SearchMatch>,
+    search_index: usize, // Match currently centred in the view
+    search_focus_requested: bool,
+    pending_jump: Option<NodeIndex>, // Table to centre on during the next canvas pass
+    focus_mode: bool,
+    focus_hops: usize,
+    history: History,
+    pending_edit: Option<EditCommand>, // Position snapshot taken when the current drag started
+    document: Option<Document>, // Source file being edited; None for read-only views
+    editor_open: bool,
+    inspector: inspector::InspectorState,
+    status_message: Option<String>, // Last save result or rejected edit
 }
 
+#[cfg(feature = "viewer")]
 impl ErdCanvas {
-    pub fn new(erd_graph: ErdGraph, title: String) -> Self {
+    pub fn new(erd_graph: ErdGraph, title: String, theme: Theme, document: Option<Document>) -> Self {
         let mut layout_engine = LayoutEngine::new();
         layout_engine.compute_layout(&erd_graph);
         
@@ -34,26 +70,15 @@ impl ErdCanvas {
         let label_count = layout_engine.get_edge_routes().len();
         let label_offsets = vec![None; label_count];
         
-        // Calculate title position at the top center of the graph
-        let mut min_y = f32::MAX;
-        let mut center_x = 0.0;
-        let mut count = 0;
+        let title_position = utils::default_title_position(&erd_graph, &layout_engine);
         
-        for node_idx in erd_graph.graph().node_indices() {
-            if let Some(layout) = layout_engine.get_node_layout(node_idx) {
-                min_y = min_y.min(layout.position.y);
-                center_x += layout.position.x + layout.size.width / 2.0;
-                count += 1;
-            }
-        }
-        
-        if count > 0 {
-            center_x /= count as f32;
+        // Offer the starting theme in the menu even if it came from a file elsewhere
+        let mut available_themes = Theme::built_in();
+        available_themes.extend(Theme::load_user_themes());
+        if !available_themes.iter().any(|t| t.name == theme.name) {
+            available_themes.push(theme.clone());
         }
         
-        // Position title above the topmost table
-        let title_position = (center_x, min_y - 80.0);
-        
         Self {
             erd_graph,
             layout_engine,
@@ -65,8 +90,344 @@ impl ErdCanvas {
             label_offsets: label_offsets.clone(),
             cached_label_positions: vec![None; label_count],
             selected_table: None,
+            selection: HashSet::new(),
             title,
             title_position,
+            theme,
+            available_themes,
+            search_open: false,
+            search_query: String::new(),
+            search_matches: Vec::new(),
+            search_index: 0,
+            search_focus_requested: false,
+            pending_jump: None,
+            focus_mode: false,
+            focus_hops: 1,
+            history: History::default(),
+            pending_edit: None,
+            document,
+            editor_open: false,
+            inspector: inspector::InspectorState::default(),
+            status_message: None,
+        }
+    }
+    
+    /// Replace the selection with a single table, or clear it
+    fn select_only(&mut self, node: Option<NodeIndex>) {
+        self.selection.clear();
+        self.selection.extend(node);
+        self.selected_table = node;
+    }
+    
+    /// Select every table, keeping the primary one or making the first table primary
+    fn select_all(&mut self) {
+        self.selection = self.erd_graph.graph().node_indices().collect();
+        if self.selected_table.is_none() {
+            self.selected_table = self.selected_nodes().first().copied();
+        }
+    }
+    
+    /// Selected tables in graph order
+    fn selected_nodes(&self) -> Vec<NodeIndex> {
+        self.erd_graph.graph().node_indices().filter(|idx| self.selection.contains(idx)).collect()
+    }
+    
+    /// Tables that move together when `node_idx` is dragged
+    fn drag_group(&self, node_idx: NodeIndex) -> Vec<NodeIndex> {
+        if self.selection.len() > 1 && self.selection.contains(&node_idx) {
+            self.selected_nodes()
+        } else {
+            vec![node_idx]
+        }
+    }
+    
+    fn table_move_snapshot(&self, node: NodeIndex) -> Option<EditCommand> {
+        self.layout_engine.get_node_layout(node).map(|layout| {
+            let pos = (layout.position.x, layout.position.y);
+            EditCommand::MoveTable { node, from: pos, to: pos }
+        })
+    }
+    
+    /// Move the selected tables to positions computed by an arrange helper, as one undoable edit
+    fn arrange_selection(&mut self, arrange: fn(&[(NodeIndex, Rect)]) -> Vec<utils::TablePlacement>) {
+        let tables: Vec<(NodeIndex, Rect)> = self.selected_nodes().into_iter()
+            .filter_map(|idx| utils::table_bounds(&self.layout_engine, idx).map(|rect| (idx, rect)))
+            .collect();
+        
+        let selected: Vec<NodeIndex> = tables.iter().map(|(idx, _)| *idx).collect();
+        
+        let edit = EditCommand::Batch(arrange(&tables).into_iter().filter_map(|(node, to)| {
+            let rect = tables.iter().find(|(idx, _)| *idx == node).map(|(_, rect)| *rect)?;
+            // Slide off any unselected table the new position lands on
+            let to = utils::resolve_collision(&self.erd_graph, &self.layout_engine, to.0, to.1, rect.width(), rect.height(), &selected);
+            Some(EditCommand::MoveTable { node, from: (rect.min.x, rect.min.y), to })
+        }).collect());
+        
+        self.apply_edit(&edit);
+        self.history.push(edit);
+    }
+    
+    /// Snapshot the drag target's current position as a not-yet-moved edit
+    fn begin_edit(&self, target: &DragTarget) -> Option<EditCommand> {
+        match target {
+            DragTarget::Table(node_idx) => {
+                let group = self.drag_group(*node_idx);
+                if group.len() > 1 {
+                    Some(EditCommand::Batch(group.into_iter().filter_map(|idx| self.table_move_snapshot(idx)).collect()))
+                } else {
+                    self.table_move_snapshot(*node_idx)
+                }
+            }
+            DragTarget::Label(idx) => {
+                let offset = self.label_offsets.get(*idx).copied().flatten();
+                Some(EditCommand::MoveLabel { edge: *idx, from: offset, to: offset })
+            }
+            DragTarget::Title => Some(EditCommand::MoveTitle { from: self.title_position, to: self.title_position }),
+            DragTarget::BoxSelect(_) | DragTarget::Connect(..) | DragTarget::None => None,
+        }
+    }
+    
+    /// Fill in the `to` side of an edit from the current state once the drag ends
+    fn finish_edit(&self, edit: EditCommand) -> EditCommand {
+        match edit {
+            EditCommand::MoveTable { node, from, to } => {
+                let to = self.layout_engine.get_node_layout(node)
+                    .map(|layout| (layout.position.x, layout.position.y))
+                    .unwrap_or(to);
+                EditCommand::MoveTable { node, from, to }
+            }
+            EditCommand::MoveLabel { edge, from, .. } => {
+                EditCommand::MoveLabel { edge, from, to: self.label_offsets.get(edge).copied().flatten() }
+            }
+            EditCommand::MoveTitle { from, .. } => EditCommand::MoveTitle { from, to: self.title_position },
+            EditCommand::Batch(edits) => EditCommand::Batch(edits.into_iter().map(|e| self.finish_edit(e)).collect()),
+            EditCommand::Schema { .. } => edit,
+        }
+    }
+    
+    /// Set the edited item to the command's `to` value
+    fn apply_edit(&mut self, edit: &EditCommand) {
+        match edit {
+            EditCommand::MoveTable { node, to, .. } => {
+                if let Some(layout) = self.layout_engine.get_node_layout_mut(*node) {
+                    layout.position.x = to.0;
+                    layout.position.y = to.1;
+                }
+                self.layout_engine.recompute_edge_routes(&self.erd_graph);
+            }
+            EditCommand::MoveLabel { edge, to, .. } => {
+                while self.label_offsets.len() <= *edge {
+                    self.label_offsets.push(None);
+                }
+                self.label_offsets[*edge] = *to;
+            }
+            EditCommand::MoveTitle { to, .. } => self.title_position = *to,
+            EditCommand::Batch(edits) => {
+                for edit in edits {
+                    self.apply_edit(edit);
+                }
+            }
+            EditCommand::Schema { to, .. } => self.restore(to),
+        }
+    }
+    
+    /// Current table positions keyed by table name
+    fn positions_by_name(&self) -> Vec<(String, (f32, f32))> {
+        let graph = self.erd_graph.graph();
+        graph.node_indices()
+            .filter_map(|idx| {
+                let layout = self.layout_engine.get_node_layout(idx)?;
+                Some((graph[idx].name.clone(), (layout.position.x, layout.position.y)))
+            })
+            .collect()
+    }
+    
+    fn snapshot(&self) -> Option<SchemaSnapshot> {
+        let doc = self.document.as_ref()?;
+        Some(SchemaSnapshot { schema: doc.schema.clone(), positions: self.positions_by_name() })
+    }
+    
+    /// Rebuild the graph from a schema snapshot, keeping tables, label offsets
+    /// and the selection attached to the same names
+    fn restore(&mut self, snapshot: &SchemaSnapshot) {
+        let erd_graph = match ErdGraph::from_schema(&snapshot.schema) {
+            Ok(graph) => graph,
+            Err(e) => {
+                self.status_message = Some(format!("❌ {}", e));
+                return;
+            }
+        };
+        
+        let label_key = |route: &super::layout::EdgeRoute| (route.from_table.clone(), route.to_table.clone(), route.label.clone());
+        let old_offsets: HashMap<_, _> = self.layout_engine.get_edge_routes().iter()
+            .zip(self.label_offsets.iter())
+            .filter_map(|(route, offset)| offset.map(|o| (label_key(route), o)))
+            .collect();
+        let selected: HashSet<String> = self.selection.iter().map(|idx| self.erd_graph.graph()[*idx].name.clone()).collect();
+        let primary = self.selected_table.map(|idx| self.erd_graph.graph()[idx].name.clone());
+        
+        let positions: HashMap<String, (f32, f32)> = snapshot.positions.iter().cloned().collect();
+        self.layout_engine.layout_with_positions(&erd_graph, &positions);
+        self.erd_graph = erd_graph;
+        
+        self.label_offsets = self.layout_engine.get_edge_routes().iter()
+            .map(|route| old_offsets.get(&label_key(route)).copied())
+            .collect();
+        self.cached_label_positions = vec![None; self.label_offsets.len()];
+        
+        let graph = self.erd_graph.graph();
+        self.selection = graph.node_indices().filter(|idx| selected.contains(&graph[*idx].name)).collect();
+        self.selected_table = primary.and_then(|name| self.erd_graph.node_index(&name));
+        
+        self.title = snapshot.schema.title.clone().unwrap_or_else(|| "Untitled Schema".to_string());
+        if let Some(doc) = self.document.as_mut() {
+            doc.schema = snapshot.schema.clone();
+        }
+        self.search_matches = self.erd_graph.search(&self.search_query);
+        self.search_index = 0;
+        self.pending_edit = None;
+        self.inspector.reload();
+    }
+    
+    /// Apply an edit from the schema editor as one undoable step
+    fn apply_schema_edit(&mut self, edit: SchemaEdit, view_center: (f32, f32)) {
+        let Some(from) = self.snapshot() else { return };
+        let mut to = from.clone();
+        if let Err(e) = edit.apply(&mut to.schema) {
+            self.status_message = Some(format!("❌ {}", e));
+            self.inspector.reload();
+            return;
+        }
+        
+        match &edit {
+            SchemaEdit::RenameTable { from: old, to: new } => {
+                for (name, _) in to.positions.iter_mut().filter(|(name, _)| name == old) {
+                    *name = new.clone();
+                }
+            }
+            SchemaEdit::AddTable { name } => to.positions.push((name.clone(), view_center)),
+            _ => {}
+        }
+        
+        self.restore(&to);
+        if let SchemaEdit::RenameTable { to: name, .. } | SchemaEdit::AddTable { name } = &edit {
+            self.select_only(self.erd_graph.node_index(name));
+        }
+        self.status_message = None;
+        self.history.push(EditCommand::Schema { from: Box::new(from), to: Box::new(to) });
+    }
+    
+    fn save_document(&mut self) {
+        if let Some(doc) = self.document.as_mut() {
+            self.status_message = Some(match doc.save() {
+                Ok(()) => format!("✅ Saved {}", doc.path.display()),
+                Err(e) => format!("❌ Failed to save: {}", e),
+            });
+        }
+    }
+    
+    fn is_dirty(&self) -> bool {
+        self.document.as_ref().is_some_and(|doc| doc.is_dirty())
+    }
+    
+    /// Table and column index of the relationship handle under a screen position
+    fn handle_at(&self, pos: Pos2) -> Option<(NodeIndex, usize)> {
+        let radius = 7.0;
+        self.erd_graph.graph().node_indices().find_map(|node_idx| {
+            let layout = self.layout_engine.get_node_layout(node_idx)?;
+            (0..self.erd_graph.graph()[node_idx].columns.len()).find_map(|col_idx| {
+                let (x, y) = interaction::column_anchor(layout, col_idx);
+                let handle = utils::transform_point(x, y, self.zoom, self.pan_offset);
+                ((handle - pos).length() <= radius).then_some((node_idx, col_idx))
+            })
+        })
+    }
+    
+    /// Finish a handle drag: link the source column to the column under the pointer
+    fn finish_connection(&mut self, from: (NodeIndex, usize), pos: Pos2, view_center: (f32, f32)) {
+        let (world_x, world_y) = utils::screen_to_world(pos, self.zoom, self.pan_offset);
+        let graph = self.erd_graph.graph();
+        let target = graph.node_indices().filter(|idx| *idx != from.0).find_map(|node_idx| {
+            let layout = self.layout_engine.get_node_layout(node_idx)?;
+            interaction::column_at(layout, graph[node_idx].columns.len(), world_x, world_y).map(|col| (node_idx, col))
+        });
+        let (Some((to_node, to_col)), Some(doc)) = (target, self.document.as_ref()) else { return };
+        
+        let from_table = &graph[from.0].name;
+        let to_table = &graph[to_node].name;
+        let column = |table: &str, idx: usize| doc.schema.tables.iter().find(|t| t.name == table).and_then(|t| t.columns.get(idx));
+        let (Some(from_column), Some(to_column)) = (column(from_table, from.1), column(to_table, to_col)) else { return };
+        
+        let edit = SchemaEdit::AddRelationship {
+            from_table: from_table.clone(),
+            from_field: from_column.name.clone(),
+            to_table: to_table.clone(),
+            to_field: to_column.name.clone(),
+            relationship_type: editor::infer_relationship_type(from_column, to_column),
+        };
+        self.apply_schema_edit(edit, view_center);
+    }
+    
+    fn undo(&mut self) {
+        if let Some(edit) = self.history.undo() {
+            self.apply_edit(&edit);
+        }
+    }
+    
+    fn redo(&mut self) {
+        if let Some(edit) = self.history.redo() {
+            self.apply_edit(&edit);
+        }
+    }
+    
+    /// Re-run the search and jump to the first match
+    fn update_search(&mut self) {
+        self.search_matches = self.erd_graph.search(&self.search_query);
+        self.search_index = 0;
+        self.jump_to_match();
+    }
+    
+    /// Select the current match's table and queue the view to centre on it
+    fn jump_to_match(&mut self) {
+        if let Some(node) = self.search_matches.get(self.search_index).map(|m| m.node) {
+            self.select_only(Some(node));
+            self.pending_jump = Some(node);
+        }
+    }
+    
+    fn close_search(&mut self) {
+        self.search_open = false;
+        self.search_query.clear();
+        self.search_matches.clear();
+        self.search_index = 0;
+    }
+    
+    /// Tables to keep at full strength: the focus neighbourhood, else the search hits
+    fn emphasized_tables(&self) -> Option<HashSet<NodeIndex>> {
+        if self.focus_mode {
+            if let Some(selected) = self.selected_table {
+                return Some(self.erd_graph.neighborhood(selected, self.focus_hops));
+            }
+        }
+        if self.search_open && !self.search_query.trim().is_empty() {
+            return Some(self.search_matches.iter().map(|m| m.node).collect());
+        }
+        None
+    }
+    
+    /// One-line description of the current search match for the search bar
+    fn search_status(&self) -> String {
+        match self.search_matches.get(self.search_index) {
+            Some(m) => {
+                let table = &self.erd_graph.graph()[m.node];
+                let name = match m.column {
+                    Some(col) => format!("{}.{}", table.name, table.columns[col].name),
+                    None => table.name.clone(),
+                };
+                format!("{} ({} of {})", name, self.search_index + 1, self.search_matches.len())
+            }
+            None if self.search_query.trim().is_empty() => String::new(),
+            None => "No matches".to_string(),
         }
     }
     
@@ -104,6 +465,9 @@ impl ErdCanvas {
             layout_engine: &self.layout_engine,
             selected_table: self.selected_table,
             label_offsets: &self.label_offsets,
+            theme: &self.theme,
+            table_link_prefix: None,
+            annotate: false,
         };
         
         svg::generate_svg(&svg_ctx, &self.title, self.title_position)
@@ -112,19 +476,24 @@ impl ErdCanvas {
     fn draw_edge_labels(&mut self, ui: &mut egui::Ui) {
         // Clone the routes to avoid borrowing issues
         let all_routes = self.layout_engine.get_edge_routes().to_vec();
+        let emphasized = self.emphasized_tables();
         
         for (idx, edge_route) in all_routes.iter().enumerate() {
             if edge_route.points.len() < 2 {
                 continue;
             }
             
+            let dimmed = emphasized.as_ref().is_some_and(|set| {
+                utils::edge_outside_set(&self.erd_graph, set, &edge_route.from_table, &edge_route.to_table)
+            });
+            
             // Draw label at the middle of the edge with relationship type and table names
-            self.draw_edge_label(ui, edge_route, &all_routes, idx);
+            self.draw_edge_label(ui, edge_route, &all_routes, idx, dimmed);
         }
     }
     
     /// Draw label at the middle of the edge with a pointer line and relationship type
-    fn draw_edge_label(&mut self, ui: &mut egui::Ui, edge_route: &super::layout::EdgeRoute, all_edge_routes: &[super::layout::EdgeRoute], edge_idx: usize) {
+    fn draw_edge_label(&mut self, ui: &mut egui::Ui, edge_route: &super::layout::EdgeRoute, all_edge_routes: &[super::layout::EdgeRoute], edge_idx: usize, dimmed: bool) {
         let points = &edge_route.points;
         if points.len() < 2 {
             return;
@@ -207,12 +576,12 @@ impl ErdCanvas {
             let rel_galley = ui.painter().layout_no_wrap(
                 rel_text_with_brackets.clone(),
                 rel_font_id.clone(),
-                Color32::from_rgb(180, 180, 180),
+                self.theme.label_type_text.to_color32(),
             );
             let label_galley = ui.painter().layout_no_wrap(
                 full_label.clone(),
                 font_id.clone(),
-                Color32::WHITE,
+                self.theme.label_text.to_color32(),
             );
             
             // Calculate total size with spacing
@@ -367,13 +736,13 @@ impl ErdCanvas {
             ui.painter().rect_filled(
                 text_rect,
                 3.0 * self.zoom,
-                Color32::from_rgba_premultiplied(50, 50, 50, 230),
+                self.theme.label_fill.to_color32(),
             );
             
             ui.painter().rect_stroke(
                 text_rect,
                 3.0 * self.zoom,
-                Stroke::new(1.0 * self.zoom, Color32::from_rgb(100, 100, 100)),
+                Stroke::new(1.0 * self.zoom, self.theme.label_border.to_color32()),
             );
             
             // Draw pointer line from box edge to line (at any angle)
@@ -396,7 +765,7 @@ impl ErdCanvas {
             
             ui.painter().line_segment(
                 [box_edge_point, line_pos],
-                Stroke::new(0.8 * self.zoom, Color32::from_rgb(120, 120, 120)),
+                Stroke::new(0.8 * self.zoom, self.theme.label_pointer.to_color32()),
             );
             
             // Draw text
@@ -407,7 +776,7 @@ impl ErdCanvas {
                     text_rect.center().y - rel_galley.size().y / 2.0,
                 ),
                 rel_galley,
-                Color32::from_rgb(180, 180, 180),
+                self.theme.label_type_text.to_color32(),
             );
             
             ui.painter().galley(
@@ -416,14 +785,58 @@ impl ErdCanvas {
                     text_rect.center().y - label_galley.size().y / 2.0,
                 ),
                 label_galley,
-                Color32::WHITE,
+                self.theme.label_text.to_color32(),
             );
+            
+            if dimmed {
+                drawing::dim_rect(&self.theme, ui, text_rect.expand(2.0 * self.zoom), 3.0 * self.zoom);
+            }
         }
     }
 }
 
+#[cfg(feature = "viewer")]
 impl eframe::App for ErdCanvas {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Ctrl+F opens the search bar, Escape closes it
+        if ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::F)) {
+            self.search_open = true;
+            self.search_focus_requested = true;
+        }
+        if self.search_open && ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.close_search();
+        }
+        
+        // Ctrl+Z undoes the last move, Ctrl+Shift+Z (or Ctrl+Y) redoes it.
+        // Text fields keep their own undo, so skip while typing.
+        if !ctx.wants_keyboard_input() && self.drag_target == DragTarget::None {
+            let (undo, redo) = ctx.input_mut(|i| {
+                let redo = i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z)
+                    || i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y);
+                let undo = i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z);
+                (undo, redo)
+            });
+            if redo {
+                self.redo();
+            } else if undo {
+                self.undo();
+            }
+        }
+        
+        // Ctrl+S saves the edited schema, Ctrl+E toggles the schema editor
+        if self.document.is_some() {
+            let (save, toggle_editor) = ctx.input_mut(|i| {
+                (i.consume_key(egui::Modifiers::COMMAND, egui::Key::S), i.consume_key(egui::Modifiers::COMMAND, egui::Key::E))
+            });
+            if save {
+                self.save_document();
+            }
+            if toggle_editor {
+                self.editor_open = !self.editor_open;
+            }
+        }
+        let view_center = utils::screen_to_world(ctx.screen_rect().center(), self.zoom, self.pan_offset);
+        
         // Add menu bar at the top
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
@@ -433,10 +846,121 @@ impl eframe::App for ErdCanvas {
                         ui.close_menu();
                     }
                 });
+                ui.menu_button("Edit", |ui| {
+                    if ui.add_enabled(self.history.can_undo(), egui::Button::new("Undo  (Ctrl+Z)")).clicked() {
+                        self.undo();
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(self.history.can_redo(), egui::Button::new("Redo  (Ctrl+Shift+Z)")).clicked() {
+                        self.redo();
+                        ui.close_menu();
+                    }
+                    if self.document.is_some() {
+                        ui.separator();
+                        ui.checkbox(&mut self.editor_open, "Schema editor  (Ctrl+E)");
+                        if ui.add_enabled(self.is_dirty(), egui::Button::new("Save  (Ctrl+S)")).clicked() {
+                            self.save_document();
+                            ui.close_menu();
+                        }
+                    }
+                });
+                ui.menu_button("Arrange", |ui| {
+                    if ui.button("Select all  (Ctrl+A)").clicked() {
+                        self.select_all();
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.add_enabled(self.selection.len() >= 2, egui::Button::new("Align left")).clicked() {
+                        self.arrange_selection(utils::align_left);
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(self.selection.len() >= 3, egui::Button::new("Distribute horizontally")).clicked() {
+                        self.arrange_selection(utils::distribute_horizontally);
+                        ui.close_menu();
+                    }
+                });
+                ui.menu_button("View", |ui| {
+                    ui.menu_button("Theme", |ui| {
+                        let mut chosen = None;
+                        for theme in &self.available_themes {
+                            if ui.radio(self.theme.name == theme.name, &theme.name).clicked() {
+                                chosen = Some(theme.clone());
+                            }
+                        }
+                        if let Some(theme) = chosen {
+                            self.theme = theme;
+                            ui.close_menu();
+                        }
+                    });
+                    ui.separator();
+                    if ui.button("Search…  (Ctrl+F)").clicked() {
+                        self.search_open = true;
+                        self.search_focus_requested = true;
+                        ui.close_menu();
+                    }
+                    ui.checkbox(&mut self.focus_mode, "Focus on selected table");
+                    ui.add(egui::Slider::new(&mut self.focus_hops, 1..=5).text("Focus depth (hops)"));
+                });
             });
         });
         
-        egui::CentralPanel::default().show(ctx, |ui| {
+        if self.search_open {
+            egui::TopBottomPanel::top("search_bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("🔍");
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.search_query)
+                            .hint_text("Search tables and columns")
+                            .desired_width(250.0),
+                    );
+                    if self.search_focus_requested {
+                        response.request_focus();
+                        self.search_focus_requested = false;
+                    }
+                    if response.changed() {
+                        self.update_search();
+                    }
+                    
+                    // Enter steps to the next match, Shift+Enter to the previous one
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        if !self.search_matches.is_empty() {
+                            let count = self.search_matches.len();
+                            self.search_index = if ui.input(|i| i.modifiers.shift) {
+                                (self.search_index + count - 1) % count
+                            } else {
+                                (self.search_index + 1) % count
+                            };
+                            self.jump_to_match();
+                        }
+                        response.request_focus();
+                    }
+                    
+                    ui.label(self.search_status());
+                    if ui.button("✖").clicked() {
+                        self.close_search();
+                    }
+                });
+            });
+        }
+        
+        if self.editor_open {
+            if let Some(doc) = &self.document {
+                let selected = self.selected_table.map(|idx| self.erd_graph.graph()[idx].name.clone());
+                let edits = egui::SidePanel::right("schema_editor")
+                    .default_width(420.0)
+                    .show(ctx, |ui| {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            inspector::show(ui, &mut self.inspector, &doc.schema, selected.as_deref())
+                        }).inner
+                    }).inner;
+                for edit in edits {
+                    self.apply_schema_edit(edit, view_center);
+                }
+            }
+        }
+        
+        let canvas_frame = egui::Frame::central_panel(&ctx.style()).fill(self.theme.background.to_color32());
+        egui::CentralPanel::default().frame(canvas_frame).show(ctx, |ui| {
             // Set initial pan and zoom to center the graph
             if !self.initial_pan_set {
                 let viewport_size = ui.available_size();
@@ -446,6 +970,16 @@ impl eframe::App for ErdCanvas {
                 self.initial_pan_set = true;
             }
             
+            // Centre on the table picked by the search bar
+            if let Some(node_idx) = self.pending_jump.take() {
+                if let Some(bounds) = utils::table_bounds(&self.layout_engine, node_idx) {
+                    utils::center_graph(&mut self.zoom, &mut self.pan_offset, ui.available_size(), bounds);
+                }
+            }
+            
+            // Keyboard shortcuts shouldn't fire while typing in the search box
+            let typing = ctx.wants_keyboard_input();
+            
             // Get pointer information
             let pointer_pos = ui.input(|i| i.pointer.hover_pos());
             let pointer_down = ui.input(|i| i.pointer.primary_down());
@@ -453,22 +987,33 @@ impl eframe::App for ErdCanvas {
             let pointer_released = ui.input(|i| i.pointer.primary_released());
             
             // Handle dragging
+            // Presses over the side panels belong to them, not the canvas
             if pointer_pressed {
-                if let Some(pos) = pointer_pos {
+                if let Some(pos) = pointer_pos.filter(|pos| ui.max_rect().contains(*pos)) {
                     let mut found_target = false;
+                    let shift = ui.input(|i| i.modifiers.shift);
+                    
+                    // In the schema editor, column handles start a new relationship
+                    if self.editor_open && self.document.is_some() {
+                        if let Some((node_idx, col_idx)) = self.handle_at(pos) {
+                            self.drag_target = DragTarget::Connect(node_idx, col_idx);
+                            found_target = true;
+                        }
+                    }
                     
                     // First check if clicking on title (highest priority)
                     let title_screen = utils::transform_point(self.title_position.0, self.title_position.1, self.zoom, self.pan_offset);
                     
-                    if interaction::check_title_click(pos, title_screen, &self.title, self.zoom) {
+                    if !found_target && interaction::check_title_click(pos, title_screen, &self.title, self.zoom) {
                         self.drag_target = DragTarget::Title;
                         self.drag_offset = (pos - title_screen).to_pos2();
-                        self.selected_table = None;
+                        self.select_only(None);
                         found_target = true;
                     }
                     
                     // Second pass: Check ALL labels with their actual rendered size
                     if !found_target {
+                    let mut clicked_label = None;
                     for (idx, edge_route) in self.layout_engine.get_edge_routes().iter().enumerate() {
                         let label_world_pos = self.get_label_position_with_offset(edge_route, idx);
                         
@@ -484,36 +1029,58 @@ impl eframe::App for ErdCanvas {
                             );
                             
                             if interaction::check_label_click(pos, label_screen, &label_text, self.zoom) {
-                                self.drag_target = DragTarget::Label(idx);
-                                self.drag_offset = (pos - label_screen).to_pos2();
-                                self.selected_table = None; // Deselect table when clicking label
-                                found_target = true;
+                                clicked_label = Some((idx, label_screen));
                                 break;
                             }
                         }
                     }
+                    if let Some((idx, label_screen)) = clicked_label {
+                        self.drag_target = DragTarget::Label(idx);
+                        self.drag_offset = (pos - label_screen).to_pos2();
+                        self.select_only(None); // Deselect tables when clicking label
+                        found_target = true;
+                    }
                     }
                     
                     // Third pass: Check tables if no label was clicked
                     if !found_target {
-                        for node_idx in self.erd_graph.graph().node_indices() {
-                            if let Some(layout) = self.layout_engine.get_node_layout(node_idx) {
-                                if interaction::check_table_click(pos, layout, self.zoom, self.pan_offset) {
-                                    self.drag_target = DragTarget::Table(node_idx);
-                                    let screen_pos = utils::transform_point(layout.position.x, layout.position.y, self.zoom, self.pan_offset);
-                                    self.drag_offset = (pos - screen_pos).to_pos2();
-                                    self.selected_table = Some(node_idx); // Select the clicked table
-                                    found_target = true;
-                                    break;
+                        let clicked_table = self.erd_graph.graph().node_indices().find_map(|node_idx| {
+                            let layout = self.layout_engine.get_node_layout(node_idx)?;
+                            interaction::check_table_click(pos, layout, self.zoom, self.pan_offset).then(|| {
+                                (node_idx, utils::transform_point(layout.position.x, layout.position.y, self.zoom, self.pan_offset))
+                            })
+                        });
+                        
+                        if let Some((node_idx, screen_pos)) = clicked_table {
+                            found_target = true;
+                            if shift && self.selection.contains(&node_idx) {
+                                // Shift-click on a selected table removes it from the selection
+                                self.selection.remove(&node_idx);
+                                if self.selected_table == Some(node_idx) {
+                                    self.selected_table = self.selected_nodes().first().copied();
                                 }
+                            } else {
+                                if shift {
+                                    self.selection.insert(node_idx);
+                                } else if !self.selection.contains(&node_idx) {
+                                    self.select_only(Some(node_idx));
+                                }
+                                self.selected_table = Some(node_idx);
+                                self.drag_target = DragTarget::Table(node_idx);
+                                self.drag_offset = (pos - screen_pos).to_pos2();
                             }
                         }
                     }
                     
-                    // If clicking on empty space, deselect
+                    // Clicking on empty space deselects (unless shift is held) and starts a box selection
                     if !found_target {
-                        self.selected_table = None;
+                        if !shift {
+                            self.select_only(None);
+                        }
+                        self.drag_target = DragTarget::BoxSelect(pos);
                     }
+                    
+                    self.pending_edit = self.begin_edit(&self.drag_target);
                 }
             }
             
@@ -529,26 +1096,32 @@ impl eframe::App for ErdCanvas {
                             self.title_position = (world_x, world_y);
                         }
                         DragTarget::Table(node_idx) => {
-                            // Get table dimensions for collision detection
+                            // The dragged table carries the rest of the selection with it
+                            let group = self.drag_group(*node_idx);
+                            
                             if let Some(layout) = self.layout_engine.get_node_layout(*node_idx) {
-                                let table_width = layout.size.width;
-                                let table_height = layout.size.height;
+                                let dx = world_x - layout.position.x;
+                                let dy = world_y - layout.position.y;
                                 
-                                // Resolve collisions with other tables
-                                let (final_x, final_y) = utils::resolve_collision(
+                                // Every table in the group moves by the same offset, so check
+                                // each one against the tables left behind
+                                let rects: Vec<Rect> = group.iter()
+                                    .filter_map(|idx| utils::table_bounds(&self.layout_engine, *idx))
+                                    .collect();
+                                let (dx, dy) = utils::resolve_group_collision(
                                     &self.erd_graph,
                                     &self.layout_engine,
-                                    world_x,
-                                    world_y,
-                                    table_width,
-                                    table_height,
-                                    *node_idx,
+                                    &rects,
+                                    (dx, dy),
+                                    &group,
                                 );
                                 
-                                // Update table position with collision resolution
-                                if let Some(layout) = self.layout_engine.get_node_layout_mut(*node_idx) {
-                                    layout.position.x = final_x;
-                                    layout.position.y = final_y;
+                                // Update table positions with collision resolution
+                                for idx in &group {
+                                    if let Some(layout) = self.layout_engine.get_node_layout_mut(*idx) {
+                                        layout.position.x += dx;
+                                        layout.position.y += dy;
+                                    }
                                 }
                             }
                             // Recalculate edge routes
@@ -570,18 +1143,53 @@ impl eframe::App for ErdCanvas {
                                 }
                             }
                         }
-                        DragTarget::None => {}
+                        DragTarget::BoxSelect(_) | DragTarget::Connect(..) | DragTarget::None => {}
                     }
                 }
             }
             
             // Release drag
             if pointer_released {
+                // Finish a box selection: add every table the box touches
+                if let (DragTarget::BoxSelect(start), Some(pos)) = (&self.drag_target, pointer_pos) {
+                    let screen_rect = Rect::from_two_pos(*start, pos);
+                    if screen_rect.width() > 3.0 || screen_rect.height() > 3.0 {
+                        let (min_x, min_y) = utils::screen_to_world(screen_rect.min, self.zoom, self.pan_offset);
+                        let (max_x, max_y) = utils::screen_to_world(screen_rect.max, self.zoom, self.pan_offset);
+                        let world_rect = Rect::from_min_max(Pos2::new(min_x, min_y), Pos2::new(max_x, max_y));
+                        
+                        for node_idx in self.erd_graph.graph().node_indices().collect::<Vec<_>>() {
+                            if utils::table_bounds(&self.layout_engine, node_idx).is_some_and(|r| r.intersects(world_rect)) {
+                                self.selection.insert(node_idx);
+                                self.selected_table.get_or_insert(node_idx);
+                            }
+                        }
+                    }
+                }
+                
+                if let (DragTarget::Connect(node_idx, col_idx), Some(pos)) = (self.drag_target.clone(), pointer_pos) {
+                    self.finish_connection((node_idx, col_idx), pos, view_center);
+                }
+                
+                if let Some(edit) = self.pending_edit.take() {
+                    let edit = self.finish_edit(edit);
+                    self.history.push(edit);
+                }
                 self.drag_target = DragTarget::None;
             }
             
+            // Ctrl+A selects every table
+            if !typing && ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::A)) {
+                self.select_all();
+            }
+            
+            // Toggle focus mode with F
+            if !typing && ui.input(|i| i.key_pressed(egui::Key::F) && !i.modifiers.command) {
+                self.focus_mode = !self.focus_mode;
+            }
+            
             // Handle keyboard zoom (+/- keys)
-            let zoom_delta = ui.input(|i| {
+            let zoom_delta = if typing { 0.0 } else { ui.input(|i| {
                 let mut delta = 0.0;
                 if i.key_pressed(egui::Key::Plus) || i.key_pressed(egui::Key::Equals) {
                     delta += 0.1;
@@ -590,7 +1198,7 @@ impl eframe::App for ErdCanvas {
                     delta -= 0.1;
                 }
                 delta
-            });
+            }) };
             if zoom_delta != 0.0 {
                 self.zoom = (self.zoom + zoom_delta).clamp(0.1, 5.0);
             }
@@ -603,7 +1211,7 @@ impl eframe::App for ErdCanvas {
             }
             
             // Handle arrow key navigation (only when not dragging)
-            if self.drag_target == DragTarget::None {
+            if self.drag_target == DragTarget::None && !typing {
                 let arrow_pan = ui.input(|i| {
                     let mut pan = egui::Vec2::ZERO;
                     let step = 20.0;
@@ -624,6 +1232,8 @@ impl eframe::App for ErdCanvas {
                 self.pan_offset += arrow_pan;
             }
             
+            let emphasized = self.emphasized_tables();
+            
             // Draw edges first (so they appear behind tables)
             {
                 let ctx = drawing::DrawingContext {
@@ -631,9 +1241,13 @@ impl eframe::App for ErdCanvas {
                     layout_engine: &self.layout_engine,
                     zoom: self.zoom,
                     pan_offset: self.pan_offset,
-                    selected_table: self.selected_table,
+                    selection: &self.selection,
+                    theme: &self.theme,
                     label_offsets: &self.label_offsets,
                     cached_label_positions: &self.cached_label_positions,
+                    emphasized: emphasized.as_ref(),
+                    search_matches: &self.search_matches,
+                    editing: self.editor_open && self.document.is_some(),
                 };
                 drawing::draw_edges(&ctx, ui);
             }
@@ -645,13 +1259,28 @@ impl eframe::App for ErdCanvas {
                     layout_engine: &self.layout_engine,
                     zoom: self.zoom,
                     pan_offset: self.pan_offset,
-                    selected_table: self.selected_table,
+                    selection: &self.selection,
+                    theme: &self.theme,
                     label_offsets: &self.label_offsets,
                     cached_label_positions: &self.cached_label_positions,
+                    emphasized: emphasized.as_ref(),
+                    search_matches: &self.search_matches,
+                    editing: self.editor_open && self.document.is_some(),
                 };
                 for node_idx in self.erd_graph.graph().node_indices() {
                     drawing::draw_table(&ctx, ui, node_idx);
                 }
+                
+                if let (DragTarget::BoxSelect(start), Some(pos)) = (&self.drag_target, pointer_pos) {
+                    drawing::draw_selection_box(&ctx, ui, Rect::from_two_pos(*start, pos));
+                }
+                
+                if let (DragTarget::Connect(node_idx, col_idx), Some(pos)) = (&self.drag_target, pointer_pos) {
+                    if let Some(layout) = self.layout_engine.get_node_layout(*node_idx) {
+                        let (x, y) = interaction::column_anchor(layout, *col_idx);
+                        drawing::draw_connection_preview(&ctx, ui, ctx.transform_point(x, y), pos);
+                    }
+                }
             }
             
             // Draw labels on top of everything
@@ -664,18 +1293,28 @@ impl eframe::App for ErdCanvas {
                     layout_engine: &self.layout_engine,
                     zoom: self.zoom,
                     pan_offset: self.pan_offset,
-                    selected_table: self.selected_table,
+                    selection: &self.selection,
+                    theme: &self.theme,
                     label_offsets: &self.label_offsets,
                     cached_label_positions: &self.cached_label_positions,
+                    emphasized: emphasized.as_ref(),
+                    search_matches: &self.search_matches,
+                    editing: self.editor_open && self.document.is_some(),
                 };
                 drawing::draw_title(&ctx, ui, &self.title, self.title_position, &self.drag_target);
             }
             
             // Show controls
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+                if let Some(doc) = &self.document {
+                    let saved = if doc.is_dirty() { "● Unsaved changes" } else { "Saved" };
+                    let status = self.status_message.as_deref().unwrap_or("");
+                    ui.label(format!("{} | {} {}", doc.path.display(), saved, status));
+                }
                 ui.label(format!(
-                    "Zoom: {:.1}x | Drag tables/labels to move | Scroll/+- to zoom | Arrow keys to pan", 
-                    self.zoom
+                    "Zoom: {:.1}x | Drag tables/labels to move | Scroll/+- to zoom | Arrow keys to pan | Ctrl+F search | F focus{}", 
+                    self.zoom,
+                    if self.focus_mode { format!(" ({} hops)", self.focus_hops) } else { String::new() }
                 ));
             });
         });
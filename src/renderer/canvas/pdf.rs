@@ -0,0 +1,351 @@
+use crate::pdf::{Align, Font, Page, Paint};
+use crate::renderer::graph::{DiffStatus, ErdGraph, RelationType};
+use crate::renderer::layout::{EdgeRoute, LayoutEngine};
+use crate::renderer::theme::{Theme, ThemeColor};
+
+/// Paper sizes in points, portrait
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Paper {
+    A4,
+    A3,
+    Letter,
+    Legal,
+    Tabloid,
+}
+
+impl Paper {
+    pub const NAMES: &'static [&'static str] = &["a4", "a3", "letter", "legal", "tabloid"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "a4" => Some(Paper::A4),
+            "a3" => Some(Paper::A3),
+            "letter" => Some(Paper::Letter),
+            "legal" => Some(Paper::Legal),
+            "tabloid" => Some(Paper::Tabloid),
+            _ => None,
+        }
+    }
+
+    fn size(self) -> (f32, f32) {
+        match self {
+            Paper::A4 => (595.28, 841.89),
+            Paper::A3 => (841.89, 1190.55),
+            Paper::Letter => (612.0, 792.0),
+            Paper::Legal => (612.0, 1008.0),
+            Paper::Tabloid => (792.0, 1224.0),
+        }
+    }
+}
+
+/// Whether the diagram is shrunk onto one page or printed across several
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageLayout {
+    Fit,
+    /// Tiles at a zoom where 1.0 prints a canvas pixel at 96 dpi
+    Tile { zoom: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfOptions {
+    pub paper: Paper,
+    pub landscape: bool,
+    pub layout: PageLayout,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        PdfOptions { paper: Paper::A4, landscape: true, layout: PageLayout::Fit }
+    }
+}
+
+pub struct PdfContext<'a> {
+    pub erd_graph: &'a ErdGraph,
+    pub layout_engine: &'a LayoutEngine,
+    pub theme: &'a Theme,
+    pub font: &'a Font<'a>,
+}
+
+const MARGIN: f32 = 36.0;
+/// Points per canvas pixel at 96 dpi
+const PIXEL: f32 = 0.75;
+
+/// Theme colour as RGB, with any transparency blended onto the background
+pub(super) fn rgb(color: ThemeColor, background: ThemeColor) -> (u8, u8, u8) {
+    let alpha = color.a as f32 / 255.0;
+    let blend = |c: u8, b: u8| (c as f32 * alpha + b as f32 * (1.0 - alpha)).round() as u8;
+    (blend(color.r, background.r), blend(color.g, background.g), blend(color.b, background.b))
+}
+
+/// Label of a relationship: its box and both text runs. Exports have no
+/// dragged labels, so each box sits on the middle of its line.
+struct Label {
+    bounds: (f32, f32, f32, f32),
+    rel_text: String,
+    field_label: String,
+    text_x: f32,
+    field_x: f32,
+    baseline: f32,
+}
+
+/// Laid out like the SVG labels, but measured with the embedded font
+fn label(font: &Font, route: &EdgeRoute) -> Option<Label> {
+    let (x, y) = super::svg::get_edge_middle_point(&route.points)?;
+    let rel_text = match route.relationship_type {
+        RelationType::OneToOne => "[1:1]",
+        RelationType::OneToMany => "[1:M]",
+        RelationType::ManyToOne => "[M:1]",
+        RelationType::ManyToMany => "[M:M]",
+    }.to_string();
+    let field_label = format!("{}.{}:{}.{}",
+        route.from_table,
+        route.label.split(':').next().unwrap_or(""),
+        route.to_table,
+        route.label.split(':').nth(1).unwrap_or("")
+    );
+    let spacing = 5.0;
+    let padding = 6.0;
+    let rel_width = font.text_width(&rel_text, 10.0);
+    let total = rel_width + spacing + font.text_width(&field_label, 11.0);
+    let (width, height) = (total + padding * 2.0, 20.0 + padding * 2.0);
+    Some(Label {
+        bounds: (x - width / 2.0, y - height / 2.0, width, height),
+        text_x: x - total / 2.0,
+        field_x: x - total / 2.0 + rel_width + spacing,
+        baseline: y + 4.0,
+        rel_text,
+        field_label,
+    })
+}
+
+/// Unit direction of the route at its start or end point
+fn end_direction(points: &[crate::renderer::layout::Point], at_start: bool) -> ((f32, f32), (f32, f32)) {
+    let (from, to) = if at_start { (&points[0], &points[1]) } else { (&points[points.len() - 2], &points[points.len() - 1]) };
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let len = (dx * dx + dy * dy).sqrt().max(1.0);
+    let pos = if at_start { (from.x, from.y) } else { (to.x, to.y) };
+    (pos, (dx / len, dy / len))
+}
+
+/// Crow's-foot or bar at one end of a relationship, as in the SVG export
+fn draw_marker(page: &mut Page, points: &[crate::renderer::layout::Point], at_start: bool, many: bool) {
+    let (pos, dir) = end_direction(points, at_start);
+    let perp = (-dir.1, dir.0);
+    if many {
+        let (size, back) = (12.0, 20.0);
+        let sign = if at_start { 1.0 } else { -1.0 };
+        let base = (pos.0 + dir.0 * back * sign, pos.1 + dir.1 * back * sign);
+        page.line(base.0, base.1, pos.0, pos.1);
+        page.line(base.0, base.1, pos.0 + perp.0 * size, pos.1 + perp.1 * size);
+        page.line(base.0, base.1, pos.0 - perp.0 * size, pos.1 - perp.1 * size);
+    } else {
+        let size = 10.0;
+        page.line(pos.0 + perp.0 * size, pos.1 + perp.1 * size, pos.0 - perp.0 * size, pos.1 - perp.1 * size);
+    }
+}
+
+/// Bounds of everything drawn, in canvas coordinates: (x, y, width, height)
+fn diagram_bounds(ctx: &PdfContext, title: &str, title_position: (f32, f32)) -> (f32, f32, f32, f32) {
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+    let mut include = |x: f32, y: f32, w: f32, h: f32| {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x + w);
+        max_y = max_y.max(y + h);
+    };
+    for node_idx in ctx.erd_graph.graph().node_indices() {
+        if let Some(layout) = ctx.layout_engine.get_node_layout(node_idx) {
+            include(layout.position.x, layout.position.y, layout.size.width, layout.size.height);
+        }
+    }
+    for route in ctx.layout_engine.get_edge_routes() {
+        for point in &route.points {
+            include(point.x, point.y, 0.0, 0.0);
+        }
+        if let Some(label) = label(ctx.font, route) {
+            let (x, y, w, h) = label.bounds;
+            include(x, y, w, h);
+        }
+    }
+    if !title.is_empty() {
+        let width = ctx.font.text_width(title, 32.0);
+        include(title_position.0 - width / 2.0, title_position.1 - 32.0, width, 40.0);
+    }
+    if min_x > max_x {
+        return (0.0, 0.0, 1.0, 1.0);
+    }
+    let padding = 20.0;
+    (min_x - padding, min_y - padding, max_x - min_x + padding * 2.0, max_y - min_y + padding * 2.0)
+}
+
+/// Draw the whole diagram in canvas coordinates
+fn draw_diagram(page: &mut Page, ctx: &PdfContext, title: &str, title_position: (f32, f32)) {
+    let theme = ctx.theme;
+    let background = theme.background;
+    let font = ctx.font;
+
+    page.stroke_color(rgb(theme.edge, background));
+    for route in ctx.layout_engine.get_edge_routes() {
+        if route.points.len() < 2 {
+            continue;
+        }
+        page.line_width(2.0);
+        let points: Vec<(f32, f32)> = route.points.iter().map(|p| (p.x, p.y)).collect();
+        page.polyline(&points);
+        page.line_width(2.5);
+        let (start_many, end_many) = match route.relationship_type {
+            RelationType::OneToOne => (false, false),
+            RelationType::OneToMany => (false, true),
+            RelationType::ManyToOne => (true, false),
+            RelationType::ManyToMany => (true, true),
+        };
+        draw_marker(page, &route.points, true, start_many);
+        draw_marker(page, &route.points, false, end_many);
+    }
+
+    for node_idx in ctx.erd_graph.graph().node_indices() {
+        let table = &ctx.erd_graph.graph()[node_idx];
+        let Some(layout) = ctx.layout_engine.get_node_layout(node_idx) else { continue };
+        let (x, y, w, h) = (layout.position.x, layout.position.y, layout.size.width, layout.size.height);
+
+        // Custom and compare-mode colours override the theme as in the SVG export
+        let ghost = table.status == DiffStatus::Removed;
+        let custom = super::utils::diff_color(table.status)
+            .or_else(|| table.color.as_deref().and_then(super::utils::parse_hex_rgb));
+        let border = custom.unwrap_or(rgb(theme.table_border, background));
+        let header = custom.unwrap_or(rgb(theme.header_fill, background));
+        let header_text = match custom {
+            Some((r, g, b)) if super::utils::is_light_color(r, g, b) => (0x2c, 0x3e, 0x50),
+            Some(_) => (255, 255, 255),
+            None => rgb(theme.header_text, background),
+        };
+
+        page.line_width(2.0);
+        page.dash(if ghost { &[6.0, 4.0] } else { &[] });
+        page.fill_color(rgb(theme.table_fill, background));
+        page.stroke_color(border);
+        page.rect(x, y, w, h, 8.0, Paint::FillStroke);
+        page.dash(&[]);
+        let header_height = 40.0;
+        page.fill_color(header);
+        page.rect(x, y, w, header_height, 8.0, Paint::Fill);
+
+        // Icons are only drawn when the font has glyphs for them
+        let name = match &table.icon {
+            Some(icon) if font.text_width(&super::utils::icon_glyph(icon), 14.0) > 0.0 => {
+                format!("{} {}", super::utils::icon_glyph(icon), table.name)
+            }
+            _ => table.name.clone(),
+        };
+        page.fill_color(header_text);
+        page.text(font, x + w / 2.0, y + header_height / 2.0 + 5.0, 14.0, Align::Center, &name);
+
+        let mut y_offset = header_height + 20.0;
+        for column in &table.columns {
+            if let Some((r, g, b)) = super::utils::diff_color(column.status).filter(|_| column.status != table.status) {
+                page.fill_color(rgb(ThemeColor::rgba(r, g, b, 64), theme.table_fill));
+                page.rect(x + 6.0, y + y_offset - 14.0, w - 12.0, 23.0, 4.0, Paint::Fill);
+                if column.status == DiffStatus::Removed {
+                    page.stroke_color((r, g, b));
+                    page.line_width(1.0);
+                    page.line(x + 12.0, y + y_offset - 2.5, x + w - 12.0, y + y_offset - 2.5);
+                }
+            }
+            page.fill_color(rgb(theme.column_text, background));
+            page.text(font, x + 15.0, y + y_offset, 11.0, Align::Left, &column.name);
+            let type_text = if column.attributes.is_empty() {
+                column.data_type.clone()
+            } else {
+                format!("{} [{}]", column.data_type, column.attributes.join(","))
+            };
+            page.fill_color(rgb(theme.type_text, background));
+            page.text(font, x + w - 15.0, y + y_offset, 9.0, Align::Right, &type_text);
+            y_offset += 25.0;
+        }
+    }
+
+    for route in ctx.layout_engine.get_edge_routes() {
+        let Some(label) = label(font, route) else { continue };
+        let (x, y, w, h) = label.bounds;
+        page.fill_color(rgb(theme.label_fill, background));
+        page.stroke_color(rgb(theme.label_border, background));
+        page.line_width(1.0);
+        page.rect(x, y, w, h, 3.0, Paint::FillStroke);
+        page.fill_color(rgb(theme.label_type_text, background));
+        page.text(font, label.text_x, label.baseline, 10.0, Align::Left, &label.rel_text);
+        page.fill_color(rgb(theme.label_text, background));
+        page.text(font, label.field_x, label.baseline, 11.0, Align::Left, &label.field_label);
+    }
+
+    page.fill_color(rgb(theme.heading_text, background));
+    page.text(font, title_position.0, title_position.1, 32.0, Align::Center, title);
+}
+
+/// Short lines in the margin marking where to trim a page
+fn crop_marks(page: &mut Page, x: f32, y: f32, width: f32, height: f32) {
+    let (gap, length) = (6.0, 18.0);
+    page.stroke_color((0, 0, 0));
+    page.line_width(0.5);
+    page.dash(&[]);
+    for (cx, cy, sx, sy) in [(x, y, -1.0, -1.0), (x + width, y, 1.0, -1.0), (x, y + height, -1.0, 1.0), (x + width, y + height, 1.0, 1.0)] {
+        page.line(cx + sx * gap, cy, cx + sx * (gap + length), cy);
+        page.line(cx, cy + sy * gap, cx, cy + sy * (gap + length));
+    }
+}
+
+/// Render the diagram onto one or more pages
+pub fn generate_pdf(ctx: &PdfContext, title: &str, title_position: (f32, f32), options: &PdfOptions) -> Vec<Page> {
+    let (short, long) = options.paper.size();
+    let (page_width, page_height) = if options.landscape { (long, short) } else { (short, long) };
+    let (avail_width, avail_height) = (page_width - MARGIN * 2.0, page_height - MARGIN * 2.0);
+    let (min_x, min_y, width, height) = diagram_bounds(ctx, title, title_position);
+    let background = rgb(ctx.theme.background, ctx.theme.background);
+
+    match options.layout {
+        PageLayout::Fit => {
+            // Shrink to the page, but never print larger than 96 dpi
+            let scale = (avail_width / width).min(avail_height / height).min(PIXEL);
+            let dx = MARGIN + (avail_width - width * scale) / 2.0;
+            let dy = MARGIN + (avail_height - height * scale) / 2.0;
+            let mut page = Page::new(page_width, page_height);
+            page.fill_color(background);
+            page.rect(dx, dy, width * scale, height * scale, 0.0, Paint::Fill);
+            page.save();
+            page.transform(scale, dx - min_x * scale, dy - min_y * scale);
+            draw_diagram(&mut page, ctx, title, title_position);
+            page.restore();
+            vec![page]
+        }
+        PageLayout::Tile { zoom } => {
+            let scale = PIXEL * zoom;
+            let columns = ((width * scale / avail_width).ceil() as usize).max(1);
+            let rows = ((height * scale / avail_height).ceil() as usize).max(1);
+            let mut pages = Vec::new();
+            for row in 0..rows {
+                for column in 0..columns {
+                    let mut page = Page::new(page_width, page_height);
+                    page.save();
+                    page.clip(MARGIN, MARGIN, avail_width, avail_height);
+                    page.fill_color(background);
+                    page.rect(MARGIN, MARGIN, avail_width, avail_height, 0.0, Paint::Fill);
+                    let dx = MARGIN - column as f32 * avail_width - min_x * scale;
+                    let dy = MARGIN - row as f32 * avail_height - min_y * scale;
+                    page.transform(scale, dx, dy);
+                    draw_diagram(&mut page, ctx, title, title_position);
+                    page.restore();
+
+                    crop_marks(&mut page, MARGIN, MARGIN, avail_width, avail_height);
+                    let caption = format!(
+                        "{}{}page {} of {} (row {}, column {})",
+                        title, if title.is_empty() { "" } else { " - " },
+                        pages.len() + 1, rows * columns, row + 1, column + 1
+                    );
+                    page.fill_color((0x55, 0x55, 0x55));
+                    page.text(ctx.font, MARGIN, page_height - MARGIN / 2.0 + 3.0, 8.0, Align::Left, &caption);
+                    pages.push(page);
+                }
+            }
+            pages
+        }
+    }
+}
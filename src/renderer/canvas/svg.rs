@@ -1,5 +1,7 @@
-use crate::renderer::graph::ErdGraph;
+use crate::formats::xml_escape;
+use crate::renderer::graph::{DiffStatus, ErdGraph};
 use crate::renderer::layout::LayoutEngine;
+use crate::renderer::theme::Theme;
 use petgraph::graph::NodeIndex;
 
 pub struct SvgContext<'a> {
@@ -7,6 +9,11 @@ pub struct SvgContext<'a> {
     pub layout_engine: &'a LayoutEngine,
     pub selected_table: Option<NodeIndex>,
     pub label_offsets: &'a [Option<(f32, f32)>],
+    pub theme: &'a Theme,
+    /// Wraps each table in a link to this prefix plus the table name
+    pub table_link_prefix: Option<&'a str>,
+    /// Groups tables and relationships with `data-*` attributes for scripts
+    pub annotate: bool,
 }
 
 pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -> Result<String, Box<dyn std::error::Error>> {
@@ -94,25 +101,44 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
     let height = max_y - min_y;
     
     // SVG header
+    let theme = ctx.theme;
     svg.push_str(&format!(
         r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
 <svg width="{}" height="{}" viewBox="{} {} {} {}" xmlns="http://www.w3.org/2000/svg">
 <defs>
   <style>
-    .table {{ fill: white; stroke: #3498db; stroke-width: 2; }}
-    .table-header {{ fill: #3498db; }}
-    .table-text {{ fill: white; font-family: Arial, sans-serif; font-size: 14px; }}
-    .column-text {{ fill: #2c3e50; font-family: Arial, sans-serif; font-size: 11px; }}
-    .type-text {{ fill: #7f8c8d; font-family: Arial, sans-serif; font-size: 9px; }}
-    .relationship {{ fill: none; stroke: #34495e; stroke-width: 2; }}
-    .relationship-selected {{ fill: none; stroke: #e74c3c; stroke-width: 3; }}
-    .label-box {{ fill: rgba(50, 50, 50, 0.9); stroke: #646464; stroke-width: 1; }}
-    .label-text {{ fill: white; font-family: Arial, sans-serif; font-size: 10px; }}
-    .title-text {{ fill: #2c3e50; font-family: Arial, sans-serif; font-size: 32px; font-weight: bold; }}
+    .table {{ fill: {}; stroke: {}; stroke-width: 2; }}
+    .table-header {{ fill: {}; }}
+    .table-text {{ fill: {}; font-family: Arial, sans-serif; font-size: 14px; }}
+    .column-text {{ fill: {}; font-family: Arial, sans-serif; font-size: 11px; }}
+    .type-text {{ fill: {}; font-family: Arial, sans-serif; font-size: 9px; }}
+    .relationship {{ fill: none; stroke: {}; stroke-width: 2; }}
+    .relationship-selected {{ fill: none; stroke: {}; stroke-width: 3; }}
+    .marker {{ stroke: {}; stroke-width: 2.5; }}
+    .label-box {{ fill: {}; stroke: {}; stroke-width: 1; }}
+    .label-pointer {{ stroke: {}; stroke-width: 0.8; }}
+    .rel-type-text {{ fill: {}; font-family: Arial, sans-serif; }}
+    .label-text {{ fill: {}; font-family: Arial, sans-serif; font-size: 10px; }}
+    .title-text {{ fill: {}; font-family: Arial, sans-serif; font-size: 32px; font-weight: bold; }}
   </style>
 </defs>
+<rect x="{}" y="{}" width="{}" height="{}" fill="{}" />
 "#,
-        width, height, min_x, min_y, width, height
+        width, height, min_x, min_y, width, height,
+        theme.table_fill.to_svg(), theme.table_border.to_svg(),
+        theme.header_fill.to_svg(),
+        theme.header_text.to_svg(),
+        theme.column_text.to_svg(),
+        theme.type_text.to_svg(),
+        theme.edge.to_svg(),
+        theme.selected.to_svg(),
+        theme.edge.to_svg(),
+        theme.label_fill.to_svg(), theme.label_border.to_svg(),
+        theme.label_pointer.to_svg(),
+        theme.label_type_text.to_svg(),
+        theme.label_text.to_svg(),
+        theme.heading_text.to_svg(),
+        min_x, min_y, width, height, theme.background.to_svg()
     ));
     
     // Draw edges first
@@ -130,6 +156,13 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
         };
         
         let class = if is_selected { "relationship-selected" } else { "relationship" };
+        if ctx.annotate {
+            svg.push_str(&format!(
+                r#"  <g class="rel" data-from="{}" data-to="{}">"#,
+                edge_route.from_table, edge_route.to_table
+            ));
+            svg.push('\n');
+        }
         
         // Build path
         let mut path = String::from("M ");
@@ -146,6 +179,9 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
         
         // Draw relationship markers
         add_svg_markers(&mut svg, edge_route);
+        if ctx.annotate {
+            svg.push_str("  </g>\n");
+        }
     }
     
     // Draw tables
@@ -157,31 +193,88 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
             let w = layout.size.width;
             let h = layout.size.height;
             
+            // Removed tables are drawn as faded, dashed ghosts
+            let ghost = table.status == DiffStatus::Removed;
+            let dash = if ghost { "; stroke-dasharray: 6 4" } else { "" };
+            
+            // Custom colour overrides the stylesheet defaults via inline styles;
+            // in compare mode the diff colour takes precedence
+            let diff_hex = super::utils::diff_color(table.status).map(|(r, g, b)| format!("#{:02x}{:02x}{:02x}", r, g, b));
+            let color = diff_hex.or_else(|| table.color.clone());
+            let custom_rgb = color.as_deref().and_then(super::utils::parse_hex_rgb);
+            let (table_style, header_style, text_style) = match (custom_rgb, &color) {
+                (Some((r, g, b)), Some(color)) => {
+                    let text_color = if super::utils::is_light_color(r, g, b) { "#2c3e50" } else { "white" };
+                    (
+                        format!(r#" style="stroke: {}{}""#, color, dash),
+                        format!(r#" style="fill: {}""#, color),
+                        format!(r#" style="fill: {}""#, text_color),
+                    )
+                }
+                _ => (String::new(), String::new(), String::new()),
+            };
+            
+            if let Some(prefix) = ctx.table_link_prefix {
+                svg.push_str(&format!(r#"  <a href="{}{}">"#, xml_escape(prefix), table.name));
+                svg.push('\n');
+            }
+            if ctx.annotate {
+                svg.push_str(&format!(r#"  <g class="table-group" data-table="{}">"#, table.name));
+                svg.push('\n');
+            }
+            if ghost {
+                svg.push_str(r#"  <g opacity="0.45">"#);
+                svg.push('\n');
+            }
+            
             // Table background
             svg.push_str(&format!(
-                r#"  <rect class="table" x="{}" y="{}" width="{}" height="{}" rx="8" />"#,
-                x, y, w, h
+                r#"  <rect class="table" x="{}" y="{}" width="{}" height="{}" rx="8"{} />"#,
+                x, y, w, h, table_style
             ));
             svg.push('\n');
             
             // Header
             let header_height = 40.0;
             svg.push_str(&format!(
-                r#"  <rect class="table-header" x="{}" y="{}" width="{}" height="{}" rx="8" />"#,
-                x, y, w, header_height
+                r#"  <rect class="table-header" x="{}" y="{}" width="{}" height="{}" rx="8"{} />"#,
+                x, y, w, header_height, header_style
             ));
             svg.push('\n');
             
-            // Table name
+            // Table name, prefixed with its icon if one is set
+            // Unknown icon names are shown as written, so escape them
+            let header_text = match &table.icon {
+                Some(icon) => format!("{} {}", super::utils::icon_glyph(icon), table.name),
+                None => table.name.clone(),
+            };
+            let header_text = xml_escape(&header_text);
             svg.push_str(&format!(
-                r#"  <text class="table-text" x="{}" y="{}" text-anchor="middle">{}</text>"#,
-                x + w / 2.0, y + header_height / 2.0 + 5.0, table.name
+                r#"  <text class="table-text" x="{}" y="{}" text-anchor="middle"{}>{}</text>"#,
+                x + w / 2.0, y + header_height / 2.0 + 5.0, text_style, header_text
             ));
             svg.push('\n');
             
             // Columns
             let mut y_offset = header_height + 20.0;
             for column in &table.columns {
+                // Tint added/removed/changed rows; whole-table changes are shown by the header instead
+                if let Some((r, g, b)) = super::utils::diff_color(column.status).filter(|_| column.status != table.status) {
+                    let row_y = y + y_offset - 14.0;
+                    svg.push_str(&format!(
+                        r#"  <rect x="{}" y="{}" width="{}" height="23" rx="4" fill="rgb({},{},{})" fill-opacity="0.25" />"#,
+                        x + 6.0, row_y, w - 12.0, r, g, b
+                    ));
+                    svg.push('\n');
+                    if column.status == DiffStatus::Removed {
+                        svg.push_str(&format!(
+                            r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="rgb({},{},{})" stroke-width="1" />"#,
+                            x + 12.0, row_y + 11.5, x + w - 12.0, row_y + 11.5, r, g, b
+                        ));
+                        svg.push('\n');
+                    }
+                }
+                
                 let col_name = if column.attributes.contains(&"PK".to_string()) {
                     format!("🔑 {}", column.name)
                 } else {
@@ -190,7 +283,7 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
                 
                 svg.push_str(&format!(
                     r#"  <text class="column-text" x="{}" y="{}">{}</text>"#,
-                    x + 15.0, y + y_offset, col_name
+                    x + 15.0, y + y_offset, xml_escape(&col_name)
                 ));
                 svg.push('\n');
                 
@@ -203,12 +296,22 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
                 
                 svg.push_str(&format!(
                     r#"  <text class="type-text" x="{}" y="{}" text-anchor="end">{}</text>"#,
-                    x + w - 15.0, y + y_offset, type_text
+                    x + w - 15.0, y + y_offset, xml_escape(&type_text)
                 ));
                 svg.push('\n');
                 
                 y_offset += 25.0;
             }
+            
+            if ghost {
+                svg.push_str("  </g>\n");
+            }
+            if ctx.annotate {
+                svg.push_str("  </g>\n");
+            }
+            if ctx.table_link_prefix.is_some() {
+                svg.push_str("  </a>\n");
+            }
         }
     }
     
@@ -291,9 +394,17 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
                 (label_x, label_y)
             };
             
+            if ctx.annotate {
+                svg.push_str(&format!(
+                    r#"  <g class="rel-label" data-from="{}" data-to="{}">"#,
+                    edge_route.from_table, edge_route.to_table
+                ));
+                svg.push('\n');
+            }
+            
             // Draw pointer line from box edge to line middle
             svg.push_str(&format!(
-                r##"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#787878" stroke-width="0.8" />"##,
+                r#"  <line class="label-pointer" x1="{}" y1="{}" x2="{}" y2="{}" />"#,
                 edge_x, edge_y, line_x, line_y
             ));
             svg.push('\n');
@@ -307,24 +418,27 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
             
             // Label text - relationship type in gray
             svg.push_str(&format!(
-                r##"  <text class="rel-type-text" x="{}" y="{}" fill="#B4B4B4" font-size="10">{}</text>"##,
-                label_x - total_text_width / 2.0, label_y + 4.0, rel_text_with_brackets
+                r#"  <text class="rel-type-text" x="{}" y="{}" font-size="10">{}</text>"#,
+                label_x - total_text_width / 2.0, label_y + 4.0, xml_escape(&rel_text_with_brackets)
             ));
             svg.push('\n');
             
             // Label text - field names in white
             svg.push_str(&format!(
-                r##"  <text class="label-text" x="{}" y="{}" font-size="11">{}</text>"##,
-                label_x - total_text_width / 2.0 + rel_text_width + spacing, label_y + 4.0, field_label
+                r#"  <text class="label-text" x="{}" y="{}" font-size="11">{}</text>"#,
+                label_x - total_text_width / 2.0 + rel_text_width + spacing, label_y + 4.0, xml_escape(&field_label)
             ));
             svg.push('\n');
+            if ctx.annotate {
+                svg.push_str("  </g>\n");
+            }
         }
     }
     
     // Draw title
     svg.push_str(&format!(
         r#"  <text class="title-text" x="{}" y="{}" text-anchor="middle">{}</text>"#,
-        title_position.0, title_position.1, title
+        title_position.0, title_position.1, xml_escape(title)
     ));
     svg.push('\n');
     
@@ -333,7 +447,7 @@ pub fn generate_svg(ctx: &SvgContext, title: &str, title_position: (f32, f32)) -
     Ok(svg)
 }
 
-fn get_edge_middle_point(points: &[crate::renderer::layout::Point]) -> Option<(f32, f32)> {
+pub(super) fn get_edge_middle_point(points: &[crate::renderer::layout::Point]) -> Option<(f32, f32)> {
     if points.len() < 2 {
         return None;
     }
@@ -435,7 +549,7 @@ fn add_svg_one_marker(svg: &mut String, points: &[crate::renderer::layout::Point
     let size = 10.0;
     
     svg.push_str(&format!(
-        r##"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#34495e" stroke-width="2.5" />"##,
+        r#"  <line class="marker" x1="{}" y1="{}" x2="{}" y2="{}" />"#,
         pos.0 + perp.0 * size, pos.1 + perp.1 * size,
         pos.0 - perp.0 * size, pos.1 - perp.1 * size
     ));
@@ -475,22 +589,36 @@ fn add_svg_many_marker(svg: &mut String, points: &[crate::renderer::layout::Poin
     
     // Center line
     svg.push_str(&format!(
-        r##"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#34495e" stroke-width="2.5" />"##,
+        r#"  <line class="marker" x1="{}" y1="{}" x2="{}" y2="{}" />"#,
         base.0, base.1, pos.0, pos.1
     ));
     svg.push('\n');
     
     // Left line
     svg.push_str(&format!(
-        r##"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#34495e" stroke-width="2.5" />"##,
+        r#"  <line class="marker" x1="{}" y1="{}" x2="{}" y2="{}" />"#,
         base.0, base.1, pos.0 + perp.0 * size, pos.1 + perp.1 * size
     ));
     svg.push('\n');
     
     // Right line
     svg.push_str(&format!(
-        r##"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#34495e" stroke-width="2.5" />"##,
+        r#"  <line class="marker" x1="{}" y1="{}" x2="{}" y2="{}" />"#,
         base.0, base.1, pos.0 - perp.0 * size, pos.1 - perp.1 * size
     ));
     svg.push('\n');
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+    use crate::renderer::{export_svg, ErdGraph, Theme};
+
+    #[test]
+    fn test_unknown_icon_is_escaped() {
+        let schema = Parser::new("table Users [icon=\"<b>&\"] {\n  id: int [pk]\n}\n").parse().unwrap();
+        let erd_graph = ErdGraph::from_schema(&schema).unwrap();
+        let svg = export_svg(&erd_graph, "", &Theme::light()).unwrap();
+        assert!(svg.contains(">&lt;b&gt;&amp; Users</text>"));
+    }
+}
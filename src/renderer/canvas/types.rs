@@ -1,3 +1,4 @@
+use egui::Pos2;
 use petgraph::graph::NodeIndex;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -6,4 +7,6 @@ pub enum DragTarget {
     Table(NodeIndex),
     Label(usize), // Index into edge_routes
     Title,
+    BoxSelect(Pos2), // Rubber-band selection started at this screen position
+    Connect(NodeIndex, usize), // Drawing a relationship from this table's column
 }
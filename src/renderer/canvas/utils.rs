@@ -1,5 +1,11 @@
+// Geometry helpers for the interactive canvas, plus the colour and title
+// helpers the exporters share. Only the latter are built without the viewer.
+#[cfg(feature = "viewer")]
+use std::collections::HashSet;
+#[cfg(feature = "viewer")]
 use egui::Pos2;
 
+#[cfg(feature = "viewer")]
 pub fn transform_point(x: f32, y: f32, zoom: f32, pan_offset: Pos2) -> Pos2 {
     Pos2::new(
         x * zoom + pan_offset.x,
@@ -7,6 +13,7 @@ pub fn transform_point(x: f32, y: f32, zoom: f32, pan_offset: Pos2) -> Pos2 {
     )
 }
 
+#[cfg(feature = "viewer")]
 pub fn screen_to_world(screen_pos: Pos2, zoom: f32, pan_offset: Pos2) -> (f32, f32) {
     (
         (screen_pos.x - pan_offset.x) / zoom,
@@ -14,7 +21,76 @@ pub fn screen_to_world(screen_pos: Pos2, zoom: f32, pan_offset: Pos2) -> (f32, f
     )
 }
 
+/// Parse a `#rgb` or `#rrggbb` colour string into RGB components
+pub fn parse_hex_rgb(hex: &str) -> Option<(u8, u8, u8)> {
+    let digits = hex.strip_prefix('#')?;
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    match digits.len() {
+        3 => {
+            let mut rgb = [0u8; 3];
+            for (i, ch) in digits.chars().enumerate() {
+                let v = ch.to_digit(16)? as u8;
+                rgb[i] = v * 17; // Expand #abc to #aabbcc
+            }
+            Some((rgb[0], rgb[1], rgb[2]))
+        }
+        6 => {
+            let r = u8::from_str_radix(&digits[0..2], 16).ok()?;
+            let g = u8::from_str_radix(&digits[2..4], 16).ok()?;
+            let b = u8::from_str_radix(&digits[4..6], 16).ok()?;
+            Some((r, g, b))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a colour is light enough that dark text should be drawn on it
+pub fn is_light_color(r: u8, g: u8, b: u8) -> bool {
+    // Perceived luminance (ITU-R BT.601)
+    let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+    luminance > 170.0
+}
+
+/// Compare-mode highlight: green for added, red for removed, amber for changed
+pub fn diff_color(status: crate::renderer::graph::DiffStatus) -> Option<(u8, u8, u8)> {
+    use crate::renderer::graph::DiffStatus;
+    match status {
+        DiffStatus::Unchanged => None,
+        DiffStatus::Added => Some((34, 197, 94)),
+        DiffStatus::Removed => Some((239, 68, 68)),
+        DiffStatus::Changed => Some((245, 158, 11)),
+    }
+}
+
+/// Map a table icon name to the glyph drawn in its header
+pub fn icon_glyph(name: &str) -> String {
+    let glyph = match name.to_lowercase().as_str() {
+        "user" | "person" => "👤",
+        "users" | "group" => "👥",
+        "key" => "🔑",
+        "lock" | "security" => "🔒",
+        "audit" | "log" | "history" => "📜",
+        "clock" | "time" => "🕒",
+        "archive" | "deprecated" => "📦",
+        "money" | "payment" => "💰",
+        "cart" | "order" => "🛒",
+        "mail" | "email" => "✉",
+        "document" | "file" => "📄",
+        "settings" | "config" => "⚙",
+        "warning" => "⚠",
+        "star" => "⭐",
+        "tag" => "🏷",
+        "link" => "🔗",
+        _ => return name.to_string(), // Unknown names are drawn as written
+    };
+    glyph.to_string()
+}
+
+#[cfg(feature = "viewer")]
 /// Check if a box at the proposed position collides with any existing tables
+/// other than the excluded ones (the table or group being moved)
 pub fn check_collision_with_tables(
     erd_graph: &crate::renderer::graph::ErdGraph,
     layout_engine: &crate::renderer::layout::LayoutEngine,
@@ -22,7 +98,7 @@ pub fn check_collision_with_tables(
     proposed_y: f32,
     table_width: f32,
     table_height: f32,
-    exclude_node: petgraph::graph::NodeIndex,
+    exclude: &[petgraph::graph::NodeIndex],
 ) -> bool {
     let proposed_rect = egui::Rect::from_min_size(
         Pos2::new(proposed_x, proposed_y),
@@ -30,7 +106,7 @@ pub fn check_collision_with_tables(
     );
     
     for node_idx in erd_graph.graph().node_indices() {
-        if node_idx == exclude_node {
+        if exclude.contains(&node_idx) {
             continue; // Don't check collision with self
         }
         
@@ -49,6 +125,7 @@ pub fn check_collision_with_tables(
     false
 }
 
+#[cfg(feature = "viewer")]
 /// Resolve collision by sliding the box to the nearest non-colliding position
 pub fn resolve_collision(
     erd_graph: &crate::renderer::graph::ErdGraph,
@@ -57,58 +134,56 @@ pub fn resolve_collision(
     proposed_y: f32,
     table_width: f32,
     table_height: f32,
-    exclude_node: petgraph::graph::NodeIndex,
+    exclude: &[petgraph::graph::NodeIndex],
+) -> (f32, f32) {
+    let rect = egui::Rect::from_min_size(Pos2::ZERO, egui::vec2(table_width, table_height));
+    resolve_group_collision(erd_graph, layout_engine, &[rect], (proposed_x, proposed_y), exclude)
+}
+
+#[cfg(feature = "viewer")]
+/// Resolve collisions for tables moved together: the same offset is applied
+/// to every rect and each one is checked against the tables not in `exclude`.
+/// Returns the offset to move the group by, slid as little as possible.
+pub fn resolve_group_collision(
+    erd_graph: &crate::renderer::graph::ErdGraph,
+    layout_engine: &crate::renderer::layout::LayoutEngine,
+    rects: &[egui::Rect],
+    (dx, dy): (f32, f32),
+    exclude: &[petgraph::graph::NodeIndex],
 ) -> (f32, f32) {
-    // If no collision, return the proposed position
-    if !check_collision_with_tables(
+    let collides = |dx: f32, dy: f32| rects.iter().any(|rect| check_collision_with_tables(
         erd_graph,
         layout_engine,
-        proposed_x,
-        proposed_y,
-        table_width,
-        table_height,
-        exclude_node,
-    ) {
-        return (proposed_x, proposed_y);
+        rect.min.x + dx,
+        rect.min.y + dy,
+        rect.width(),
+        rect.height(),
+        exclude,
+    ));
+    
+    // If no collision, return the proposed offset
+    if !collides(dx, dy) {
+        return (dx, dy);
     }
     
-    // Try to push the box away from colliding objects
+    // Try to push the group away from colliding objects
     // Test multiple directions: up, down, left, right
     
     let max_offset = 150.0; // Maximum offset before giving up
     let step = 5.0; // Increment step
     
-    let original_x = proposed_x;
-    let original_y = proposed_y;
-    
     // Try pushing vertically first
     for offset_steps in 1..=(max_offset / step) as i32 {
         let offset = offset_steps as f32 * step;
         
         // Try moving up
-        if !check_collision_with_tables(
-            erd_graph,
-            layout_engine,
-            proposed_x,
-            proposed_y - offset,
-            table_width,
-            table_height,
-            exclude_node,
-        ) {
-            return (proposed_x, proposed_y - offset);
+        if !collides(dx, dy - offset) {
+            return (dx, dy - offset);
         }
         
         // Try moving down
-        if !check_collision_with_tables(
-            erd_graph,
-            layout_engine,
-            proposed_x,
-            proposed_y + offset,
-            table_width,
-            table_height,
-            exclude_node,
-        ) {
-            return (proposed_x, proposed_y + offset);
+        if !collides(dx, dy + offset) {
+            return (dx, dy + offset);
         }
     }
     
@@ -117,36 +192,56 @@ pub fn resolve_collision(
         let offset = offset_steps as f32 * step;
         
         // Try moving left
-        if !check_collision_with_tables(
-            erd_graph,
-            layout_engine,
-            proposed_x - offset,
-            proposed_y,
-            table_width,
-            table_height,
-            exclude_node,
-        ) {
-            return (proposed_x - offset, proposed_y);
+        if !collides(dx - offset, dy) {
+            return (dx - offset, dy);
         }
         
         // Try moving right
-        if !check_collision_with_tables(
-            erd_graph,
-            layout_engine,
-            proposed_x + offset,
-            proposed_y,
-            table_width,
-            table_height,
-            exclude_node,
-        ) {
-            return (proposed_x + offset, proposed_y);
+        if !collides(dx + offset, dy) {
+            return (dx + offset, dy);
         }
     }
     
-    // If all else fails, keep the original position
-    (original_x, original_y)
+    // If all else fails, keep the proposed offset
+    (dx, dy)
+}
+
+#[cfg(feature = "viewer")]
+/// A table and the world position it should move to
+pub type TablePlacement = (petgraph::graph::NodeIndex, (f32, f32));
+
+#[cfg(feature = "viewer")]
+/// New positions that line every table up with the leftmost one
+pub fn align_left(tables: &[(petgraph::graph::NodeIndex, egui::Rect)]) -> Vec<TablePlacement> {
+    let left = tables.iter().map(|(_, rect)| rect.min.x).fold(f32::MAX, f32::min);
+    tables.iter().map(|(idx, rect)| (*idx, (left, rect.min.y))).collect()
+}
+
+#[cfg(feature = "viewer")]
+/// New positions that keep the outermost tables in place and spread the
+/// others so the horizontal gaps between neighbours are equal
+pub fn distribute_horizontally(tables: &[(petgraph::graph::NodeIndex, egui::Rect)]) -> Vec<TablePlacement> {
+    let min_gap = 40.0;
+    
+    let mut sorted = tables.to_vec();
+    sorted.sort_by(|a, b| a.1.min.x.total_cmp(&b.1.min.x));
+    if sorted.len() < 3 {
+        return sorted.iter().map(|(idx, rect)| (*idx, (rect.min.x, rect.min.y))).collect();
+    }
+    
+    let span = sorted.iter().map(|(_, r)| r.max.x).fold(f32::MIN, f32::max) - sorted[0].1.min.x;
+    let widths: f32 = sorted.iter().map(|(_, r)| r.width()).sum();
+    let gap = ((span - widths) / (sorted.len() - 1) as f32).max(min_gap);
+    
+    let mut x = sorted[0].1.min.x;
+    sorted.iter().map(|(idx, rect)| {
+        let pos = (x, rect.min.y);
+        x += rect.width() + gap;
+        (*idx, pos)
+    }).collect()
 }
 
+#[cfg(feature = "viewer")]
 pub fn calculate_bounds(
     erd_graph: &crate::renderer::graph::ErdGraph,
     layout_engine: &crate::renderer::layout::LayoutEngine,
@@ -175,6 +270,59 @@ pub fn calculate_bounds(
     }
 }
 
+#[cfg(feature = "viewer")]
+/// World-space rect of a single table, for centring the view on it
+pub fn table_bounds(
+    layout_engine: &crate::renderer::layout::LayoutEngine,
+    node_idx: petgraph::graph::NodeIndex,
+) -> Option<egui::Rect> {
+    layout_engine.get_node_layout(node_idx).map(|layout| {
+        egui::Rect::from_min_size(
+            Pos2::new(layout.position.x, layout.position.y),
+            egui::vec2(layout.size.width, layout.size.height),
+        )
+    })
+}
+
+#[cfg(feature = "viewer")]
+/// True if either end of an edge lies outside the given table set
+pub fn edge_outside_set(
+    erd_graph: &crate::renderer::graph::ErdGraph,
+    set: &HashSet<petgraph::graph::NodeIndex>,
+    from_table: &str,
+    to_table: &str,
+) -> bool {
+    [from_table, to_table].iter().any(|name| {
+        erd_graph.node_index(name).is_none_or(|idx| !set.contains(&idx))
+    })
+}
+
+/// Default title position: centred above the topmost table
+pub fn default_title_position(
+    erd_graph: &crate::renderer::graph::ErdGraph,
+    layout_engine: &crate::renderer::layout::LayoutEngine,
+) -> (f32, f32) {
+    let mut min_y = f32::MAX;
+    let mut center_x = 0.0;
+    let mut count = 0;
+    
+    for node_idx in erd_graph.graph().node_indices() {
+        if let Some(layout) = layout_engine.get_node_layout(node_idx) {
+            min_y = min_y.min(layout.position.y);
+            center_x += layout.position.x + layout.size.width / 2.0;
+            count += 1;
+        }
+    }
+    
+    if count > 0 {
+        center_x /= count as f32;
+    }
+    
+    // Position title above the topmost table
+    (center_x, min_y - 80.0)
+}
+
+#[cfg(feature = "viewer")]
 pub fn center_graph(
     zoom: &mut f32,
     pan_offset: &mut Pos2,
@@ -207,3 +355,54 @@ pub fn center_graph(
         *pan_offset = viewport_center - scaled_graph_center.to_vec2();
     }
 }
+
+#[cfg(all(test, feature = "viewer"))]
+mod tests {
+    use super::*;
+    use petgraph::graph::NodeIndex;
+
+    fn rect(x: f32, y: f32, w: f32) -> egui::Rect {
+        egui::Rect::from_min_size(Pos2::new(x, y), egui::vec2(w, 100.0))
+    }
+
+    #[test]
+    fn test_group_collision_ignores_tables_between_the_group() {
+        let schema = crate::parser::Parser::new("table A {\n  id: int\n}\ntable B {\n  id: int\n}\ntable C {\n  id: int\n}\n").parse().unwrap();
+        let erd_graph = crate::renderer::graph::ErdGraph::from_schema(&schema).unwrap();
+        let positions = [("A", (0.0, 0.0)), ("C", (400.0, 0.0)), ("B", (800.0, 0.0))]
+            .into_iter().map(|(name, pos)| (name.to_string(), pos)).collect();
+        let mut layout_engine = crate::renderer::layout::LayoutEngine::new();
+        layout_engine.layout_with_positions(&erd_graph, &positions);
+        
+        let group = [erd_graph.node_index("A").unwrap(), erd_graph.node_index("B").unwrap()];
+        let rects: Vec<egui::Rect> = group.iter().filter_map(|idx| table_bounds(&layout_engine, *idx)).collect();
+        // C sits inside the group's bounding box but neither table touches it
+        assert_eq!(resolve_group_collision(&erd_graph, &layout_engine, &rects, (10.0, 0.0), &group), (10.0, 0.0));
+        // Moving B onto C slides the whole group off it
+        let (_, dy) = resolve_group_collision(&erd_graph, &layout_engine, &rects, (-400.0, 0.0), &group);
+        assert_ne!(dy, 0.0);
+    }
+
+    #[test]
+    fn test_align_left() {
+        let tables = [(NodeIndex::new(0), rect(120.0, 0.0, 50.0)), (NodeIndex::new(1), rect(40.0, 300.0, 80.0))];
+        let moved = align_left(&tables);
+        assert_eq!(moved, vec![(NodeIndex::new(0), (40.0, 0.0)), (NodeIndex::new(1), (40.0, 300.0))]);
+    }
+
+    #[test]
+    fn test_distribute_horizontally() {
+        // Span 0..400 with 300 of table width leaves two 50px gaps
+        let tables = [
+            (NodeIndex::new(0), rect(300.0, 10.0, 100.0)),
+            (NodeIndex::new(1), rect(0.0, 20.0, 100.0)),
+            (NodeIndex::new(2), rect(120.0, 30.0, 100.0)),
+        ];
+        let moved = distribute_horizontally(&tables);
+        assert_eq!(moved, vec![
+            (NodeIndex::new(1), (0.0, 20.0)),
+            (NodeIndex::new(2), (150.0, 30.0)),
+            (NodeIndex::new(0), (300.0, 10.0)),
+        ]);
+    }
+}
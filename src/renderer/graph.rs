@@ -1,11 +1,14 @@
 use petgraph::graph::{DiGraph, NodeIndex};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Represents a table in the ERD
 #[derive(Debug, Clone)]
 pub struct TableNode {
     pub name: String,
     pub columns: Vec<ColumnData>,
+    pub color: Option<String>, // Custom header colour as a hex string
+    pub icon: Option<String>,  // Icon name shown before the table name
+    pub status: DiffStatus,    // How the table changed when comparing two schemas
 }
 
 #[derive(Debug, Clone)]
@@ -13,6 +16,17 @@ pub struct ColumnData {
     pub name: String,
     pub data_type: String,
     pub attributes: Vec<String>,
+    pub status: DiffStatus,
+}
+
+/// Compare-mode state of a table or column; `Removed` items are drawn as ghosts
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DiffStatus {
+    #[default]
+    Unchanged,
+    Added,
+    Removed,
+    Changed,
 }
 
 /// Represents a relationship edge
@@ -31,6 +45,32 @@ pub enum RelationType {
     ManyToMany,
 }
 
+/// A search hit: the table, plus the column index if a column name matched
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchMatch {
+    pub node: NodeIndex,
+    pub column: Option<usize>,
+}
+
+fn column_data(column: &crate::ast::Column, status: DiffStatus) -> ColumnData {
+    ColumnData {
+        name: column.name.clone(),
+        data_type: column.datatype.to_string(),
+        attributes: column.attributes.iter().map(|attr| attr.to_string()).collect(),
+        status,
+    }
+}
+
+fn table_node(table: &crate::ast::Table, status: DiffStatus) -> TableNode {
+    TableNode {
+        name: table.name.clone(),
+        columns: table.columns.iter().map(|col| column_data(col, status)).collect(),
+        color: table.color().map(|c| c.to_string()),
+        icon: table.icon().map(|i| i.to_string()),
+        status,
+    }
+}
+
 /// The ERD graph structure using petgraph
 pub struct ErdGraph {
     pub(crate) graph: DiGraph<TableNode, RelationshipEdge>,
@@ -67,9 +107,146 @@ impl ErdGraph {
         Ok(())
     }
 
+    /// Convert a schema's tables and relationships into a graph
+    pub fn from_schema(schema: &crate::ast::Schema) -> Result<Self, String> {
+        let mut erd_graph = ErdGraph::new();
+        
+        for table in &schema.tables {
+            erd_graph.add_table(table_node(table, DiffStatus::Unchanged));
+        }
+        
+        erd_graph.add_relationships(schema)?;
+        Ok(erd_graph)
+    }
+    
+    /// Graph of the new schema with every table and column marked by how it
+    /// changed. Removed tables and columns are kept so they can be drawn as ghosts.
+    #[cfg_attr(not(feature = "viewer"), allow(dead_code))]
+    pub fn from_comparison(old: &crate::ast::Schema, new: &crate::ast::Schema) -> Result<Self, String> {
+        use crate::diff::{Change, SchemaDiff};
+        
+        let diff = SchemaDiff::between(old, new);
+        let mut erd_graph = ErdGraph::new();
+        
+        for table in &new.tables {
+            let mut status = DiffStatus::Unchanged;
+            let mut column_status = HashMap::new();
+            let mut removed_columns = Vec::new();
+            for change in &diff.changes {
+                match change {
+                    Change::TableAdded(t) if t.name == table.name => status = DiffStatus::Added,
+                    Change::TableChanged { new: t, .. } if t.name == table.name => status = DiffStatus::Changed,
+                    Change::ColumnAdded { table: name, column } if *name == table.name => {
+                        column_status.insert(column.name.as_str(), DiffStatus::Added);
+                    }
+                    Change::ColumnChanged { table: name, new: column, .. } if *name == table.name => {
+                        column_status.insert(column.name.as_str(), DiffStatus::Changed);
+                    }
+                    Change::ColumnRemoved { table: name, column } if *name == table.name => removed_columns.push(column),
+                    _ => {}
+                }
+            }
+            
+            let mut node = table_node(table, status);
+            for column in &mut node.columns {
+                column.status = column_status.get(column.name.as_str()).copied().unwrap_or(status);
+            }
+            node.columns.extend(removed_columns.into_iter().map(|c| column_data(c, DiffStatus::Removed)));
+            erd_graph.add_table(node);
+        }
+        
+        for change in &diff.changes {
+            if let Change::TableRemoved(table) = change {
+                erd_graph.add_table(table_node(table, DiffStatus::Removed));
+            }
+        }
+        
+        erd_graph.add_relationships(new)?;
+        Ok(erd_graph)
+    }
+    
+    fn add_relationships(&mut self, schema: &crate::ast::Schema) -> Result<(), String> {
+        use crate::ast::RelationshipType;
+        
+        for rel in &schema.relationships {
+            let relationship_type = match rel.relationship_type {
+                RelationshipType::OneToOne => RelationType::OneToOne,
+                RelationshipType::OneToMany => RelationType::OneToMany,
+                RelationshipType::ManyToOne => RelationType::ManyToOne,
+                RelationshipType::ManyToMany => RelationType::ManyToMany,
+            };
+            
+            self.add_relationship(
+                &rel.from_table,
+                &rel.to_table,
+                RelationshipEdge {
+                    from_field: rel.from_field.clone(),
+                    to_field: rel.to_field.clone(),
+                    relationship_type,
+                },
+            )?;
+        }
+        
+        Ok(())
+    }
+
     pub fn graph(&self) -> &DiGraph<TableNode, RelationshipEdge> {
         &self.graph
     }
+
+    pub fn node_index(&self, table_name: &str) -> Option<NodeIndex> {
+        self.node_map.get(table_name).copied()
+    }
+
+    /// Case-insensitive substring search over table and column names.
+    /// Table name matches come first, then column matches, each in graph order.
+    #[cfg_attr(not(feature = "viewer"), allow(dead_code))]
+    pub fn search(&self, query: &str) -> Vec<SearchMatch> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut table_matches = Vec::new();
+        let mut column_matches = Vec::new();
+
+        for node in self.graph.node_indices() {
+            let table = &self.graph[node];
+            if table.name.to_lowercase().contains(&query) {
+                table_matches.push(SearchMatch { node, column: None });
+            }
+            for (col_idx, column) in table.columns.iter().enumerate() {
+                if column.name.to_lowercase().contains(&query) {
+                    column_matches.push(SearchMatch { node, column: Some(col_idx) });
+                }
+            }
+        }
+
+        table_matches.extend(column_matches);
+        table_matches
+    }
+
+    /// Tables reachable from `start` within `hops` relationships, ignoring direction
+    #[cfg_attr(not(feature = "viewer"), allow(dead_code))]
+    pub fn neighborhood(&self, start: NodeIndex, hops: usize) -> HashSet<NodeIndex> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start);
+        queue.push_back((start, 0));
+
+        while let Some((node, depth)) = queue.pop_front() {
+            if depth == hops {
+                continue;
+            }
+            for neighbor in self.graph.neighbors_undirected(node) {
+                if visited.insert(neighbor) {
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+
+        visited
+    }
 }
 
 impl Default for ErdGraph {
@@ -77,3 +254,106 @@ impl Default for ErdGraph {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(name: &str, columns: &[&str]) -> TableNode {
+        TableNode {
+            name: name.to_string(),
+            columns: columns.iter().map(|c| ColumnData {
+                name: c.to_string(),
+                data_type: "int".to_string(),
+                attributes: vec![],
+                status: DiffStatus::Unchanged,
+            }).collect(),
+            color: None,
+            icon: None,
+            status: DiffStatus::Unchanged,
+        }
+    }
+
+    fn edge() -> RelationshipEdge {
+        RelationshipEdge {
+            from_field: "id".to_string(),
+            to_field: "id".to_string(),
+            relationship_type: RelationType::OneToMany,
+        }
+    }
+
+    /// invoices -> invoice_line_items -> products -> suppliers, plus an isolated audit_log
+    fn chain_graph() -> ErdGraph {
+        let mut graph = ErdGraph::new();
+        graph.add_table(table("invoices", &["id", "customer_id"]));
+        graph.add_table(table("invoice_line_items", &["id", "invoice_id", "product_id"]));
+        graph.add_table(table("products", &["id", "supplier_id"]));
+        graph.add_table(table("suppliers", &["id"]));
+        graph.add_table(table("audit_log", &["id", "invoice_ref"]));
+        graph.add_relationship("invoices", "invoice_line_items", edge()).unwrap();
+        graph.add_relationship("invoice_line_items", "products", edge()).unwrap();
+        graph.add_relationship("products", "suppliers", edge()).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_search_tables_before_columns() {
+        let graph = chain_graph();
+        let matches = graph.search("INVOICE");
+
+        let invoices = graph.node_index("invoices").unwrap();
+        let line_items = graph.node_index("invoice_line_items").unwrap();
+        let audit = graph.node_index("audit_log").unwrap();
+
+        assert_eq!(matches[0], SearchMatch { node: invoices, column: None });
+        assert_eq!(matches[1], SearchMatch { node: line_items, column: None });
+        assert_eq!(matches[2], SearchMatch { node: line_items, column: Some(1) });
+        assert_eq!(matches[3], SearchMatch { node: audit, column: Some(1) });
+        assert_eq!(matches.len(), 4);
+    }
+
+    #[test]
+    fn test_search_empty_query() {
+        assert!(chain_graph().search("   ").is_empty());
+    }
+
+    #[test]
+    fn test_neighborhood_hops() {
+        let graph = chain_graph();
+        let line_items = graph.node_index("invoice_line_items").unwrap();
+
+        let zero = graph.neighborhood(line_items, 0);
+        assert_eq!(zero.len(), 1);
+
+        let one = graph.neighborhood(line_items, 1);
+        assert_eq!(one.len(), 3);
+        assert!(one.contains(&graph.node_index("invoices").unwrap()));
+        assert!(one.contains(&graph.node_index("products").unwrap()));
+
+        let two = graph.neighborhood(line_items, 2);
+        assert_eq!(two.len(), 4);
+        assert!(!two.contains(&graph.node_index("audit_log").unwrap()));
+    }
+
+    #[test]
+    fn test_comparison_marks_changes() {
+        use crate::parser::Parser;
+        let old = Parser::new("table Users { id: int [pk], age: int }\ntable Legacy { id: int }").parse().unwrap();
+        let new = Parser::new("table Users { id: int [pk], age: str, email: str }\ntable Orders { id: int }").parse().unwrap();
+        let graph = ErdGraph::from_comparison(&old, &new).unwrap();
+
+        let users = &graph.graph()[graph.node_index("Users").unwrap()];
+        assert_eq!(users.status, DiffStatus::Unchanged);
+        let columns: Vec<(&str, DiffStatus)> = users.columns.iter().map(|c| (c.name.as_str(), c.status)).collect();
+        assert_eq!(columns, vec![
+            ("id", DiffStatus::Unchanged),
+            ("age", DiffStatus::Changed),
+            ("email", DiffStatus::Added),
+        ]);
+
+        assert_eq!(graph.graph()[graph.node_index("Orders").unwrap()].status, DiffStatus::Added);
+        let legacy = &graph.graph()[graph.node_index("Legacy").unwrap()];
+        assert_eq!(legacy.status, DiffStatus::Removed);
+        assert_eq!(legacy.columns[0].status, DiffStatus::Removed);
+    }
+}
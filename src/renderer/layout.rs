@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use euclid::{Point2D, Size2D};
 use rand::Rng;
 
-use super::graph::{ErdGraph, RelationType};
+use super::graph::{ErdGraph, RelationType, TableNode};
 
 pub struct UnknownUnit;
 pub type Point = Point2D<f32, UnknownUnit>;
@@ -53,8 +53,6 @@ impl LayoutEngine {
             return;
         }
         
-        println!("Computing layout for {} nodes", node_count);
-        
         // Initialize node layouts with proper sizes and initial positions
         self.initialize_node_layouts(graph);
         
@@ -63,8 +61,6 @@ impl LayoutEngine {
         
         // Route edges orthogonally
         self.route_edges_orthogonal(graph);
-        
-        println!("Layout complete");
     }
     
     /// Initialize node layouts with calculated sizes
@@ -80,35 +76,7 @@ impl LayoutEngine {
         
         for (idx, node) in g.node_indices().enumerate() {
             let table = &g[node];
-            
-            // Calculate width based on content (use character-based estimation)
-            let min_width = 200.0;
-            let max_width = 500.0;
-            
-            // Estimate width based on table name
-            let name_width = table.name.len() as f32 * 9.0 + 40.0; // ~9px per char + padding
-            
-            // Estimate width based on longest column content
-            let mut max_column_width: f32 = 0.0;
-            for column in &table.columns {
-                // Column name
-                let col_name_width = column.name.len() as f32 * 7.0;
-                
-                // Data type and attributes
-                let type_text = if column.attributes.is_empty() {
-                    column.data_type.clone()
-                } else {
-                    format!("{} [{}]", column.data_type, column.attributes.join(","))
-                };
-                let type_width = type_text.len() as f32 * 6.0;
-                
-                let total_column_width = col_name_width + type_width + 40.0; // Add padding
-                max_column_width = max_column_width.max(total_column_width);
-            }
-            
-            // Choose the maximum of name width and column width, clamped to min/max
-            let width = name_width.max(max_column_width).max(min_width).min(max_width);
-            let height = 60.0 + table.columns.len() as f32 * 25.0;
+            let size = Self::measure_table(table);
             
             // Place in grid with some randomness
             let col = idx % cols;
@@ -116,18 +84,84 @@ impl LayoutEngine {
             let x = col as f32 * spacing_x + rng.gen_range(-30.0..30.0);
             let y = row as f32 * spacing_y + rng.gen_range(-30.0..30.0);
             
-            println!("Node {} '{}' at ({:.1}, {:.1}) size ({:.1}, {:.1})", 
-                node.index(), table.name, x, y, width, height);
+            self.node_layouts.insert(
+                node,
+                NodeLayout {
+                    position: Point::new(x, y),
+                    size,
+                    layer: 0,
+                },
+            );
+        }
+    }
+    
+    /// Estimate a table's on-screen size from its name and columns
+    fn measure_table(table: &TableNode) -> Size {
+        // Calculate width based on content (use character-based estimation)
+        let min_width = 200.0;
+        let max_width = 500.0;
+        
+        // Estimate width based on table name
+        let icon_width = if table.icon.is_some() { 24.0 } else { 0.0 };
+        let name_width = table.name.len() as f32 * 9.0 + icon_width + 40.0; // ~9px per char + padding
+        
+        // Estimate width based on longest column content
+        let mut max_column_width: f32 = 0.0;
+        for column in &table.columns {
+            // Column name
+            let col_name_width = column.name.len() as f32 * 7.0;
+            
+            // Data type and attributes
+            let type_text = if column.attributes.is_empty() {
+                column.data_type.clone()
+            } else {
+                format!("{} [{}]", column.data_type, column.attributes.join(","))
+            };
+            let type_width = type_text.len() as f32 * 6.0;
+            
+            let total_column_width = col_name_width + type_width + 40.0; // Add padding
+            max_column_width = max_column_width.max(total_column_width);
+        }
+        
+        // Choose the maximum of name width and column width, clamped to min/max
+        let width = name_width.max(max_column_width).max(min_width).min(max_width);
+        let height = 60.0 + table.columns.len() as f32 * 25.0;
+        
+        Size::new(width, height)
+    }
+    
+    /// Lay out a graph keeping known tables where they were (by name).
+    /// Tables without a position are placed in a column to the right.
+    pub fn layout_with_positions(&mut self, graph: &ErdGraph, positions: &HashMap<String, (f32, f32)>) {
+        let g = graph.graph();
+        self.node_layouts.clear();
+        
+        let right = positions.values().map(|p| p.0).fold(0.0_f32, f32::max) + 450.0;
+        let mut next_y = positions.values().map(|p| p.1).fold(f32::MAX, f32::min).min(0.0);
+        
+        for node in g.node_indices() {
+            let table = &g[node];
+            let size = Self::measure_table(table);
+            let (x, y) = match positions.get(&table.name) {
+                Some(pos) => *pos,
+                None => {
+                    let pos = (right, next_y);
+                    next_y += size.height + self.min_spacing;
+                    pos
+                }
+            };
             
             self.node_layouts.insert(
                 node,
                 NodeLayout {
                     position: Point::new(x, y),
-                    size: Size::new(width, height),
+                    size,
                     layer: 0,
                 },
             );
         }
+        
+        self.route_edges_orthogonal(graph);
     }
     
     /// Force-directed layout with collision avoidance
@@ -210,10 +244,6 @@ impl LayoutEngine {
                     layout.position.y += fy * damping;
                 }
             }
-            
-            if iter % 20 == 0 {
-                println!("Layout iteration {}/{}", iter, iterations);
-            }
         }
         
         // Final cleanup: resolve any remaining overlaps
@@ -225,7 +255,7 @@ impl LayoutEngine {
         let g = graph.graph();
         let nodes: Vec<NodeIndex> = g.node_indices().collect();
         
-        for pass in 0..15 {  // Multiple passes to ensure no overlaps
+        for _ in 0..15 {  // Multiple passes to ensure no overlaps
             let mut moved = false;
             
             for i in 0..nodes.len() {
@@ -270,7 +300,6 @@ impl LayoutEngine {
             }
             
             if !moved {
-                println!("No overlaps after {} passes", pass + 1);
                 break;
             }
         }
@@ -477,6 +506,7 @@ impl LayoutEngine {
         self.node_layouts.get(&node)
     }
     
+    #[cfg(feature = "viewer")]
     pub fn get_node_layout_mut(&mut self, node: NodeIndex) -> Option<&mut NodeLayout> {
         self.node_layouts.get_mut(&node)
     }
@@ -486,6 +516,7 @@ impl LayoutEngine {
     }
     
     /// Recompute edge routes after nodes have moved
+    #[cfg(feature = "viewer")]
     pub fn recompute_edge_routes(&mut self, graph: &ErdGraph) {
         self.route_edges_orthogonal(graph);
     }
@@ -1,12 +1,20 @@
 mod graph;
 mod layout;
 mod canvas;
+mod theme;
 
-pub use graph::{ErdGraph, TableNode, ColumnData, RelationshipEdge, RelationType};
+pub use graph::ErdGraph;
+#[cfg(feature = "viewer")]
 pub use canvas::ErdCanvas;
+pub use theme::Theme;
+pub use canvas::pdf::{PageLayout, Paper, PdfOptions};
 
-// Window rendering entry point
-pub fn render_window(erd_graph: ErdGraph, title: String) -> Result<(), Box<dyn std::error::Error>> {
+#[cfg(feature = "viewer")]
+use crate::editor::Document;
+
+// Window rendering entry point. With a document the schema can be edited and saved.
+#[cfg(feature = "viewer")]
+pub fn render_window(erd_graph: ErdGraph, title: String, theme: Theme, document: Option<Document>) -> Result<(), Box<dyn std::error::Error>> {
     let window_title = if title.is_empty() {
         "FreeERD - ERD Viewer".to_string()
     } else {
@@ -24,8 +32,148 @@ pub fn render_window(erd_graph: ErdGraph, title: String) -> Result<(), Box<dyn s
     eframe::run_native(
         "FreeERD",
         options,
-        Box::new(move |_cc| Ok(Box::new(ErdCanvas::new(erd_graph, title_clone)))),
+        Box::new(move |_cc| Ok(Box::new(ErdCanvas::new(erd_graph, title_clone, theme, document)))),
     )?;
     
     Ok(())
 }
+
+/// Where a table was placed, in canvas pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableLayout {
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// A relationship line as routed between its tables
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipLayout {
+    pub from_table: String,
+    pub to_table: String,
+    pub points: Vec<(f32, f32)>,
+}
+
+/// Table positions and relationship routes of a diagram. Layouts are
+/// randomised, so keep one to render the same picture again.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Layout {
+    pub tables: Vec<TableLayout>,
+    pub relationships: Vec<RelationshipLayout>,
+}
+
+/// Place the tables and route the relationships without drawing anything
+pub fn compute_layout(erd_graph: &ErdGraph) -> Layout {
+    let mut layout_engine = layout::LayoutEngine::new();
+    layout_engine.compute_layout(erd_graph);
+    
+    let tables = erd_graph.graph().node_indices()
+        .filter_map(|idx| {
+            let node = layout_engine.get_node_layout(idx)?;
+            Some(TableLayout {
+                name: erd_graph.graph()[idx].name.clone(),
+                x: node.position.x,
+                y: node.position.y,
+                width: node.size.width,
+                height: node.size.height,
+            })
+        })
+        .collect();
+    let relationships = layout_engine.get_edge_routes().iter()
+        .map(|route| RelationshipLayout {
+            from_table: route.from_table.clone(),
+            to_table: route.to_table.clone(),
+            points: route.points.iter().map(|p| (p.x, p.y)).collect(),
+        })
+        .collect();
+    Layout { tables, relationships }
+}
+
+/// Extras for exported SVG that is embedded in a page
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SvgOptions<'a> {
+    /// Each table links to this prefix plus its name
+    pub table_link_prefix: Option<&'a str>,
+    /// Tables and relationships carry `data-*` attributes for scripts
+    pub annotate: bool,
+}
+
+/// Lay out the graph and render it to SVG without opening a window
+pub fn export_svg(erd_graph: &ErdGraph, title: &str, theme: &Theme) -> Result<String, Box<dyn std::error::Error>> {
+    export_svg_with(erd_graph, title, theme, SvgOptions::default())
+}
+
+/// Like `export_svg`, with links or script hooks added
+pub fn export_svg_with(erd_graph: &ErdGraph, title: &str, theme: &Theme, options: SvgOptions) -> Result<String, Box<dyn std::error::Error>> {
+    let mut layout_engine = layout::LayoutEngine::new();
+    layout_engine.compute_layout(erd_graph);
+    svg_from_engine(erd_graph, &layout_engine, title, theme, options)
+}
+
+/// Render to SVG with the tables where an earlier `compute_layout` put them
+pub fn export_svg_at(erd_graph: &ErdGraph, layout: &Layout, title: &str, theme: &Theme) -> Result<String, Box<dyn std::error::Error>> {
+    let positions = layout.tables.iter().map(|t| (t.name.clone(), (t.x, t.y))).collect();
+    let mut layout_engine = layout::LayoutEngine::new();
+    layout_engine.layout_with_positions(erd_graph, &positions);
+    svg_from_engine(erd_graph, &layout_engine, title, theme, SvgOptions::default())
+}
+
+fn svg_from_engine(erd_graph: &ErdGraph, layout_engine: &layout::LayoutEngine, title: &str, theme: &Theme, options: SvgOptions) -> Result<String, Box<dyn std::error::Error>> {
+    let title_position = canvas::utils::default_title_position(erd_graph, layout_engine);
+    let label_offsets = vec![None; layout_engine.get_edge_routes().len()];
+    
+    let svg_ctx = canvas::svg::SvgContext {
+        erd_graph,
+        layout_engine,
+        selected_table: None,
+        label_offsets: &label_offsets,
+        theme,
+        table_link_prefix: options.table_link_prefix,
+        annotate: options.annotate,
+    };
+    
+    canvas::svg::generate_svg(&svg_ctx, title, title_position)
+}
+
+/// Lay out the graph and render it to a PDF. The canvas font is embedded
+/// unless another TrueType font file is given.
+pub fn export_pdf(erd_graph: &ErdGraph, title: &str, theme: &Theme, options: &PdfOptions, font_path: Option<&std::path::Path>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let (font_name, font_data) = match font_path {
+        Some(path) => {
+            let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            (name, std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?)
+        }
+        // The same font egui draws the canvas with
+        None => ("Ubuntu-Light".to_string(), epaint_default_fonts::UBUNTU_LIGHT.to_vec()),
+    };
+    let font = crate::pdf::Font::new(&font_name, &font_data)?;
+    
+    let mut layout_engine = layout::LayoutEngine::new();
+    layout_engine.compute_layout(erd_graph);
+    let title_position = canvas::utils::default_title_position(erd_graph, &layout_engine);
+    
+    let pdf_ctx = canvas::pdf::PdfContext {
+        erd_graph,
+        layout_engine: &layout_engine,
+        theme,
+        font: &font,
+    };
+    let pages = canvas::pdf::generate_pdf(&pdf_ctx, title, title_position, options);
+    Ok(crate::pdf::write(&font, title, &pages))
+}
+
+/// Lay out the graph and write it as a draw.io (diagrams.net) file
+pub fn export_drawio(erd_graph: &ErdGraph, title: &str, theme: &Theme) -> String {
+    let mut layout_engine = layout::LayoutEngine::new();
+    layout_engine.compute_layout(erd_graph);
+    let title_position = canvas::utils::default_title_position(erd_graph, &layout_engine);
+    
+    let drawio_ctx = canvas::drawio::DrawioContext {
+        erd_graph,
+        layout_engine: &layout_engine,
+        theme,
+    };
+    canvas::drawio::generate_drawio(&drawio_ctx, title, title_position)
+}
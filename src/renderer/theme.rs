@@ -0,0 +1,414 @@
+// Colour themes shared by the canvas and the SVG exporter
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// RGBA colour usable by both egui and SVG output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemeColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+    /// Whether r, g and b are already scaled by alpha, as egui stores colours
+    premultiplied: bool,
+}
+
+impl ThemeColor {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        ThemeColor { r, g, b, a: 255, premultiplied: false }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        ThemeColor { r, g, b, a, premultiplied: false }
+    }
+
+    /// Translucent colour given the way egui stores it. The built-in themes
+    /// use this so the canvas keeps the colours it was always drawn with.
+    pub const fn premultiplied(r: u8, g: u8, b: u8, a: u8) -> Self {
+        ThemeColor { r, g, b, a, premultiplied: true }
+    }
+
+    /// Parse `#rgb`, `#rrggbb` or `#rrggbbaa`
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.strip_prefix('#')?;
+        // Checked first so the slicing below stays on character boundaries
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        if digits.len() == 8 {
+            let alpha = u8::from_str_radix(&digits[6..8], 16).ok()?;
+            let (r, g, b) = super::canvas::utils::parse_hex_rgb(&hex[..7])?;
+            return Some(ThemeColor::rgba(r, g, b, alpha));
+        }
+        let (r, g, b) = super::canvas::utils::parse_hex_rgb(hex)?;
+        Some(ThemeColor::rgb(r, g, b))
+    }
+
+    #[cfg(feature = "viewer")]
+    pub fn to_color32(self) -> egui::Color32 {
+        if self.premultiplied {
+            egui::Color32::from_rgba_premultiplied(self.r, self.g, self.b, self.a)
+        } else {
+            egui::Color32::from_rgba_unmultiplied(self.r, self.g, self.b, self.a)
+        }
+    }
+
+    /// SVG/CSS colour value (`#rrggbb` when opaque, `rgba(...)` otherwise)
+    pub fn to_svg(self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("rgba({}, {}, {}, {:.2})", self.r, self.g, self.b, self.a as f32 / 255.0)
+        }
+    }
+}
+
+/// Every colour used by the renderers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub background: ThemeColor,
+    pub table_fill: ThemeColor,
+    pub table_border: ThemeColor,
+    pub table_shadow: ThemeColor,
+    pub header_fill: ThemeColor,
+    pub header_text: ThemeColor,
+    pub column_text: ThemeColor,
+    pub type_text: ThemeColor,
+    pub edge: ThemeColor,
+    pub selected: ThemeColor,
+    pub selected_header: ThemeColor,
+    pub label_fill: ThemeColor,
+    pub label_border: ThemeColor,
+    pub label_text: ThemeColor,
+    pub label_type_text: ThemeColor,
+    pub label_pointer: ThemeColor,
+    pub title_fill: ThemeColor,
+    pub title_drag_fill: ThemeColor,
+    pub title_border: ThemeColor,
+    pub title_text: ThemeColor,
+    pub heading_text: ThemeColor, // Title text in exports, drawn without a box
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Theme {
+            name: "light".to_string(),
+            background: ThemeColor::rgb(245, 247, 250),
+            table_fill: ThemeColor::rgb(255, 255, 255),
+            table_border: ThemeColor::rgb(52, 152, 219),
+            table_shadow: ThemeColor::premultiplied(0, 0, 0, 30),
+            header_fill: ThemeColor::rgb(52, 152, 219),
+            header_text: ThemeColor::rgb(255, 255, 255),
+            column_text: ThemeColor::rgb(44, 62, 80),
+            type_text: ThemeColor::rgb(127, 140, 141),
+            edge: ThemeColor::rgb(52, 73, 94),
+            selected: ThemeColor::rgb(255, 200, 0),
+            selected_header: ThemeColor::rgb(255, 180, 0),
+            label_fill: ThemeColor::premultiplied(50, 50, 50, 230),
+            label_border: ThemeColor::rgb(100, 100, 100),
+            label_text: ThemeColor::rgb(255, 255, 255),
+            label_type_text: ThemeColor::rgb(180, 180, 180),
+            label_pointer: ThemeColor::rgb(120, 120, 120),
+            title_fill: ThemeColor::rgb(52, 152, 219),
+            title_drag_fill: ThemeColor::rgb(70, 130, 180),
+            title_border: ThemeColor::rgb(255, 255, 255),
+            title_text: ThemeColor::rgb(255, 255, 255),
+            heading_text: ThemeColor::rgb(44, 62, 80),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Theme {
+            name: "dark".to_string(),
+            background: ThemeColor::rgb(30, 31, 34),
+            table_fill: ThemeColor::rgb(43, 45, 49),
+            table_border: ThemeColor::rgb(59, 130, 246),
+            table_shadow: ThemeColor::premultiplied(0, 0, 0, 90),
+            header_fill: ThemeColor::rgb(37, 99, 235),
+            header_text: ThemeColor::rgb(255, 255, 255),
+            column_text: ThemeColor::rgb(229, 231, 235),
+            type_text: ThemeColor::rgb(156, 163, 175),
+            edge: ThemeColor::rgb(148, 163, 184),
+            selected: ThemeColor::rgb(250, 204, 21),
+            selected_header: ThemeColor::rgb(234, 179, 8),
+            label_fill: ThemeColor::premultiplied(17, 24, 39, 235),
+            label_border: ThemeColor::rgb(75, 85, 99),
+            label_text: ThemeColor::rgb(243, 244, 246),
+            label_type_text: ThemeColor::rgb(156, 163, 175),
+            label_pointer: ThemeColor::rgb(107, 114, 128),
+            title_fill: ThemeColor::rgb(37, 99, 235),
+            title_drag_fill: ThemeColor::rgb(29, 78, 216),
+            title_border: ThemeColor::rgb(148, 163, 184),
+            title_text: ThemeColor::rgb(255, 255, 255),
+            heading_text: ThemeColor::rgb(243, 244, 246),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme {
+            name: "high-contrast".to_string(),
+            background: ThemeColor::rgb(0, 0, 0),
+            table_fill: ThemeColor::rgb(0, 0, 0),
+            table_border: ThemeColor::rgb(255, 255, 255),
+            table_shadow: ThemeColor::premultiplied(0, 0, 0, 0),
+            header_fill: ThemeColor::rgb(255, 255, 255),
+            header_text: ThemeColor::rgb(0, 0, 0),
+            column_text: ThemeColor::rgb(255, 255, 255),
+            type_text: ThemeColor::rgb(0, 255, 255),
+            edge: ThemeColor::rgb(255, 255, 255),
+            selected: ThemeColor::rgb(255, 255, 0),
+            selected_header: ThemeColor::rgb(255, 255, 0),
+            label_fill: ThemeColor::rgb(0, 0, 0),
+            label_border: ThemeColor::rgb(255, 255, 255),
+            label_text: ThemeColor::rgb(255, 255, 255),
+            label_type_text: ThemeColor::rgb(255, 255, 0),
+            label_pointer: ThemeColor::rgb(255, 255, 255),
+            title_fill: ThemeColor::rgb(0, 0, 0),
+            title_drag_fill: ThemeColor::rgb(64, 64, 64),
+            title_border: ThemeColor::rgb(255, 255, 255),
+            title_text: ThemeColor::rgb(255, 255, 255),
+            heading_text: ThemeColor::rgb(255, 255, 255),
+        }
+    }
+
+    pub fn built_in() -> Vec<Theme> {
+        vec![Theme::light(), Theme::dark(), Theme::high_contrast()]
+    }
+
+    pub fn by_name(name: &str) -> Option<Theme> {
+        Theme::built_in()
+            .into_iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Resolve a `--theme` value: a built-in name or a path to a TOML theme file
+    pub fn resolve(value: &str) -> Result<Theme, String> {
+        if let Some(theme) = Theme::by_name(value) {
+            return Ok(theme);
+        }
+        if value.ends_with(".toml") || Path::new(value).exists() {
+            return Theme::load(Path::new(value));
+        }
+        Err(format!(
+            "Unknown theme '{}' (expected light, dark, high-contrast or a .toml file)",
+            value
+        ))
+    }
+
+    /// Load a user theme from a TOML file
+    pub fn load(path: &Path) -> Result<Theme, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read theme '{}': {}", path.display(), e))?;
+        let fallback_name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "custom".to_string());
+        Theme::from_toml(&content, &fallback_name)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Parse a theme from TOML.
+    ///
+    /// Supported keys are `name`, `base` (a built-in theme to start from) and one
+    /// key per colour field, either at the top level or in a `[colors]` table.
+    pub fn from_toml(content: &str, fallback_name: &str) -> Result<Theme, String> {
+        let mut entries = Vec::new();
+        let mut section = String::new();
+
+        for (line_no, raw_line) in content.lines().enumerate() {
+            let line = strip_toml_comment(raw_line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                if !line.ends_with(']') {
+                    return Err(format!("line {}: malformed table header", line_no + 1));
+                }
+                section = line[1..line.len() - 1].trim().to_string();
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected key = \"value\"", line_no + 1))?;
+            let value = value.trim();
+            if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+                return Err(format!("line {}: value must be a quoted string", line_no + 1));
+            }
+            entries.push((
+                line_no + 1,
+                section.clone(),
+                key.trim().to_string(),
+                value[1..value.len() - 1].to_string(),
+            ));
+        }
+
+        // Resolve the base theme first so the order of keys doesn't matter
+        let mut theme = match entries.iter().find(|(_, s, k, _)| s.is_empty() && k == "base") {
+            Some((line_no, _, _, base)) => Theme::by_name(base)
+                .ok_or_else(|| format!("line {}: unknown base theme '{}'", line_no, base))?,
+            None => Theme::light(),
+        };
+        theme.name = fallback_name.to_string();
+
+        for (line_no, section, key, value) in entries {
+            match (section.as_str(), key.as_str()) {
+                ("", "name") => theme.name = value,
+                ("", "base") => {}
+                ("", _) | ("colors", _) => {
+                    let color = ThemeColor::from_hex(&value)
+                        .ok_or_else(|| format!("line {}: invalid colour '{}'", line_no, value))?;
+                    let slot = theme
+                        .color_mut(&key)
+                        .ok_or_else(|| format!("line {}: unknown colour key '{}'", line_no, key))?;
+                    *slot = color;
+                }
+                _ => return Err(format!("line {}: unknown table [{}]", line_no, section)),
+            }
+        }
+
+        Ok(theme)
+    }
+
+    fn color_mut(&mut self, key: &str) -> Option<&mut ThemeColor> {
+        let slot = match key {
+            "background" => &mut self.background,
+            "table_fill" => &mut self.table_fill,
+            "table_border" => &mut self.table_border,
+            "table_shadow" => &mut self.table_shadow,
+            "header_fill" => &mut self.header_fill,
+            "header_text" => &mut self.header_text,
+            "column_text" => &mut self.column_text,
+            "type_text" => &mut self.type_text,
+            "edge" => &mut self.edge,
+            "selected" => &mut self.selected,
+            "selected_header" => &mut self.selected_header,
+            "label_fill" => &mut self.label_fill,
+            "label_border" => &mut self.label_border,
+            "label_text" => &mut self.label_text,
+            "label_type_text" => &mut self.label_type_text,
+            "label_pointer" => &mut self.label_pointer,
+            "title_fill" => &mut self.title_fill,
+            "title_drag_fill" => &mut self.title_drag_fill,
+            "title_border" => &mut self.title_border,
+            "title_text" => &mut self.title_text,
+            "heading_text" => &mut self.heading_text,
+            _ => return None,
+        };
+        Some(slot)
+    }
+
+    /// Directory scanned for user themes (`$XDG_CONFIG_HOME/free-erd/themes`)
+    pub fn user_theme_dir() -> Option<PathBuf> {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+        Some(config_home.join("free-erd").join("themes"))
+    }
+
+    /// Load every `.toml` theme in the user theme directory, skipping broken files
+    pub fn load_user_themes() -> Vec<Theme> {
+        let mut themes = Vec::new();
+        let Some(dir) = Theme::user_theme_dir() else {
+            return themes;
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return themes;
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().map(|ext| ext == "toml").unwrap_or(false))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            match Theme::load(&path) {
+                Ok(theme) => themes.push(theme),
+                Err(e) => eprintln!("⚠️  Skipping theme: {}", e),
+            }
+        }
+        themes
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
+
+fn strip_toml_comment(line: &str) -> &str {
+    // A '#' inside a quoted value is a colour, not a comment
+    let mut in_string = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_from_hex() {
+        assert_eq!(ThemeColor::from_hex("#3498db"), Some(ThemeColor::rgb(52, 152, 219)));
+        assert_eq!(ThemeColor::from_hex("#fff"), Some(ThemeColor::rgb(255, 255, 255)));
+        assert_eq!(ThemeColor::from_hex("#00000080"), Some(ThemeColor::rgba(0, 0, 0, 128)));
+        assert_eq!(ThemeColor::from_hex("3498db"), None);
+        assert_eq!(ThemeColor::from_hex("#12345"), None);
+        assert_eq!(ThemeColor::from_hex("#aééb"), None);
+        assert_eq!(ThemeColor::from_hex("#aabbccé"), None);
+        assert_eq!(ThemeColor::from_hex("#+abcde"), None);
+    }
+
+    #[test]
+    fn test_color_to_svg() {
+        assert_eq!(ThemeColor::rgb(52, 152, 219).to_svg(), "#3498db");
+        assert_eq!(ThemeColor::rgba(0, 0, 0, 51).to_svg(), "rgba(0, 0, 0, 0.20)");
+    }
+
+    #[test]
+    fn test_built_in_lookup() {
+        assert_eq!(Theme::by_name("dark").unwrap().name, "dark");
+        assert_eq!(Theme::by_name("High-Contrast").unwrap().name, "high-contrast");
+        assert!(Theme::by_name("solarized").is_none());
+        assert!(Theme::resolve("solarized").is_err());
+    }
+
+    #[test]
+    fn test_theme_from_toml() {
+        let toml = r##"
+            # Docs site theme
+            name = "docs"
+            base = "dark"
+
+            [colors]
+            header_fill = "#ff6600"  # brand orange
+            edge = "#ccc"
+        "##;
+        let theme = Theme::from_toml(toml, "fallback").unwrap();
+
+        assert_eq!(theme.name, "docs");
+        assert_eq!(theme.header_fill, ThemeColor::rgb(255, 102, 0));
+        assert_eq!(theme.edge, ThemeColor::rgb(204, 204, 204));
+        assert_eq!(theme.background, Theme::dark().background);
+    }
+
+    #[test]
+    fn test_theme_from_toml_errors() {
+        assert!(Theme::from_toml(r#"header_fill = "blue""#, "t").is_err());
+        assert!(Theme::from_toml(r##"not_a_color = "#fff""##, "t").is_err());
+        assert!(Theme::from_toml(r#"base = "sepia""#, "t").is_err());
+        assert!(Theme::from_toml("edge = #fff", "t").is_err());
+        assert_eq!(Theme::from_toml("", "empty").unwrap().name, "empty");
+    }
+}
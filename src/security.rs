@@ -141,7 +141,7 @@ impl SecurityValidator {
         
         for rel in &schema.relationships {
             graph.entry(rel.from_table.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(rel.to_table.clone());
         }
 
@@ -152,19 +152,17 @@ impl SecurityValidator {
 
         // Check each table as potential starting point
         for table in &schema.tables {
-            if !visited.contains(&table.name) {
-                if Self::dfs_cycle_detect(
-                    &table.name,
-                    &graph,
-                    &mut visited,
-                    &mut rec_stack,
-                    &mut path,
-                    0
-                )? {
-                    return Err(SecurityError::CyclicRelationship(
-                        path.join(" -> ")
-                    ));
-                }
+            if !visited.contains(&table.name) && Self::dfs_cycle_detect(
+                &table.name,
+                &graph,
+                &mut visited,
+                &mut rec_stack,
+                &mut path,
+                0
+            )? {
+                return Err(SecurityError::CyclicRelationship(
+                    path.join(" -> ")
+                ));
             }
         }
 
@@ -244,10 +242,13 @@ mod tests {
                             span: None,
                         }
                     ],
+                    attributes: vec![],
                     span: None,
                 }
             ],
             relationships: vec![],
+            nodes: vec![],
+            edges: vec![],
         };
 
         assert!(SecurityValidator::validate(&schema).is_ok());
@@ -260,6 +261,7 @@ mod tests {
             tables.push(Table {
                 name: format!("Table{}", i),
                 columns: vec![],
+                attributes: vec![],
                 span: None,
             });
         }
@@ -268,6 +270,8 @@ mod tests {
             title: None,
             tables,
             relationships: vec![],
+            nodes: vec![],
+            edges: vec![],
         };
 
         assert!(matches!(
@@ -284,10 +288,13 @@ mod tests {
                 Table {
                     name: "Invalid-Name!".to_string(),
                     columns: vec![],
+                    attributes: vec![],
                     span: None,
                 }
             ],
             relationships: vec![],
+            nodes: vec![],
+            edges: vec![],
         };
 
         assert!(matches!(
@@ -301,8 +308,8 @@ mod tests {
         let schema = Schema {
             title: None,
             tables: vec![
-                Table { name: "A".to_string(), columns: vec![], span: None },
-                Table { name: "B".to_string(), columns: vec![], span: None },
+                Table { name: "A".to_string(), columns: vec![], attributes: vec![], span: None },
+                Table { name: "B".to_string(), columns: vec![], attributes: vec![], span: None },
             ],
             relationships: vec![
                 Relationship {
@@ -322,6 +329,8 @@ mod tests {
                     span: None,
                 },
             ],
+            nodes: vec![],
+            edges: vec![],
         };
 
         assert!(matches!(
@@ -0,0 +1,447 @@
+// Sample data: type-correct fake rows for every table, filled in dependency
+// order so foreign keys always point at rows that exist
+use crate::ast::*;
+use crate::codegen::GeneratedFile;
+use crate::sql::{self, Dialect, ForeignKey};
+use chrono::{Duration, NaiveDate};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+/// Share of rows left NULL in nullable columns
+const NULL_CHANCE: f64 = 0.1;
+/// Tries at a fresh value for a unique column or composite key before giving up
+const ATTEMPTS: usize = 100;
+/// Rows per INSERT statement
+const BATCH: usize = 100;
+
+const FIRST_NAMES: &[&str] = &["Ada", "Alan", "Grace", "Linus", "Margaret", "Ken", "Barbara", "Dennis", "Frances", "Edsger", "Radia", "Tim"];
+const LAST_NAMES: &[&str] = &["Lovelace", "Turing", "Hopper", "Torvalds", "Hamilton", "Thompson", "Liskov", "Ritchie", "Allen", "Dijkstra", "Perlman", "Lee"];
+const WORDS: &[&str] = &["lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed", "do", "eiusmod", "tempor"];
+
+/// One generated cell
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Int(i64),
+    /// Floats and decimals, already formatted
+    Number(String),
+    Bool(bool),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl Value {
+    fn sql(&self, dialect: Dialect) -> String {
+        match self {
+            Value::Null => "NULL".to_string(),
+            Value::Int(n) => n.to_string(),
+            Value::Number(n) => n.clone(),
+            Value::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+            Value::Text(s) => sql::sql_literal(&DefaultValue::String(s.clone())),
+            Value::Bytes(bytes) => match dialect {
+                Dialect::Postgres => format!("'\\x{}'", hex(bytes)),
+                Dialect::MySql => format!("X'{}'", hex(bytes)),
+            },
+        }
+    }
+
+    /// CSV field; NULL is an empty field
+    fn csv(&self) -> String {
+        match self {
+            Value::Null => String::new(),
+            Value::Int(n) => n.to_string(),
+            Value::Number(n) => n.clone(),
+            Value::Bool(b) => b.to_string(),
+            Value::Text(s) => csv_field(s),
+            Value::Bytes(bytes) => hex(bytes),
+        }
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Generated rows of one table, cells in column order
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableData {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    /// Auto-increment integer columns, whose sequences need moving past the rows
+    pub serials: Vec<String>,
+}
+
+impl TableData {
+    fn column_values(&self, column: &str) -> Vec<Value> {
+        let Some(idx) = self.columns.iter().position(|c| c == column) else { return Vec::new() };
+        self.rows.iter().map(|row| row[idx].clone()).filter(|v| *v != Value::Null).collect()
+    }
+}
+
+/// Sample rows for a whole schema, tables in dependency order
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeedData {
+    pub tables: Vec<TableData>,
+    /// Constraints that could not be honoured, e.g. a unique boolean
+    pub warnings: Vec<String>,
+}
+
+struct Generator {
+    rng: StdRng,
+    rows: usize,
+    fks: Vec<ForeignKey>,
+    tables: Vec<TableData>,
+    warnings: Vec<String>,
+}
+
+impl Generator {
+    fn warn(&mut self, message: String) {
+        if !self.warnings.contains(&message) {
+            self.warnings.push(message);
+        }
+    }
+
+    fn pick<'a>(&mut self, options: &[&'a str]) -> &'a str {
+        options[self.rng.gen_range(0..options.len())]
+    }
+
+    fn date(&mut self) -> NaiveDate {
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap_or_default();
+        start + Duration::days(self.rng.gen_range(0..6 * 365))
+    }
+
+    fn time(&mut self) -> String {
+        format!("{:02}:{:02}:{:02}", self.rng.gen_range(0..24), self.rng.gen_range(0..60), self.rng.gen_range(0..60))
+    }
+
+    /// Text shaped by the column name. Unique columns get the row number
+    /// worked in, so they never repeat.
+    fn text(&mut self, column: &str, row: usize, unique: bool) -> String {
+        let name = column.to_lowercase();
+        let first = self.pick(FIRST_NAMES);
+        let last = self.pick(LAST_NAMES);
+        let suffix = if unique { (row + 1).to_string() } else { String::new() };
+        if name.contains("email") {
+            return format!("{}.{}{}@example.com", first.to_lowercase(), last.to_lowercase(), suffix);
+        }
+        if name.contains("url") || name.contains("website") {
+            return format!("https://example.com/{}{}", self.pick(WORDS), suffix);
+        }
+        if name.contains("phone") {
+            let number = if unique { row + 1 } else { self.rng.gen_range(0..10000) };
+            return format!("+1-555-{:04}", number);
+        }
+        let text = if name.contains("first") && name.contains("name") {
+            first.to_string()
+        } else if name.contains("last") && name.contains("name") {
+            last.to_string()
+        } else if name.contains("name") {
+            format!("{} {}", first, last)
+        } else {
+            let count = self.rng.gen_range(2..=5);
+            (0..count).map(|_| self.pick(WORDS)).collect::<Vec<_>>().join(" ")
+        };
+        if unique { format!("{} {}", text, suffix) } else { text }
+    }
+
+    /// A random value of the column's type
+    fn fake(&mut self, column: &Column, row: usize, unique: bool) -> Value {
+        match &column.datatype {
+            DataType::Int if unique => Value::Int(self.rng.gen_range(1..=(self.rows as i64 * 10).max(1000))),
+            DataType::Int => Value::Int(self.rng.gen_range(0..=1000)),
+            DataType::Bool => Value::Bool(self.rng.gen_bool(0.5)),
+            DataType::Double | DataType::Float => Value::Number(format!("{:.2}", self.rng.gen_range(0.0..1000.0))),
+            DataType::Decimal => Value::Number(format!("{}.{:02}", self.rng.gen_range(0..10000), self.rng.gen_range(0..100))),
+            DataType::Date => Value::Text(self.date().to_string()),
+            DataType::Time => Value::Text(self.time()),
+            DataType::DateTime => {
+                let date = self.date();
+                Value::Text(format!("{} {}", date, self.time()))
+            }
+            DataType::Blob | DataType::TinyBlob | DataType::LargeBlob => {
+                let len = match column.datatype { DataType::TinyBlob => 8, DataType::LargeBlob => 32, _ => 16 };
+                Value::Bytes((0..len).map(|_| self.rng.gen()).collect())
+            }
+            DataType::String | DataType::Custom(_) => Value::Text(self.text(&column.name, row, unique)),
+        }
+    }
+
+    /// The column's default. `now` becomes a generated time so that runs
+    /// with the same seed match.
+    fn default(&mut self, column: &Column, value: &DefaultValue) -> Value {
+        match value {
+            DefaultValue::Now => match column.datatype {
+                DataType::Date | DataType::Time => self.fake(column, 0, false),
+                _ => self.fake(&Column { datatype: DataType::DateTime, ..column.clone() }, 0, false),
+            },
+            DefaultValue::True => Value::Bool(true),
+            DefaultValue::False => Value::Bool(false),
+            DefaultValue::Null => Value::Null,
+            DefaultValue::String(s) => Value::Text(s.clone()),
+            DefaultValue::Number(n) => Value::Int(*n),
+        }
+    }
+
+    /// Value of a column that isn't a foreign key
+    fn value(&mut self, table: &Table, column: &Column, row: usize, composite: bool, seen: &HashSet<String>) -> Value {
+        let sequential = column.attributes.contains(&Attribute::AutoIncrement)
+            || (column.is_primary_key() && !composite && column.datatype == DataType::Int);
+        if sequential {
+            return Value::Int(row as i64 + 1);
+        }
+        if sql::is_nullable(column) && self.rng.gen_bool(NULL_CHANCE) {
+            return Value::Null;
+        }
+        let unique = (column.is_primary_key() && !composite) || column.attributes.contains(&Attribute::Unique);
+        if let Some(default) = sql::default_value(column).filter(|_| !unique) {
+            return self.default(column, default);
+        }
+
+        let mut value = self.fake(column, row, unique);
+        for _ in 0..ATTEMPTS {
+            if !unique || !seen.contains(&value.csv()) {
+                return value;
+            }
+            value = self.fake(column, row, unique);
+        }
+        self.warn(format!("{}.{} could not be kept unique with {} rows", table.name, column.name, self.rows));
+        value
+    }
+
+    /// A foreign key value taken from a parent row. Self-references point
+    /// at earlier rows; the first row points at itself unless it can be NULL.
+    fn reference(&mut self, fk: &ForeignKey, column: &Column, data: &TableData, row: &[Value], pool: &mut Option<Vec<Value>>) -> Value {
+        let nullable = sql::is_nullable(column);
+        if nullable && self.rng.gen_bool(NULL_CHANCE) {
+            return Value::Null;
+        }
+        let parents = if fk.ref_table == data.name {
+            let mut values = data.column_values(&fk.ref_column);
+            if values.is_empty() && !nullable {
+                if let Some(idx) = data.columns.iter().position(|c| *c == fk.ref_column) {
+                    values.push(row[idx].clone());
+                }
+            }
+            values
+        } else {
+            self.tables.iter()
+                .find(|t| t.name == fk.ref_table)
+                .map(|t| t.column_values(&fk.ref_column))
+                .unwrap_or_default()
+        };
+        if parents.is_empty() {
+            if !nullable {
+                self.warn(format!("{}.{} has no {} rows to reference", data.name, column.name, fk.ref_table));
+            }
+            return Value::Null;
+        }
+
+        // A unique reference uses each parent once, in shuffled order
+        let unique = column.is_primary_key() || column.attributes.contains(&Attribute::Unique);
+        if unique {
+            let pool = pool.get_or_insert_with(|| {
+                let mut values = parents.clone();
+                values.shuffle(&mut self.rng);
+                values
+            });
+            if let Some(value) = pool.pop() {
+                return value;
+            }
+            if nullable {
+                return Value::Null;
+            }
+            self.warn(format!("{}.{} is unique but {} has fewer rows", data.name, column.name, fk.ref_table));
+        }
+        parents[self.rng.gen_range(0..parents.len())].clone()
+    }
+
+    fn table(&mut self, table: &Table) -> TableData {
+        let mut data = TableData {
+            name: table.name.clone(),
+            columns: table.columns.iter().map(|c| c.name.clone()).collect(),
+            rows: Vec::new(),
+            serials: table.columns.iter()
+                .filter(|c| c.datatype == DataType::Int && c.attributes.contains(&Attribute::AutoIncrement))
+                .map(|c| c.name.clone())
+                .collect(),
+        };
+        let fks: Vec<Option<ForeignKey>> = table.columns.iter()
+            .map(|c| self.fks.iter().find(|fk| fk.table == table.name && fk.column == c.name).cloned())
+            .collect();
+        let keys: Vec<usize> = (0..table.columns.len()).filter(|&i| table.columns[i].is_primary_key()).collect();
+        let composite = keys.len() > 1;
+        for (column, fk) in table.columns.iter().zip(&fks) {
+            if let (DataType::Custom(name), None, None) = (&column.datatype, fk, sql::default_value(column)) {
+                self.warn(format!("{}.{} has custom type '{}' and is filled with text", table.name, column.name, name));
+            }
+        }
+
+        let mut seen: Vec<HashSet<String>> = vec![HashSet::new(); table.columns.len()];
+        let mut seen_keys: HashSet<Vec<String>> = HashSet::new();
+        let mut pools: HashMap<usize, Option<Vec<Value>>> = HashMap::new();
+        for row_index in 0..self.rows {
+            let mut accepted = None;
+            for _ in 0..ATTEMPTS {
+                // Plain columns first, so a self-reference can point at its own row
+                let mut row: Vec<Value> = table.columns.iter().zip(&fks).enumerate()
+                    .map(|(i, (column, fk))| match fk {
+                        Some(_) => Value::Null,
+                        None => self.value(table, column, row_index, composite, &seen[i]),
+                    })
+                    .collect();
+                for (i, (column, fk)) in table.columns.iter().zip(&fks).enumerate() {
+                    if let Some(fk) = fk {
+                        let pool = pools.entry(i).or_default();
+                        row[i] = self.reference(fk, column, &data, &row, pool);
+                    }
+                }
+                let key: Vec<String> = keys.iter().map(|&i| row[i].csv()).collect();
+                if !composite || seen_keys.insert(key) {
+                    accepted = Some(row);
+                    break;
+                }
+            }
+            let Some(row) = accepted else {
+                self.warn(format!("{} has only {} distinct primary keys; generated {} of {} rows", table.name, data.rows.len(), data.rows.len(), self.rows));
+                break;
+            };
+            for (i, value) in row.iter().enumerate() {
+                seen[i].insert(value.csv());
+            }
+            data.rows.push(row);
+        }
+        data
+    }
+}
+
+impl SeedData {
+    /// `rows` rows per table. The same schema, row count and seed always
+    /// give the same data.
+    pub fn generate(schema: &Schema, rows: usize, seed: u64) -> Self {
+        let mut generator = Generator {
+            rng: StdRng::seed_from_u64(seed),
+            rows,
+            fks: sql::foreign_keys(schema),
+            tables: Vec::new(),
+            warnings: Vec::new(),
+        };
+        for name in sql::dependency_order(schema) {
+            let Some(table) = schema.tables.iter().find(|t| t.name == name) else { continue };
+            let data = generator.table(table);
+            generator.tables.push(data);
+        }
+        SeedData { tables: generator.tables, warnings: generator.warnings }
+    }
+
+    /// INSERT statements in one transaction, parents before children.
+    /// Postgres sequences are moved past the inserted ids.
+    pub fn to_sql(&self, dialect: Dialect) -> String {
+        let mut output = String::from("-- Sample data generated by FreeERD\nBEGIN;\n");
+        for table in &self.tables {
+            if table.rows.is_empty() {
+                continue;
+            }
+            let columns = table.columns.iter().map(|c| dialect.quote(c)).collect::<Vec<_>>().join(", ");
+            for batch in table.rows.chunks(BATCH) {
+                output.push_str(&format!("\nINSERT INTO {} ({}) VALUES\n", dialect.quote(&table.name), columns));
+                let rows: Vec<String> = batch.iter()
+                    .map(|row| format!("  ({})", row.iter().map(|v| v.sql(dialect)).collect::<Vec<_>>().join(", ")))
+                    .collect();
+                output.push_str(&rows.join(",\n"));
+                output.push_str(";\n");
+            }
+            if dialect == Dialect::Postgres {
+                for serial in &table.serials {
+                    output.push_str(&format!(
+                        "SELECT setval(pg_get_serial_sequence({}, {}), {});\n",
+                        sql::sql_literal(&DefaultValue::String(dialect.quote(&table.name))),
+                        sql::sql_literal(&DefaultValue::String(serial.clone())),
+                        table.rows.len()
+                    ));
+                }
+            }
+        }
+        output.push_str("\nCOMMIT;\n");
+        output
+    }
+
+    /// One `<table>.csv` per table with a header row
+    pub fn to_csv(&self) -> Vec<GeneratedFile> {
+        self.tables.iter()
+            .map(|table| {
+                let mut content = table.columns.iter().map(|c| csv_field(c)).collect::<Vec<_>>().join(",");
+                content.push('\n');
+                for row in &table.rows {
+                    content.push_str(&row.iter().map(Value::csv).collect::<Vec<_>>().join(","));
+                    content.push('\n');
+                }
+                GeneratedFile::new(format!("{}.csv", table.name), content)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_seed_rows() {
+        let schema = Parser::new(r#"
+table Orders {
+  id: int [pk, autoincrement],
+  user_id: int [fk],
+  note: str [nullable],
+  status: str [default="new"]
+}
+table Users {
+  id: int [pk, autoincrement],
+  email: str [unique],
+  manager_id: int [fk, nullable]
+}
+Users.id > Orders.user_id
+Users.id > Users.manager_id
+"#).parse().unwrap();
+
+        let data = SeedData::generate(&schema, 50, 7);
+        assert_eq!(data, SeedData::generate(&schema, 50, 7));
+        assert!(data.warnings.is_empty());
+        assert_eq!(data.tables.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), ["Users", "Orders"]);
+
+        let users = &data.tables[0];
+        let orders = &data.tables[1];
+        assert_eq!(users.rows[49][0], Value::Int(50));
+        let emails: HashSet<String> = users.rows.iter().map(|r| r[1].csv()).collect();
+        assert_eq!(emails.len(), 50);
+        assert!(orders.rows.iter().all(|r| matches!(r[1], Value::Int(1..=50))));
+        assert!(orders.rows.iter().any(|r| r[2] == Value::Null));
+        assert!(orders.rows.iter().all(|r| r[3] == Value::Text("new".to_string())));
+        // Managers are always earlier users
+        assert!(users.rows.iter().all(|r| match (&r[0], &r[2]) {
+            (Value::Int(id), Value::Int(manager)) => manager < id,
+            (_, other) => *other == Value::Null,
+        }));
+
+        let sql = data.to_sql(Dialect::Postgres);
+        assert!(sql.contains("INSERT INTO \"Users\" (\"id\", \"email\", \"manager_id\") VALUES\n  (1, '"));
+        assert!(sql.contains("SELECT setval(pg_get_serial_sequence('\"Orders\"', 'id'), 50);\n"));
+        assert!(sql.find("INSERT INTO \"Users\"") < sql.find("INSERT INTO \"Orders\""));
+
+        let csv = data.to_csv();
+        assert_eq!(csv[1].path, std::path::PathBuf::from("Orders.csv"));
+        assert!(csv[1].content.starts_with("id,user_id,note,status\n1,"));
+        assert_eq!(csv[1].content.lines().count(), 51);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::ast::*;
+use std::collections::HashMap;
+
+/// SQL flavour used when generating statements
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+}
+
+impl Dialect {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "postgres" | "postgresql" | "pg" => Some(Dialect::Postgres),
+            "mysql" | "mariadb" => Some(Dialect::MySql),
+            _ => None,
+        }
+    }
+
+    pub fn quote(&self, ident: &str) -> String {
+        match self {
+            Dialect::Postgres => format!("\"{}\"", ident.replace('"', "\"\"")),
+            Dialect::MySql => format!("`{}`", ident.replace('`', "``")),
+        }
+    }
+
+    /// Column type for a datatype. Auto-increment integers use the dialect's serial form.
+    pub fn column_type(&self, datatype: &DataType, autoincrement: bool) -> String {
+        let name = match (self, datatype) {
+            (Dialect::Postgres, DataType::Int) if autoincrement => "SERIAL",
+            (Dialect::Postgres, DataType::String) => "TEXT",
+            (Dialect::MySql, DataType::String) => "VARCHAR(255)",
+            (Dialect::Postgres, DataType::Int) => "INTEGER",
+            (Dialect::MySql, DataType::Int) => "INT",
+            (_, DataType::Bool) => "BOOLEAN",
+            (Dialect::Postgres, DataType::Double) => "DOUBLE PRECISION",
+            (Dialect::MySql, DataType::Double) => "DOUBLE",
+            (Dialect::Postgres, DataType::Float) => "REAL",
+            (Dialect::MySql, DataType::Float) => "FLOAT",
+            (Dialect::Postgres, DataType::Decimal) => "NUMERIC",
+            (Dialect::MySql, DataType::Decimal) => "DECIMAL(18, 4)",
+            (_, DataType::Date) => "DATE",
+            (_, DataType::Time) => "TIME",
+            (Dialect::Postgres, DataType::DateTime) => "TIMESTAMP",
+            (Dialect::MySql, DataType::DateTime) => "DATETIME",
+            (Dialect::Postgres, DataType::Blob | DataType::TinyBlob | DataType::LargeBlob) => "BYTEA",
+            (Dialect::MySql, DataType::Blob) => "BLOB",
+            (Dialect::MySql, DataType::TinyBlob) => "TINYBLOB",
+            (Dialect::MySql, DataType::LargeBlob) => "LONGBLOB",
+            (_, DataType::Custom(custom)) => return custom.clone(),
+        };
+        let mut name = name.to_string();
+        if autoincrement && *self == Dialect::MySql {
+            name.push_str(" AUTO_INCREMENT");
+        }
+        name
+    }
+
+    /// Column definition as used in CREATE TABLE and ADD COLUMN
+    pub fn column_definition(&self, column: &Column) -> String {
+        let autoincrement = column.attributes.contains(&Attribute::AutoIncrement);
+        let mut definition = format!("{} {}", self.quote(&column.name), self.column_type(&column.datatype, autoincrement));
+        if !is_nullable(column) {
+            definition.push_str(" NOT NULL");
+        }
+        if let Some(default) = default_value(column) {
+            definition.push_str(&format!(" DEFAULT {}", sql_literal(default)));
+        }
+        definition
+    }
+}
+
+pub fn is_nullable(column: &Column) -> bool {
+    column.attributes.contains(&Attribute::Nullable) && !column.is_primary_key()
+}
+
+pub fn default_value(column: &Column) -> Option<&DefaultValue> {
+    column.attributes.iter().find_map(|a| match a {
+        Attribute::Default(value) => Some(value),
+        _ => None,
+    })
+}
+
+pub fn sql_literal(value: &DefaultValue) -> String {
+    match value {
+        DefaultValue::Now => "CURRENT_TIMESTAMP".to_string(),
+        DefaultValue::True => "TRUE".to_string(),
+        DefaultValue::False => "FALSE".to_string(),
+        DefaultValue::Null => "NULL".to_string(),
+        DefaultValue::String(s) => format!("'{}'", s.replace('\'', "''")),
+        DefaultValue::Number(n) => n.to_string(),
+    }
+}
+
+/// A relationship resolved to the column that holds the reference
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForeignKey {
+    pub table: String,
+    pub column: String,
+    pub ref_table: String,
+    pub ref_column: String,
+}
+
+impl ForeignKey {
+    pub fn constraint_name(&self) -> String {
+        format!("fk_{}_{}", self.table, self.column)
+    }
+}
+
+fn find_column<'a>(schema: &'a Schema, table: &str, column: &str) -> Option<&'a Column> {
+    schema.tables.iter()
+        .find(|t| t.name == table)
+        .and_then(|t| t.columns.iter().find(|c| c.name == column))
+}
+
+/// Decide which end of a relationship holds the foreign key. Columns marked
+/// `[fk]` win, then the side that isn't a key or unique; otherwise the
+/// arrow decides (`>` puts the many side on the right). Many-to-many
+/// relationships need a junction table and have no single foreign key.
+pub fn foreign_key(schema: &Schema, rel: &Relationship) -> Option<ForeignKey> {
+    if rel.relationship_type == RelationshipType::ManyToMany {
+        return None;
+    }
+    let from = find_column(schema, &rel.from_table, &rel.from_field)?;
+    let to = find_column(schema, &rel.to_table, &rel.to_field)?;
+    let is_key = |c: &Column| c.is_primary_key() || c.attributes.contains(&Attribute::Unique);
+
+    let from_holds = match (from.is_foreign_key(), to.is_foreign_key()) {
+        (true, false) => true,
+        (false, true) => false,
+        _ => match (is_key(from), is_key(to)) {
+            (false, true) => true,
+            (true, false) => false,
+            _ => rel.relationship_type == RelationshipType::ManyToOne,
+        },
+    };
+
+    Some(if from_holds {
+        ForeignKey {
+            table: rel.from_table.clone(),
+            column: rel.from_field.clone(),
+            ref_table: rel.to_table.clone(),
+            ref_column: rel.to_field.clone(),
+        }
+    } else {
+        ForeignKey {
+            table: rel.to_table.clone(),
+            column: rel.to_field.clone(),
+            ref_table: rel.from_table.clone(),
+            ref_column: rel.from_field.clone(),
+        }
+    })
+}
+
+pub fn foreign_keys(schema: &Schema) -> Vec<ForeignKey> {
+    schema.relationships.iter().filter_map(|rel| foreign_key(schema, rel)).collect()
+}
+
+/// Table names ordered so referenced tables come before the tables that
+/// reference them. Tables in a reference cycle keep their schema order at
+/// the end; self-references are ignored.
+pub fn dependency_order(schema: &Schema) -> Vec<String> {
+    let fks = foreign_keys(schema);
+    let mut pending: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+    let mut remaining_parents: HashMap<&str, usize> = pending.iter().map(|name| {
+        let parents = fks.iter()
+            .filter(|fk| fk.table == *name && fk.ref_table != *name)
+            .map(|fk| fk.ref_table.as_str())
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        (*name, parents)
+    }).collect();
+
+    let mut order = Vec::new();
+    while let Some(pos) = pending.iter().position(|name| remaining_parents[name] == 0) {
+        let name = pending.remove(pos);
+        order.push(name.to_string());
+        let children: std::collections::HashSet<&str> = fks.iter()
+            .filter(|fk| fk.ref_table == name && fk.table != name)
+            .map(|fk| fk.table.as_str())
+            .collect();
+        for child in children {
+            if let Some(count) = remaining_parents.get_mut(child) {
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+    order.extend(pending.into_iter().map(str::to_string));
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn schema() -> Schema {
+        Parser::new(r#"
+table Comments {
+  id: int [pk],
+  post_id: int
+}
+table Posts {
+  id: int [pk],
+  user_id: int [fk]
+}
+table Users {
+  id: int [pk, autoincrement],
+  email: str [unique, default="none"],
+  bio: str [nullable]
+}
+Posts.id < Comments.post_id
+Users.id > Posts.user_id
+"#).parse().unwrap()
+    }
+
+    #[test]
+    fn test_foreign_key_side() {
+        let schema = schema();
+        assert_eq!(foreign_keys(&schema), vec![
+            ForeignKey {
+                table: "Comments".to_string(),
+                column: "post_id".to_string(),
+                ref_table: "Posts".to_string(),
+                ref_column: "id".to_string(),
+            },
+            ForeignKey {
+                table: "Posts".to_string(),
+                column: "user_id".to_string(),
+                ref_table: "Users".to_string(),
+                ref_column: "id".to_string(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        assert_eq!(dependency_order(&schema()), vec!["Users", "Posts", "Comments"]);
+    }
+
+    #[test]
+    fn test_column_definitions() {
+        let schema = schema();
+        let users = &schema.tables[2];
+        assert_eq!(Dialect::Postgres.column_definition(&users.columns[0]), "\"id\" SERIAL NOT NULL");
+        assert_eq!(Dialect::MySql.column_definition(&users.columns[0]), "`id` INT AUTO_INCREMENT NOT NULL");
+        assert_eq!(Dialect::Postgres.column_definition(&users.columns[1]), "\"email\" TEXT NOT NULL DEFAULT 'none'");
+        assert_eq!(Dialect::Postgres.column_definition(&users.columns[2]), "\"bio\" TEXT");
+    }
+}
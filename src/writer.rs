@@ -0,0 +1,660 @@
+use crate::ast::*;
+use crate::lexer::{Lexer, Token, Spanned, Span};
+
+/// Render a schema as canonical .frd source
+pub fn to_frd(schema: &Schema) -> Result<String, String> {
+    check_edges(schema)?;
+    let mut blocks: Vec<String> = Vec::new();
+
+    if let Some(title) = &schema.title {
+        blocks.push(format_title(title));
+    }
+    for table in &schema.tables {
+        blocks.push(format_table(table));
+    }
+    for node in &schema.nodes {
+        blocks.push(format_node(node));
+    }
+
+    let edges: Vec<String> = schema.edges.iter().map(format_edge).collect();
+    if !edges.is_empty() {
+        blocks.push(edges.join("\n"));
+    }
+
+    let relationships: Vec<String> = schema.relationships.iter().map(format_relationship).collect();
+    if !relationships.is_empty() {
+        blocks.push(relationships.join("\n"));
+    }
+
+    let mut output = blocks.join("\n\n");
+    output.push('\n');
+    Ok(output)
+}
+
+/// Edges with properties can only be written in the block form, which is
+/// always bidirectional, so a one-way edge with properties has no .frd form
+fn check_edges(schema: &Schema) -> Result<(), String> {
+    let one_way = schema.edges.iter().find(|e| {
+        e.edge_type != EdgeType::Bidirectional && !(e.properties.is_empty() && e.attributes.is_empty())
+    });
+    match one_way {
+        Some(edge) => Err(format!(
+            "Edge {} from {} to {} has properties, so it can only be written as bidirectional",
+            edge.name, edge.from_node, edge.to_node
+        )),
+        None => Ok(()),
+    }
+}
+
+fn format_title(title: &str) -> String {
+    format!("#title \"{}\"", escape(title))
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// String values are quoted with the lexer's escapes, so `"` and `\` survive
+fn format_attribute(attribute: &Attribute) -> String {
+    match attribute {
+        Attribute::Default(DefaultValue::String(s)) => format!("default=\"{}\"", escape(s)),
+        other => other.to_string(),
+    }
+}
+
+fn format_table_attribute(attribute: &TableAttribute) -> String {
+    match attribute {
+        TableAttribute::Icon(icon) => format!("icon=\"{}\"", escape(icon)),
+        other => other.to_string(),
+    }
+}
+
+fn format_attributes(attributes: &[Attribute]) -> String {
+    if attributes.is_empty() {
+        String::new()
+    } else {
+        let attrs: Vec<String> = attributes.iter().map(format_attribute).collect();
+        format!(" [{}]", attrs.join(", "))
+    }
+}
+
+fn format_field(name: &str, datatype: &DataType, attributes: &[Attribute]) -> String {
+    format!("{}: {}{}", name, datatype, format_attributes(attributes))
+}
+
+fn format_table_header(table: &Table) -> String {
+    if table.attributes.is_empty() {
+        format!("table {} {{", table.name)
+    } else {
+        let attrs: Vec<String> = table.attributes.iter().map(format_table_attribute).collect();
+        format!("table {} [{}] {{", table.name, attrs.join(", "))
+    }
+}
+
+fn format_block(header: String, fields: Vec<String>) -> String {
+    if fields.is_empty() {
+        return format!("{}\n}}", header);
+    }
+    format!("{}\n  {}\n}}", header, fields.join(",\n  "))
+}
+
+pub fn format_table(table: &Table) -> String {
+    let fields = table.columns.iter()
+        .map(|c| format_field(&c.name, &c.datatype, &c.attributes))
+        .collect();
+    format_block(format_table_header(table), fields)
+}
+
+pub fn format_node(node: &Node) -> String {
+    let fields = node.fields.iter()
+        .map(|f| format_field(&f.name, &f.datatype, &f.attributes))
+        .collect();
+    format_block(format!("node {} {{", node.name), fields)
+}
+
+pub fn format_edge(edge: &Edge) -> String {
+    // Plain edges use the shorthand form; edges with properties need the block form,
+    // which is always bidirectional (`check_edges` rejects one-way ones first)
+    if edge.properties.is_empty() && edge.attributes.is_empty() {
+        return match edge.edge_type {
+            EdgeType::Outgoing => format!("{} -[{}]-> {}", edge.from_node, edge.name, edge.to_node),
+            EdgeType::Incoming => format!("{} <-[{}]- {}", edge.from_node, edge.name, edge.to_node),
+            EdgeType::Bidirectional => format!("{} <-[{}]-> {}", edge.from_node, edge.name, edge.to_node),
+        };
+    }
+
+    let header = format!(
+        "edge {} (from: {}, to: {}){} {{",
+        edge.name, edge.from_node, edge.to_node, format_attributes(&edge.attributes)
+    );
+    let fields = edge.properties.iter()
+        .map(|p| format_field(&p.name, &p.datatype, &p.attributes))
+        .collect();
+    format_block(header, fields)
+}
+
+pub fn format_relationship(rel: &Relationship) -> String {
+    let operator = match rel.relationship_type {
+        RelationshipType::OneToMany => ">",
+        RelationshipType::ManyToOne => "<",
+        RelationshipType::ManyToMany => "<>",
+        RelationshipType::OneToOne => "-",
+    };
+    format!("{}.{} {} {}.{}", rel.from_table, rel.from_field, operator, rel.to_table, rel.to_field)
+}
+
+/// Top-level statement kinds, indexed into the matching `Schema` vector
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StatementKind {
+    Title,
+    Table(usize),
+    Node(usize),
+    Edge(usize),
+    Relationship(usize),
+}
+
+/// A top-level statement and the (1-based, inclusive) source lines it covers
+#[derive(Debug, Clone, Copy)]
+struct Statement {
+    kind: StatementKind,
+    start_line: usize,
+    end_line: usize,
+}
+
+/// Find the top-level statements in `source`, in the same order the parser reads them
+fn scan_statements(source: &str) -> Vec<Statement> {
+    let tokens: Vec<Spanned<Token>> = Lexer::new(source).tokenize();
+    let mut statements = Vec::new();
+    let (mut tables, mut nodes, mut edges, mut relationships) = (0, 0, 0, 0);
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let start_line = tokens[i].span.line;
+        let (kind, end) = match &tokens[i].value {
+            Token::Title => {
+                // #title "..." is two tokens
+                let end = (i + 1).min(tokens.len() - 1);
+                (StatementKind::Title, end)
+            }
+            Token::Table | Token::Node | Token::Edge => {
+                let kind = match tokens[i].value {
+                    Token::Table => { tables += 1; StatementKind::Table(tables - 1) }
+                    Token::Node => { nodes += 1; StatementKind::Node(nodes - 1) }
+                    _ => { edges += 1; StatementKind::Edge(edges - 1) }
+                };
+                (kind, block_end(&tokens, i))
+            }
+            Token::Identifier(_) => {
+                let is_relationship = matches!(tokens.get(i + 1).map(|t| &t.value), Some(Token::Dot));
+                if is_relationship {
+                    relationships += 1;
+                    (StatementKind::Relationship(relationships - 1), relationship_end(&tokens, i))
+                } else {
+                    edges += 1;
+                    (StatementKind::Edge(edges - 1), line_end(&tokens, i))
+                }
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        statements.push(Statement { kind, start_line, end_line: tokens[end].span.line });
+        i = end + 1;
+    }
+
+    statements
+}
+
+/// Index of the `}` closing the first block after `start`
+fn block_end(tokens: &[Spanned<Token>], start: usize) -> usize {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token.value {
+            Token::LeftBrace => depth += 1,
+            Token::RightBrace => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            Token::Eof => return i,
+            _ => {}
+        }
+    }
+    tokens.len() - 1
+}
+
+/// Index of the last token of `Table.field <op> Table.field`
+fn relationship_end(tokens: &[Spanned<Token>], start: usize) -> usize {
+    let mut seen = 0;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token.value {
+            Token::Newline => continue,
+            Token::Eof | Token::Comment(_) => return i.saturating_sub(1).max(start),
+            _ => {
+                seen += 1;
+                if seen == 7 {
+                    return i;
+                }
+            }
+        }
+    }
+    tokens.len() - 1
+}
+
+/// Index of the last token before the end of the line
+fn line_end(tokens: &[Spanned<Token>], start: usize) -> usize {
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        if matches!(token.value, Token::Newline | Token::Comment(_) | Token::Eof) {
+            return i.saturating_sub(1).max(start);
+        }
+    }
+    tokens.len() - 1
+}
+
+/// The `// comment` at the end of a line, if any (ignoring `//` inside strings)
+fn trailing_comment(line: &str) -> Option<&str> {
+    let mut in_string = None;
+    let mut prev = ' ';
+    for (idx, ch) in line.char_indices() {
+        match in_string {
+            Some(quote) if ch == quote && prev != '\\' => in_string = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => in_string = Some(ch),
+            None if ch == '/' && line[idx + 1..].starts_with('/') => {
+                return Some(line[idx..].trim_end());
+            }
+            None => {}
+        }
+        prev = ch;
+    }
+    None
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Find the item in `edited` that corresponds to `original`: the one with the
+/// same span, or for span-less items (shorthand edges) the one at the same index
+fn find_match<T>(edited: &[T], index: usize, span: Option<Span>, span_of: fn(&T) -> Option<Span>) -> Option<&T> {
+    match span {
+        Some(span) => edited.iter().find(|item| span_of(item) == Some(span)),
+        None => edited.get(index).filter(|item| span_of(item).is_none()),
+    }
+}
+
+/// Items in `edited` that have no counterpart in `original`
+fn new_items<'a, T>(edited: &'a [T], original: &[T], span_of: fn(&T) -> Option<Span>) -> Vec<&'a T> {
+    edited.iter().enumerate().filter(|(idx, item)| match span_of(item) {
+        Some(span) => !original.iter().any(|o| span_of(o) == Some(span)),
+        None => *idx >= original.iter().filter(|o| span_of(o).is_none()).count(),
+    }).map(|(_, item)| item).collect()
+}
+
+/// Re-render a changed table, keeping the comments that sat above or beside
+/// each surviving column and any comments before the closing brace
+fn format_table_preserving(original: &Table, edited: &Table, lines: &[&str], statement: &Statement) -> String {
+    let body_lines = statement.start_line + 1..statement.end_line;
+    let indent = original.columns.first()
+        .and_then(|c| c.span)
+        .and_then(|span| lines.get(span.line - 1))
+        .map(|line| leading_whitespace(line).to_string())
+        .unwrap_or_else(|| "  ".to_string());
+
+    // Comment-only lines directly above each original column
+    let comments_above = |line_no: usize| -> Vec<String> {
+        let mut comments = Vec::new();
+        let mut current = line_no - 1;
+        while body_lines.contains(&current) {
+            let text = lines[current - 1].trim();
+            if !text.starts_with("//") {
+                break;
+            }
+            comments.insert(0, format!("{}{}", indent, text));
+            current -= 1;
+        }
+        comments
+    };
+
+    let mut output = vec![format_table_header(edited)];
+    let count = edited.columns.len();
+
+    for (idx, column) in edited.columns.iter().enumerate() {
+        let original_line = column.span
+            .filter(|span| original.columns.iter().any(|c| c.span == Some(*span)))
+            .map(|span| span.line);
+
+        if let Some(line_no) = original_line {
+            output.extend(comments_above(line_no));
+        }
+
+        let comma = if idx + 1 < count { "," } else { "" };
+        let mut text = format!("{}{}{}", indent, format_field(&column.name, &column.datatype, &column.attributes), comma);
+        if let Some(comment) = original_line.and_then(|line_no| trailing_comment(lines[line_no - 1])) {
+            text.push(' ');
+            text.push_str(comment);
+        }
+        output.push(text);
+    }
+
+    // Comments between the last column and the closing brace
+    let last_column_line = original.columns.iter().filter_map(|c| c.span).map(|s| s.line).max().unwrap_or(statement.start_line);
+    for line_no in last_column_line + 1..statement.end_line {
+        if lines[line_no - 1].trim().starts_with("//") {
+            output.push(format!("{}{}", indent, lines[line_no - 1].trim()));
+        }
+    }
+
+    output.push("}".to_string());
+    output.join("\n")
+}
+
+/// Keep a changed single-line statement's trailing comment
+fn with_trailing_comment(text: String, lines: &[&str], statement: &Statement) -> String {
+    match trailing_comment(lines[statement.end_line - 1]) {
+        Some(comment) if statement.start_line == statement.end_line => format!("{} {}", text, comment),
+        _ => text,
+    }
+}
+
+/// Append a multi-line block, separated from the previous line by one blank line
+fn push_block(output: &mut Vec<String>, block: String) {
+    if output.last().is_some_and(|l| !l.trim().is_empty()) {
+        output.push(String::new());
+    }
+    output.push(block);
+}
+
+/// Write `edited` back over `source` (which parsed to `original`). Untouched
+/// statements, comments, blank lines and ordering are kept as they were;
+/// changed statements are re-rendered in place, deleted ones are removed and
+/// new ones are added after the last statement of the same kind.
+// Only the editor in the viewer saves this way
+#[cfg_attr(not(feature = "viewer"), allow(dead_code))]
+pub fn write_preserving(source: &str, original: &Schema, edited: &Schema) -> Result<String, String> {
+    check_edges(edited)?;
+    let lines: Vec<&str> = source.lines().collect();
+    let statements = scan_statements(source);
+    
+    // Nothing to preserve but comments: write the canonical form after them
+    if statements.is_empty() {
+        let mut text = source.trim_end().to_string();
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str(&to_frd(edited)?);
+        return Ok(text);
+    }
+
+    let new_tables = new_items(&edited.tables, &original.tables, |t| t.span);
+    let new_nodes = new_items(&edited.nodes, &original.nodes, |n| n.span);
+    let new_edges = new_items(&edited.edges, &original.edges, |e| e.span);
+    let new_relationships = new_items(&edited.relationships, &original.relationships, |r| r.span);
+
+    let last_of = |matches: fn(&StatementKind) -> bool| statements.iter().rposition(|s| matches(&s.kind));
+    let last_table = last_of(|k| matches!(k, StatementKind::Table(_)));
+    let last_node = last_of(|k| matches!(k, StatementKind::Node(_)));
+    let last_edge = last_of(|k| matches!(k, StatementKind::Edge(_)));
+    let last_relationship = last_of(|k| matches!(k, StatementKind::Relationship(_)));
+
+    let mut output: Vec<String> = Vec::new();
+    let mut next_line = 1;
+    let mut title_written = false;
+
+    for (stmt_idx, statement) in statements.iter().enumerate() {
+        // Comments and blank lines before this statement
+        for line_no in next_line..statement.start_line {
+            output.push(lines[line_no - 1].to_string());
+        }
+        next_line = statement.end_line + 1;
+
+        let verbatim = || lines[statement.start_line - 1..statement.end_line].join("\n");
+
+        let rendered = match statement.kind {
+            StatementKind::Title => {
+                title_written = true;
+                if edited.title == original.title {
+                    Some(verbatim())
+                } else {
+                    edited.title.as_deref().map(format_title)
+                }
+            }
+            StatementKind::Table(idx) => original.tables.get(idx).and_then(|orig| {
+                find_match(&edited.tables, idx, orig.span, |t| t.span).map(|table| {
+                    if table == orig { verbatim() } else { format_table_preserving(orig, table, &lines, statement) }
+                })
+            }),
+            StatementKind::Node(idx) => original.nodes.get(idx).and_then(|orig| {
+                find_match(&edited.nodes, idx, orig.span, |n| n.span).map(|node| {
+                    if node == orig { verbatim() } else { format_node(node) }
+                })
+            }),
+            StatementKind::Edge(idx) => original.edges.get(idx).and_then(|orig| {
+                find_match(&edited.edges, idx, orig.span, |e| e.span).map(|edge| {
+                    if edge == orig { verbatim() } else { with_trailing_comment(format_edge(edge), &lines, statement) }
+                })
+            }),
+            StatementKind::Relationship(idx) => original.relationships.get(idx).and_then(|orig| {
+                find_match(&edited.relationships, idx, orig.span, |r| r.span).map(|rel| {
+                    if rel == orig { verbatim() } else { with_trailing_comment(format_relationship(rel), &lines, statement) }
+                })
+            }),
+        };
+
+        let deleted = rendered.is_none();
+        output.extend(rendered);
+
+        // New items go after the last statement of their kind
+        if Some(stmt_idx) == last_table {
+            for table in &new_tables {
+                push_block(&mut output, format_table(table));
+            }
+        }
+        if Some(stmt_idx) == last_node {
+            for node in &new_nodes {
+                push_block(&mut output, format_node(node));
+            }
+        }
+        if Some(stmt_idx) == last_edge {
+            output.extend(new_edges.iter().map(|e| format_edge(e)));
+        }
+        if Some(stmt_idx) == last_relationship {
+            output.extend(new_relationships.iter().map(|r| format_relationship(r)));
+        }
+
+        // Don't leave a double blank line where a statement was deleted
+        if deleted {
+            while next_line <= lines.len()
+                && lines[next_line - 1].trim().is_empty()
+                && output.last().is_none_or(|l| l.trim().is_empty())
+            {
+                next_line += 1;
+            }
+        }
+    }
+
+    for line_no in next_line..=lines.len() {
+        output.push(lines[line_no - 1].to_string());
+    }
+
+    // Kinds that had no statements in the file yet go at the end
+    let mut appended: Vec<String> = Vec::new();
+    if !title_written {
+        if let Some(title) = &edited.title {
+            output.insert(0, format_title(title));
+            output.insert(1, String::new());
+        }
+    }
+    if last_table.is_none() {
+        appended.extend(new_tables.iter().map(|t| format_table(t)));
+    }
+    if last_node.is_none() {
+        appended.extend(new_nodes.iter().map(|n| format_node(n)));
+    }
+    if last_edge.is_none() && !new_edges.is_empty() {
+        appended.push(new_edges.iter().map(|e| format_edge(e)).collect::<Vec<_>>().join("\n"));
+    }
+    if last_relationship.is_none() && !new_relationships.is_empty() {
+        appended.push(new_relationships.iter().map(|r| format_relationship(r)).collect::<Vec<_>>().join("\n"));
+    }
+    for block in appended {
+        push_block(&mut output, block);
+    }
+
+    let mut text = output.join("\n");
+    text.push('\n');
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    /// Drop spans so schemas from different sources can be compared
+    fn strip_spans(mut schema: Schema) -> Schema {
+        for table in &mut schema.tables {
+            table.span = None;
+            for column in &mut table.columns {
+                column.span = None;
+            }
+        }
+        for rel in &mut schema.relationships {
+            rel.span = None;
+        }
+        for node in &mut schema.nodes {
+            node.span = None;
+            for field in &mut node.fields {
+                field.span = None;
+            }
+        }
+        for edge in &mut schema.edges {
+            edge.span = None;
+            for prop in &mut edge.properties {
+                prop.span = None;
+            }
+        }
+        schema
+    }
+
+    fn parse(source: &str) -> Schema {
+        Parser::new(source).parse().unwrap()
+    }
+
+    const SOURCE: &str = r##"#title "Shop"
+
+// Customers and their orders
+table Users [color=#3b82f6] {
+  id: int [pk, autoincrement],
+  // contact details
+  email: str [unique], // must be unique
+  name: str
+}
+
+table Orders {
+  id: int [pk],
+  user_id: int [fk]
+}
+
+// Relationships
+Users.id > Orders.user_id // one user, many orders
+"##;
+
+    #[test]
+    fn test_to_frd_round_trip() {
+        let schema = parse(SOURCE);
+        let formatted = to_frd(&schema).unwrap();
+        assert_eq!(strip_spans(parse(&formatted)), strip_spans(schema));
+
+        let graph = parse(include_str!("../examples/test_nodes.frd"));
+        assert_eq!(strip_spans(parse(&to_frd(&graph).unwrap())), strip_spans(graph));
+    }
+
+    #[test]
+    fn test_quotes_and_backslashes_round_trip() {
+        let mut schema = parse("table Files {\n  path: str\n}\n");
+        schema.tables[0].attributes = vec![TableAttribute::Icon("say \"hi\" \\o/".to_string())];
+        schema.tables[0].columns[0].attributes = vec![Attribute::Default(DefaultValue::String("C:\\dir \"q\"".to_string()))];
+
+        let written = to_frd(&schema).unwrap();
+        assert!(written.contains(r#"table Files [icon="say \"hi\" \\o/"] {"#));
+        assert!(written.contains(r#"path: str [default="C:\\dir \"q\""]"#));
+        assert_eq!(strip_spans(parse(&written)), strip_spans(schema));
+    }
+
+    #[test]
+    fn test_one_way_edge_with_properties_is_refused() {
+        let mut graph = parse("node User {\n  id: int\n}\nedge FOLLOWS (from: User, to: User) {\n  since: date\n}\n");
+        assert!(to_frd(&graph).unwrap().contains("edge FOLLOWS (from: User, to: User) {\n  since: date\n}"));
+
+        graph.edges[0].edge_type = EdgeType::Outgoing;
+        assert_eq!(to_frd(&graph).unwrap_err(), "Edge FOLLOWS from User to User has properties, so it can only be written as bidirectional");
+        let original = parse("node User {\n  id: int\n}\n");
+        assert!(write_preserving("node User {\n  id: int\n}\n", &original, &graph).is_err());
+    }
+
+    #[test]
+    fn test_unchanged_schema_is_written_verbatim() {
+        let schema = parse(SOURCE);
+        assert_eq!(write_preserving(SOURCE, &schema, &schema).unwrap(), SOURCE);
+    }
+
+    #[test]
+    fn test_changed_table_keeps_comments() {
+        let original = parse(SOURCE);
+        let mut edited = original.clone();
+        edited.tables[0].name = "Customers".to_string();
+        edited.tables[0].columns[1].datatype = DataType::Custom("citext".to_string());
+        edited.tables[0].columns.push(Column {
+            name: "created_at".to_string(),
+            datatype: DataType::DateTime,
+            attributes: vec![Attribute::Default(DefaultValue::Now)],
+            span: None,
+        });
+        edited.relationships[0].from_table = "Customers".to_string();
+
+        let written = write_preserving(SOURCE, &original, &edited).unwrap();
+        assert!(written.contains("// Customers and their orders\ntable Customers [color=#3b82f6] {"));
+        assert!(written.contains("  // contact details\n  email: citext [unique], // must be unique"));
+        assert!(written.contains("  name: str,\n  created_at: datetime [default=NOW]\n}"));
+        assert!(written.contains("Customers.id > Orders.user_id // one user, many orders"));
+        assert_eq!(strip_spans(parse(&written)), strip_spans(edited));
+    }
+
+    #[test]
+    fn test_added_and_deleted_statements() {
+        let original = parse(SOURCE);
+        let mut edited = original.clone();
+        edited.tables.remove(1);
+        edited.relationships.clear();
+        edited.tables.push(Table {
+            name: "Products".to_string(),
+            columns: vec![Column { name: "id".to_string(), datatype: DataType::Int, attributes: vec![Attribute::PrimaryKey], span: None }],
+            attributes: vec![],
+            span: None,
+        });
+
+        let written = write_preserving(SOURCE, &original, &edited).unwrap();
+        assert!(!written.contains("Orders"));
+        assert!(written.contains("  name: str\n}\n\ntable Products {\n  id: int [pk]\n}\n"));
+        assert!(written.contains("// Relationships"));
+        assert_eq!(strip_spans(parse(&written)), strip_spans(edited));
+    }
+
+    #[test]
+    fn test_empty_source_uses_canonical_form() {
+        let edited = parse(SOURCE);
+        let written = write_preserving("// sketch\n", &Schema::new(), &edited).unwrap();
+        assert!(written.starts_with("// sketch\n\n#title \"Shop\"\n\ntable Users"));
+        assert_eq!(strip_spans(parse(&written)), strip_spans(edited));
+    }
+
+    #[test]
+    fn test_trailing_comment_ignores_strings() {
+        assert_eq!(trailing_comment(r#"  url: str [default="http://x"] // link"#), Some("// link"));
+        assert_eq!(trailing_comment(r#"  url: str [default="http://x"]"#), None);
+    }
+}